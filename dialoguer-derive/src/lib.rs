@@ -0,0 +1,190 @@
+//! Derive macro for the `dialoguer::Prompt` trait.
+//!
+//! This crate is not meant to be used directly; enable the `derive`
+//! feature of dialoguer instead.
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `dialoguer::Prompt` for a struct with named fields or an enum
+/// of unit variants.
+///
+/// Every field of a struct is asked for in order with the `Prompt`
+/// implementation of its type, so strings and numbers become inputs and
+/// bools become confirmations.  An enum is asked for with a selection of
+/// its variants.
+///
+/// Fields and variants are labelled with their humanized name unless
+/// `#[prompt(label = "...")]` is given.  `#[prompt(input)]` asks for a
+/// field with an input prompt parsing the value with `FromStr`.
+#[proc_macro_derive(Prompt, attributes(prompt))]
+pub fn derive_prompt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// The options set with `#[prompt(...)]`.
+#[derive(Default)]
+struct Options {
+    label: Option<String>,
+    input: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Options> {
+        let mut rv = Options::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("prompt")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    rv.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("input") {
+                    rv.input = true;
+                } else {
+                    return Err(meta.error("unsupported prompt option"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(rv)
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let body = match input.data {
+        Data::Struct(ref data) => expand_struct(name, &data.fields)?,
+        Data::Enum(ref data) => {
+            let mut labels = vec![];
+            let mut variants = vec![];
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "only variants without fields can be prompted for",
+                    ));
+                }
+                let options = Options::parse(&variant.attrs)?;
+                labels.push(
+                    options
+                        .label
+                        .unwrap_or_else(|| humanize(&variant.ident.to_string())),
+                );
+                variants.push(&variant.ident);
+            }
+            let indexes = 0..variants.len();
+            quote! {
+                let idx = ::dialoguer::Select::with_theme(theme)
+                    .with_prompt(text)
+                    .items(&[#(#labels),*])
+                    .default(0)
+                    .interact()?;
+                Ok(match idx {
+                    #(#indexes => #name::#variants,)*
+                    _ => unreachable!(),
+                })
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "unions cannot be prompted for",
+            ))
+        }
+    };
+    // spanned at the macro so `dyn` is a keyword for 2015 edition callers
+    let dyn_token = quote_spanned!(Span::mixed_site()=> dyn);
+    Ok(quote! {
+        impl #impl_generics ::dialoguer::Prompt for #name #ty_generics #where_clause {
+            fn prompt_with_theme(
+                theme: &#dyn_token ::dialoguer::theme::Theme,
+                text: &str,
+            ) -> ::dialoguer::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_struct(name: &syn::Ident, fields: &Fields) -> syn::Result<TokenStream2> {
+    let fields = match *fields {
+        Fields::Named(ref fields) => &fields.named,
+        Fields::Unit => return Ok(quote! { let _ = (theme, text); Ok(#name) }),
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "only structs with named fields can be prompted for",
+            ))
+        }
+    };
+    let mut values = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let options = Options::parse(&field.attrs)?;
+        let label = options
+            .label
+            .unwrap_or_else(|| humanize(&ident.to_string()));
+        values.push(if options.input {
+            quote! {
+                #ident: ::dialoguer::Input::<#ty>::with_theme(theme)
+                    .with_prompt(#label)
+                    .interact()?
+            }
+        } else {
+            quote! {
+                #ident: <#ty as ::dialoguer::Prompt>::prompt_with_theme(theme, #label)?
+            }
+        });
+    }
+    Ok(quote! {
+        let _ = text;
+        Ok(#name {
+            #(#values,)*
+        })
+    })
+}
+
+/// Turns a field or variant name like `private_key` or `PrivateKey` into
+/// a label like `Private key`.
+fn humanize(name: &str) -> String {
+    let mut rv = String::new();
+    for (idx, c) in name.trim_start_matches("r#").chars().enumerate() {
+        if c == '_' {
+            rv.push(' ');
+        } else if idx == 0 {
+            rv.extend(c.to_uppercase());
+        } else if c.is_uppercase() {
+            if !rv.ends_with(' ') {
+                rv.push(' ');
+            }
+            rv.extend(c.to_lowercase());
+        } else {
+            rv.push(c);
+        }
+    }
+    rv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize("private_key"), "Private key");
+        assert_eq!(humanize("PrivateKey"), "Private key");
+        assert_eq!(humanize("r#type"), "Type");
+        assert_eq!(humanize("Tls"), "Tls");
+    }
+}
@@ -14,15 +14,15 @@ fn main() {
         .with_prompt("Pick your food")
         .items(&checkboxes[..])
         .defaults(&defaults[..])
-        .interact()
+        .interact_items()
         .unwrap();
 
     if selections.is_empty() {
         println!("You did not select anything :(");
     } else {
         println!("You selected these things:");
-        for selection in selections {
-            println!("  {}", checkboxes[selection]);
+        for (_, item) in selections {
+            println!("  {}", item);
         }
     }
 }
@@ -0,0 +1,16 @@
+extern crate dialoguer;
+
+use dialoguer::theme::ColoredTheme;
+use dialoguer::ConfirmPhrase;
+
+fn main() {
+    if ConfirmPhrase::with_theme(&ColoredTheme::default())
+        .with_phrase("my-project")
+        .interact()
+        .unwrap()
+    {
+        println!("Deleting my-project");
+    } else {
+        println!("Nothing was deleted");
+    }
+}
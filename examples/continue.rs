@@ -14,6 +14,10 @@ fn main() {
         return;
     }
 
-    let input: String = Input::new().with_prompt("Your name").interact().unwrap();
+    let input: String = Input::new()
+        .with_prompt("Your name")
+        .with_placeholder("e.g. Jane Doe")
+        .interact()
+        .unwrap();
     println!("Hello {}!", input);
 }
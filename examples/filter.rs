@@ -0,0 +1,26 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Checkboxes};
+
+fn main() {
+    let crates = &[
+        "clap",
+        "console",
+        "dialoguer",
+        "indicatif",
+        "serde",
+        "serde_json",
+        "tempfile",
+        "toml",
+    ];
+    let selections = Checkboxes::with_theme(&ColoredTheme::default())
+        .with_prompt("Pick dependencies (type to filter)")
+        .items(&crates[..])
+        .filterable(true)
+        .interact_items()
+        .unwrap();
+
+    for (_, name) in selections {
+        println!("{}", name);
+    }
+}
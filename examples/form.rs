@@ -0,0 +1,31 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Prompt};
+
+#[derive(Debug, Prompt)]
+enum Tls {
+    #[prompt(label = "automatic with ACME")]
+    Automatic,
+    #[prompt(label = "manual")]
+    Manual,
+    #[prompt(label = "no")]
+    Disabled,
+}
+
+#[derive(Debug, Prompt)]
+#[allow(dead_code)]
+struct Config {
+    hostname: String,
+    port: u16,
+    #[prompt(label = "Configure TLS")]
+    tls: Tls,
+    #[prompt(input)]
+    interface: std::net::IpAddr,
+    #[prompt(label = "Save the configuration?")]
+    save: bool,
+}
+
+fn main() {
+    let config = Config::prompt_with_theme(&ColoredTheme::default(), "Server setup").unwrap();
+    println!("{:#?}", config);
+}
@@ -6,7 +6,7 @@ use dialoguer::{Input, KeyPrompt};
 fn main() {
     let rv = KeyPrompt::with_theme(&ColoredTheme::default())
         .with_text("Do you want to continue?")
-        .items(&['y', 'n', 'p'])
+        .items(&[('y', "yes"), ('n', "no"), ('p', "preview")])
         .default(1)
         .interact()
         .unwrap();
@@ -0,0 +1,13 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Select};
+
+fn main() {
+    let selection = Select::with_theme(&ColoredTheme::default())
+        .with_prompt("Pick a number")
+        .lazy_items((1..).map(|n| format!("Number {}", n)), None)
+        .default(0)
+        .interact_items()
+        .unwrap();
+    println!("You picked {}", selection.1);
+}
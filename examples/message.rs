@@ -0,0 +1,25 @@
+extern crate dialoguer;
+
+use dialoguer::theme::{ColoredTheme, ColorfulTheme, Severity};
+use dialoguer::Message;
+
+fn main() {
+    Message::new()
+        .with_text("This software is provided \"as is\", without warranty of any kind.")
+        .interact()
+        .unwrap();
+
+    Message::with_theme(&ColorfulTheme::default())
+        .with_text("All data on /dev/sdb will be erased.\nMake sure you have a backup.")
+        .severity(Severity::Warning)
+        .boxed(true)
+        .interact()
+        .unwrap();
+
+    Message::with_theme(&ColoredTheme::default())
+        .with_text("The disk could not be unmounted.")
+        .severity(Severity::Error)
+        .report(false)
+        .interact()
+        .unwrap();
+}
@@ -5,6 +5,7 @@ use dialoguer::{theme::ColorfulTheme, PasswordInput};
 fn main() {
     let password = PasswordInput::with_theme(&ColorfulTheme::default())
         .with_prompt("Password")
+        .with_mask(Some('•'))
         .with_confirmation("Repeat password", "Error: the passwords don't match.")
         .interact()
         .unwrap();
@@ -0,0 +1,18 @@
+extern crate console;
+extern crate dialoguer;
+
+use console::Key;
+use dialoguer::{theme::ColoredTheme, Pause};
+
+fn main() {
+    println!("The configuration was written to config.toml.");
+    Pause::with_theme(&ColoredTheme::default())
+        .interact()
+        .unwrap();
+
+    Pause::with_theme(&ColoredTheme::default())
+        .with_text("Press enter to exit")
+        .wait_for(Key::Enter)
+        .interact()
+        .unwrap();
+}
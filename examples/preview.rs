@@ -0,0 +1,22 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Select};
+
+fn main() {
+    let crates = &["console", "dialoguer", "indicatif"];
+    let descriptions = &[
+        "A terminal and console abstraction for Rust.\nStyles, key reading and terminal sizes.",
+        "A command line prompting library.",
+        "A command line progress reporting library.\nProgress bars and spinners.",
+    ];
+
+    let selection = Select::with_theme(&ColoredTheme::default())
+        .with_prompt("Pick a crate")
+        .items(&crates[..])
+        .default(0)
+        .preview(|idx| descriptions[idx].to_string())
+        .preview_height(2)
+        .interact()
+        .unwrap();
+    println!("Picked {}", crates[selection]);
+}
@@ -0,0 +1,21 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Select};
+
+fn main() {
+    let crates = vec![
+        vec!["console", "0.9.2", "42 kB"],
+        vec!["dialoguer", "0.5.0", "31 kB"],
+        vec!["indicatif", "0.14.0", "128 kB"],
+        vec!["tempfile", "3.1.0", "27 kB"],
+    ];
+
+    let selection = Select::with_theme(&ColoredTheme::default())
+        .with_prompt("Pick a crate")
+        .with_header(&["Name", "Version", "Size"])
+        .default(0)
+        .rows(&crates)
+        .interact()
+        .unwrap();
+    println!("You picked {}!", crates[selection][0]);
+}
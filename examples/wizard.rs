@@ -8,6 +8,7 @@ use console::Style;
 use dialoguer::{theme::ColorfulTheme, Confirmation, Input, Select};
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Config {
     interface: IpAddr,
     hostname: String,
@@ -0,0 +1,27 @@
+extern crate dialoguer;
+
+use dialoguer::{theme::ColoredTheme, Wizard};
+
+fn main() {
+    let theme = ColoredTheme::default();
+    let answers = Wizard::with_theme(&theme)
+        .with_title("Server setup")
+        .input("hostname", "Hostname")
+        .select(
+            "tls",
+            "Configure TLS",
+            &["automatic with ACME", "manual", "no"],
+        )
+        .input("key", "Path to private key")
+        .skip_if(|answers| answers.get("tls").and_then(|a| a.as_index()) != Some(1))
+        .input("cert", "Path to certificate")
+        .skip_if(|answers| answers.get("key").is_none())
+        .checkboxes("features", "Features", &["gzip", "http2", "logging"])
+        .confirm("save", "Save the configuration?")
+        .interact()
+        .unwrap();
+
+    for (key, answer) in answers.iter() {
+        println!("{}: {}", key, answer);
+    }
+}
@@ -0,0 +1,121 @@
+//! Asking for values missing from the command line.
+use std::any::Any;
+use std::io;
+
+use clap::ArgMatches;
+
+use error::Result;
+use form::Prompt;
+use interact::Interact;
+
+/// Returns a value given on the command line or asks for it.
+///
+/// This is implemented for `Option`, as found in arguments parsed with
+/// clap.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, OrPrompt};
+///
+/// # let name_arg: Option<String> = None;
+/// # let port_arg: Option<u16> = None;
+/// let name = name_arg.or_prompt(Input::new().with_prompt("Your name"))?;
+/// let port = port_arg.or_ask("Port")?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub trait OrPrompt<T> {
+    /// Runs the prompt if the value is missing.
+    fn or_prompt<P: Interact<T> + ?Sized>(self, prompt: &P) -> Result<T>;
+
+    /// Asks for the value with the given text if it is missing.
+    fn or_ask(self, text: &str) -> Result<T>
+    where
+        T: Prompt;
+}
+
+impl<T> OrPrompt<T> for Option<T> {
+    fn or_prompt<P: Interact<T> + ?Sized>(self, prompt: &P) -> Result<T> {
+        match self {
+            Some(value) => Ok(value),
+            None => prompt.interact(),
+        }
+    }
+
+    fn or_ask(self, text: &str) -> Result<T>
+    where
+        T: Prompt,
+    {
+        match self {
+            Some(value) => Ok(value),
+            None => T::prompt(text),
+        }
+    }
+}
+
+/// Asks for the values of arguments missing from clap's matches.
+pub trait ArgMatchesExt {
+    /// Returns the value of the argument `id` or runs the prompt.
+    ///
+    /// Fails if `T` is not the type of the argument.
+    fn get_or_prompt<T, P>(&self, id: &str, prompt: &P) -> Result<T>
+    where
+        T: Any + Clone + Send + Sync + 'static,
+        P: Interact<T> + ?Sized;
+}
+
+impl ArgMatchesExt for ArgMatches {
+    fn get_or_prompt<T, P>(&self, id: &str, prompt: &P) -> Result<T>
+    where
+        T: Any + Clone + Send + Sync + 'static,
+        P: Interact<T> + ?Sized,
+    {
+        self.try_get_one::<T>(id)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?
+            .cloned()
+            .or_prompt(prompt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, Command};
+    use console::Term;
+    use prompts::Input;
+    use testing::Script;
+
+    #[test]
+    fn test_or_prompt() {
+        let mut prompt = Input::<String>::new();
+        prompt.with_prompt("Name").on(&Term::buffered_stderr());
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("Ann")
+            .start()
+            .unwrap();
+        assert_eq!(Some("Bob".to_string()).or_prompt(&prompt).unwrap(), "Bob");
+        assert_eq!(None.or_prompt(&prompt).unwrap(), "Ann");
+        session.finish().unwrap();
+
+        let matches = Command::new("test")
+            .arg(Arg::new("name").long("name"))
+            .get_matches_from(vec!["test", "--name", "Bob"]);
+        assert_eq!(matches.get_or_prompt("name", &prompt).unwrap(), "Bob");
+        assert!(matches
+            .get_or_prompt::<u16, _>("name", &Input::new())
+            .is_err());
+
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("Ann")
+            .start()
+            .unwrap();
+        assert_eq!(
+            Interact::interact_on(&prompt, &Term::buffered_stderr()).unwrap(),
+            "Ann"
+        );
+        session.finish().unwrap();
+    }
+}
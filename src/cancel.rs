@@ -0,0 +1,138 @@
+//! Cancelling prompts from other threads.
+use std::cell::RefCell;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use error::Error;
+
+thread_local! {
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// A handle to cancel the prompts of a thread from another thread, eg:
+/// when the application is shutting down.
+///
+/// A cancelled prompt is cleared and fails with `Error::Cancelled`.  On
+/// unix prompts reading keys notice the cancellation while they wait.
+/// Prompts reading whole lines, eg: when the terminal cannot move the
+/// cursor, only notice it before they start reading, and so do all
+/// prompts on platforms other than unix.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> dialoguer::Result<()> {
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use dialoguer::{Confirmation, Error, PromptHandle};
+///
+/// let handle = PromptHandle::current();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(30));
+///     handle.cancel();
+/// });
+/// match Confirmation::new().with_text("Deploy now?").interact() {
+///     Ok(deploy) => println!("Deploying: {}", deploy),
+///     Err(Error::Cancelled) => println!("No answer in time"),
+///     Err(err) => return Err(err),
+/// }
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PromptHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PromptHandle {
+    /// Returns the handle of the prompts asked on this thread.
+    pub fn current() -> PromptHandle {
+        CANCELLED.with(|cancelled| PromptHandle {
+            cancelled: cancelled
+                .borrow_mut()
+                .get_or_insert_with(Default::default)
+                .clone(),
+        })
+    }
+
+    /// Cancels the prompt waiting for input.
+    ///
+    /// If no prompt is waiting the next prompt asked on the thread is
+    /// cancelled instead.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Returns `true` if a handle to the prompts of this thread was taken.
+pub(crate) fn is_cancellable() -> bool {
+    CANCELLED.with(|cancelled| cancelled.borrow().is_some())
+}
+
+/// Fails with `Error::Cancelled` once after the prompts of this thread
+/// were cancelled.
+pub(crate) fn check() -> io::Result<()> {
+    let cancelled = CANCELLED.with(|cancelled| {
+        let cancelled = cancelled.borrow();
+        cancelled
+            .as_ref()
+            .is_some_and(|cancelled| cancelled.swap(false, Ordering::SeqCst))
+    });
+    if cancelled {
+        return Err(Error::Cancelled.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+    use std::time::Duration;
+
+    use console::{Key, Term};
+
+    use prompts::Confirmation;
+    use session;
+    use testing::Script;
+
+    #[test]
+    fn test_cancel() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Deploy now?")
+            .expect_prompt("Deploy now?")
+            .expect_prompt("Deploy now?")
+            .press(Key::Char('y'))
+            .start()
+            .unwrap();
+        let handle = PromptHandle::current();
+        let waiting = handle.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            waiting.cancel();
+        });
+        let err = Confirmation::new()
+            .with_text("Deploy now?")
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Cancelled));
+        canceller.join().unwrap();
+
+        handle.cancel();
+        let err = Confirmation::new()
+            .with_text("Deploy now?")
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Cancelled));
+        let deploy = Confirmation::new()
+            .with_text("Deploy now?")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(deploy);
+        assert_eq!(session::take_interrupt(), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Durations written in a human-friendly way.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The units of a duration with their length in milliseconds, the
+/// largest one first.
+const UNITS: [(&str, u64); 6] = [
+    ("w", 7 * 24 * 60 * 60 * 1000),
+    ("d", 24 * 60 * 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+/// A duration like `90s`, `1h30m` or `2d` that can be read by an `Input`.
+///
+/// A duration is a sequence of numbers each followed by one of the units
+/// `w`, `d`, `h`, `m`, `s` and `ms`.  Whitespace between the parts is
+/// ignored.  Durations are shown in the same way, eg: as the default of
+/// an input.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use std::time::Duration;
+/// use dialoguer::{HumanDuration, Input};
+///
+/// let timeout: Duration = Input::<HumanDuration>::new()
+///     .with_prompt("Timeout")
+///     .default(Some(Duration::from_secs(90).into()))
+///     .interact()?
+///     .into();
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(pub Duration);
+
+/// The error returned for a text that is not a duration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDurationError {
+    text: String,
+}
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is not a duration, expected numbers followed by w, d, h, m, s or ms \
+             like 90s, 1h30m or 2d",
+            self.text
+        )
+    }
+}
+
+impl Error for ParseDurationError {}
+
+impl FromStr for HumanDuration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<HumanDuration, ParseDurationError> {
+        let err = || ParseDurationError { text: s.into() };
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err(err());
+        }
+        let mut millis: u64 = 0;
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let num: u64 = rest[..digits].parse().map_err(|_| err())?;
+            rest = &rest[digits..];
+            let letters = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let unit = rest[..letters].to_ascii_lowercase();
+            let len = UNITS
+                .iter()
+                .find(|&&(name, _)| name == unit)
+                .map(|&(_, len)| len)
+                .ok_or_else(err)?;
+            millis = num
+                .checked_mul(len)
+                .and_then(|part| millis.checked_add(part))
+                .ok_or_else(err)?;
+            rest = rest[letters..].trim_start();
+        }
+        Ok(HumanDuration(Duration::from_millis(millis)))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut millis = self.0.as_millis();
+        if millis == 0 {
+            return write!(f, "0s");
+        }
+        for &(name, len) in &UNITS {
+            let len = u128::from(len);
+            if millis >= len {
+                write!(f, "{}{}", millis / len, name)?;
+                millis %= len;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(duration: Duration) -> HumanDuration {
+        HumanDuration(duration)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(duration: HumanDuration) -> Duration {
+        duration.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let parse = |s: &str| s.parse::<HumanDuration>().map(Duration::from);
+        assert_eq!(parse("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse(" 2d 1ms "), Ok(Duration::from_millis(172_800_001)));
+        assert_eq!(parse("1H"), Ok(Duration::from_secs(3600)));
+        assert!(parse("").is_err());
+        assert!(parse("90").is_err());
+        assert!(parse("1y").is_err());
+        assert!(parse("h").is_err());
+        assert!(parse("99999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(
+            HumanDuration(Duration::from_secs(5400)).to_string(),
+            "1h30m"
+        );
+        assert_eq!(
+            HumanDuration(Duration::from_millis(1500)).to_string(),
+            "1s500ms"
+        );
+        assert_eq!(HumanDuration(Duration::from_secs(0)).to_string(), "0s");
+        assert_eq!(
+            HumanDuration(Duration::from_secs(691_200)).to_string(),
+            "1w1d"
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fs;
-use std::io;
 use std::io::{Read, Write};
 use std::process;
 
+use error::Result;
+
 /// Launches the default editor edit a string.
 ///
 /// Example:
@@ -89,7 +90,7 @@ impl Editor {
     ///
     /// Returns `None` if the file was not saved or otherwise the
     /// entered text.
-    pub fn edit(&self, s: &str) -> io::Result<Option<String>> {
+    pub fn edit(&self, s: &str) -> Result<Option<String>> {
         let mut f = tempfile::Builder::new()
             .prefix("edit-")
             .suffix(&self.extension)
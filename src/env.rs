@@ -0,0 +1,24 @@
+//! Answers taken from environment variables.
+use std::env;
+use std::io;
+
+/// Returns the name and value of the variable answering a prompt if it
+/// is set.
+pub(crate) fn answer(key: Option<&str>) -> Option<(&str, String)> {
+    let key = key?;
+    env::var(key).ok().map(|value| (key, value))
+}
+
+/// Returns `true` if `DIALOGUER_ACCESSIBLE` asks for the accessible
+/// mode of list prompts.
+pub(crate) fn accessible() -> bool {
+    env::var_os("DIALOGUER_ACCESSIBLE").is_some_and(|val| !val.is_empty() && val != "0")
+}
+
+/// Creates the error returned for an invalid value of a variable.
+pub(crate) fn invalid(key: &str, err: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid value in {}: {}", key, err),
+    )
+}
@@ -0,0 +1,110 @@
+//! The error returned by prompts.
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+
+/// The error returned when a prompt could not be answered.
+///
+/// Errors of the terminal are kept as `Io`.  Converting an `Error` into
+/// an `io::Error` and back keeps its variant.
+#[derive(Debug)]
+pub enum Error {
+    /// No terminal is attached to read the answer from
+    NotATty,
+    /// The user interrupted the prompt with Ctrl-C
+    Interrupted,
+    /// The prompt was cancelled through a `PromptHandle`
+    Cancelled,
+    /// The default or assumed answer does not fit the prompt
+    InvalidDefault(String),
+    /// The menu has no items to choose from
+    NoItems,
+    /// No valid value was entered within the attempts allowed by
+    /// `Input::max_attempts`
+    AttemptsExceeded {
+        /// The number of invalid values entered
+        attempts: usize,
+    },
+    /// Reading from or writing to the terminal failed
+    Io(io::Error),
+}
+
+/// The result of answering a prompt.
+pub type Result<T, E = Error> = result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotATty => write!(f, "no terminal to read the answer from"),
+            Error::Interrupted => write!(f, "interrupted by Ctrl-C"),
+            Error::Cancelled => write!(f, "the prompt was cancelled"),
+            Error::InvalidDefault(ref err) => write!(f, "invalid default: {}", err),
+            Error::NoItems => write!(f, "there are no items to select from"),
+            Error::AttemptsExceeded { attempts } => {
+                write!(f, "no valid value after {} attempts", attempts)
+            }
+            Error::Io(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        if err.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            let kind = err.kind();
+            return match err.into_inner().map(|inner| inner.downcast::<Error>()) {
+                Some(Ok(inner)) => *inner,
+                _ => Error::Io(kind.into()),
+            };
+        }
+        match err.kind() {
+            io::ErrorKind::Interrupted => Error::Interrupted,
+            io::ErrorKind::NotConnected => Error::NotATty,
+            _ => Error::Io(err),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        let kind = match err {
+            Error::Io(err) => return err,
+            Error::NotATty => io::ErrorKind::NotConnected,
+            Error::Interrupted => io::ErrorKind::Interrupted,
+            Error::Cancelled => io::ErrorKind::Other,
+            Error::InvalidDefault(_) | Error::NoItems | Error::AttemptsExceeded { .. } => {
+                io::ErrorKind::InvalidInput
+            }
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_round_trip() {
+        let err = io::Error::from(Error::InvalidDefault("no default to assume".into()));
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        match Error::from(err) {
+            Error::InvalidDefault(ref err) => assert_eq!(err, "no default to assume"),
+            err => panic!("unexpected error {:?}", err),
+        }
+        let err = io::Error::new(io::ErrorKind::Interrupted, "interrupted by Ctrl-C");
+        assert!(matches!(Error::from(err), Error::Interrupted));
+        let err = io::Error::new(io::ErrorKind::NotFound, "gone");
+        assert!(matches!(Error::from(err), Error::Io(_)));
+    }
+}
@@ -0,0 +1,76 @@
+//! Values that can be asked for interactively.
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use error::Result;
+use prompts::{Confirmation, Input};
+use theme::{get_default_theme, Theme};
+
+/// A value that can be asked for interactively.
+///
+/// This is implemented for strings, numbers and bools.  With the `derive`
+/// feature it can be derived for structs, which are asked for field by
+/// field, and for enums, which are picked from a selection.
+///
+/// ## Example usage
+///
+/// ```rust,ignore
+/// use dialoguer::Prompt;
+///
+/// #[derive(Prompt)]
+/// enum Tls {
+///     Automatic,
+///     Manual,
+///     #[prompt(label = "No TLS")]
+///     Disabled,
+/// }
+///
+/// #[derive(Prompt)]
+/// struct Config {
+///     hostname: String,
+///     port: u16,
+///     tls: Tls,
+///     #[prompt(label = "Save the configuration?")]
+///     save: bool,
+/// }
+///
+/// let config = Config::prompt("Server setup")?;
+/// ```
+pub trait Prompt: Sized {
+    /// Asks for the value with a specific theme.
+    fn prompt_with_theme(theme: &dyn Theme, text: &str) -> Result<Self>;
+
+    /// Asks for the value.
+    fn prompt(text: &str) -> Result<Self> {
+        Self::prompt_with_theme(get_default_theme(), text)
+    }
+}
+
+fn input<T>(theme: &dyn Theme, text: &str) -> Result<T>
+where
+    T: Clone + FromStr + Display + 'static,
+    T::Err: Display + Debug,
+{
+    Input::with_theme(theme).with_prompt(text).interact()
+}
+
+macro_rules! impl_input_prompt {
+    ($($ty:ty),*) => {
+        $(
+            impl Prompt for $ty {
+                fn prompt_with_theme(theme: &dyn Theme, text: &str) -> Result<Self> {
+                    input(theme, text)
+                }
+            }
+        )*
+    };
+}
+
+impl_input_prompt!(String, char, f32, f64);
+impl_input_prompt!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Prompt for bool {
+    fn prompt_with_theme(theme: &dyn Theme, text: &str) -> Result<Self> {
+        Confirmation::with_theme(theme).with_text(text).interact()
+    }
+}
@@ -0,0 +1,140 @@
+//! Matching of filter queries against items.
+
+/// Matches the query typed into the filter of a menu against its items.
+///
+/// A match has a score, items with higher scores are listed first, and
+/// the char indexes of the matched characters, which are highlighted.
+/// Closures taking the query and the text implement this trait too.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::{Checkboxes, SubstringMatcher};
+///
+/// let hosts = Checkboxes::new()
+///     .items(&["prod-eu", "prod-us", "staging"])
+///     .matcher(SubstringMatcher)
+///     .filterable(true)
+///     .interact()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub trait Matcher {
+    /// Returns the score and the matched char indexes of `text` or `None`
+    /// if it does not match `query`.  An empty query has to match.
+    fn find(&self, query: &str, text: &str) -> Option<(i64, Vec<usize>)>;
+}
+
+impl<F: Fn(&str, &str) -> Option<(i64, Vec<usize>)>> Matcher for F {
+    fn find(&self, query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+        self(query, text)
+    }
+}
+
+/// Matches the characters of the query in order, ignoring case, with
+/// any characters in between.  This is the default.
+///
+/// Runs of consecutive characters and matches at the start of words
+/// score higher, gaps score lower.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FuzzyMatcher;
+
+impl Matcher for FuzzyMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+        let indexes = fuzzy_match(query, text)?;
+        let chars: Vec<char> = text.chars().collect();
+        let mut score = 0;
+        let mut last = None;
+        for &idx in &indexes {
+            score += 16;
+            if idx == 0 || !chars[idx - 1].is_alphanumeric() {
+                score += 8;
+            }
+            match last {
+                Some(last) if idx == last + 1 => score += 8,
+                Some(last) => score -= (idx - last - 1) as i64,
+                None => score -= idx as i64,
+            }
+            last = Some(idx);
+        }
+        Some((score, indexes))
+    }
+}
+
+/// Matches the query as a contiguous part of the text, ignoring case.
+///
+/// Matches closer to the start of the text score higher.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubstringMatcher;
+
+impl Matcher for SubstringMatcher {
+    fn find(&self, query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+        let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+        let chars: Vec<char> = text.chars().collect();
+        if query.is_empty() {
+            return Some((0, vec![]));
+        }
+        // compares char by char so the indexes stay those of `text`
+        let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+        (0..chars.len())
+            .find(|&start| {
+                chars.len() - start >= query.len()
+                    && query.iter().zip(&chars[start..]).all(|(&q, &c)| same(q, c))
+            })
+            .map(|start| (-(start as i64), (start..start + query.len()).collect()))
+    }
+}
+
+/// Matches `pattern` as a case insensitive subsequence of `text`.
+///
+/// Returns the char indexes of the matched characters or `None` if the
+/// text does not match.  An empty pattern matches everything.
+pub(crate) fn fuzzy_match(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let mut indexes = vec![];
+    let mut chars = text.chars().enumerate();
+    for p in pattern.chars() {
+        loop {
+            match chars.next() {
+                Some((idx, c)) if c.to_lowercase().eq(p.to_lowercase()) => {
+                    indexes.push(idx);
+                    break;
+                }
+                Some(_) => {}
+                None => return None,
+            }
+        }
+    }
+    Some(indexes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "anything"), Some(vec![]));
+        assert_eq!(fuzzy_match("vc", "Vanilla Cupcake"), Some(vec![0, 8]));
+        assert_eq!(
+            fuzzy_match("MUF", "Chocolate Muffin"),
+            Some(vec![10, 11, 12])
+        );
+        assert_eq!(fuzzy_match("cv", "Vanilla Cupcake"), None);
+        assert_eq!(fuzzy_match("ice", "Ic"), None);
+    }
+
+    #[test]
+    fn test_matchers() {
+        let score = |text| FuzzyMatcher.find("cup", text).map(|(score, _)| score);
+        assert!(score("Cupcake") > score("Vanilla Cupcake"));
+        assert!(score("Vanilla Cupcake") > score("coconut pie"));
+        assert_eq!(score("Muffin"), None);
+
+        assert_eq!(
+            SubstringMatcher.find("CAKE", "Cupcake"),
+            Some((-3, vec![3, 4, 5, 6]))
+        );
+        assert_eq!(SubstringMatcher.find("cpk", "Cupcake"), None);
+        assert_eq!(SubstringMatcher.find("", "Cupcake"), Some((0, vec![])));
+    }
+}
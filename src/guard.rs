@@ -0,0 +1,107 @@
+//! Restoring the terminal when a prompt is left by a panic.
+use std::thread;
+
+use console::Term;
+
+/// Saves the state of a terminal when a prompt starts and restores it if
+/// the prompt is left by a panic, eg: in a validator or preview callback.
+///
+/// This shows the cursor, turns off reverse video and brings back echo
+/// and line editing so the shell is usable after the program exits.
+pub(crate) struct PanicGuard {
+    term: Term,
+    #[cfg(unix)]
+    mode: Option<TtyMode>,
+}
+
+impl PanicGuard {
+    pub fn new(term: &Term) -> PanicGuard {
+        PanicGuard {
+            term: term.clone(),
+            #[cfg(unix)]
+            mode: if term.is_term() {
+                TtyMode::save().ok()
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        if !thread::panicking() || !self.term.is_term() {
+            return;
+        }
+        #[cfg(unix)]
+        {
+            if let Some(ref mode) = self.mode {
+                mode.restore();
+            }
+        }
+        self.term.write_str("\x1b[?5l\x1b[?25h").ok();
+        self.term.flush().ok();
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use self::unix::TtyMode;
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    /// The mode of the controlling terminal as it was when saved.
+    pub(crate) struct TtyMode {
+        fd: RawFd,
+        _tty: Option<File>,
+        original: libc::termios,
+    }
+
+    impl TtyMode {
+        /// Saves the mode of stdin or, if that is not a terminal, of
+        /// `/dev/tty`.
+        pub fn save() -> io::Result<TtyMode> {
+            let (fd, tty) = if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+                (libc::STDIN_FILENO, None)
+            } else {
+                let tty = File::open("/dev/tty")?;
+                (tty.as_raw_fd(), Some(tty))
+            };
+            let mut original = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(TtyMode {
+                fd,
+                _tty: tty,
+                original,
+            })
+        }
+
+        /// The file descriptor of the terminal.
+        pub fn fd(&self) -> RawFd {
+            self.fd
+        }
+
+        /// Returns the saved mode.
+        pub fn original(&self) -> libc::termios {
+            self.original
+        }
+
+        /// Changes the mode of the terminal.
+        pub fn set(&self, termios: &libc::termios) -> io::Result<()> {
+            if unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, termios) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+
+        /// Changes the mode of the terminal back to the saved one.
+        pub fn restore(&self) {
+            unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &self.original) };
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Entries of earlier inputs that can be recalled.
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The entries of earlier inputs recalled with the arrow keys.
+pub trait History {
+    /// Returns the entry `pos` places back, `0` being the latest one.
+    fn read(&self, pos: usize) -> Option<String>;
+
+    /// Records an entered value.
+    fn write(&mut self, entry: &str) -> io::Result<()>;
+}
+
+/// A history kept in a file so that it is recalled across invocations.
+///
+/// The file holds one entry per line, the latest one last.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FileHistory, Input};
+///
+/// let history = FileHistory::open(".repl_history")?
+///     .max_entries(500)
+///     .deduplicate(true);
+/// let mut input = Input::<String>::new();
+/// input.with_prompt(">").history(history);
+/// loop {
+///     let cmd = input.interact()?;
+///     if cmd == "exit" {
+///         break;
+///     }
+/// }
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+#[derive(Debug)]
+pub struct FileHistory {
+    path: PathBuf,
+    entries: VecDeque<String>,
+    max_entries: Option<usize>,
+    deduplicate: bool,
+}
+
+impl FileHistory {
+    /// Loads the history from a file.
+    ///
+    /// A missing file is an empty history and is created once an entry
+    /// is recorded.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileHistory> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .collect(),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => VecDeque::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(FileHistory {
+            path,
+            entries,
+            max_entries: None,
+            deduplicate: false,
+        })
+    }
+
+    /// Limits the number of entries kept, dropping the oldest ones.
+    pub fn max_entries(mut self, val: usize) -> FileHistory {
+        self.max_entries = Some(val);
+        self.trim();
+        self
+    }
+
+    /// Drops earlier entries equal to a recorded one.
+    pub fn deduplicate(mut self, val: bool) -> FileHistory {
+        self.deduplicate = val;
+        self
+    }
+
+    /// Returns the entries, the oldest one first.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.as_str())
+    }
+
+    fn trim(&mut self) {
+        if let Some(max) = self.max_entries {
+            while self.entries.len() > max {
+                self.entries.pop_front();
+            }
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut text = String::new();
+        for entry in &self.entries {
+            text.push_str(entry);
+            text.push('\n');
+        }
+        fs::write(&self.path, text)
+    }
+}
+
+impl History for FileHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        let idx = self.entries.len().checked_sub(pos + 1)?;
+        self.entries.get(idx).cloned()
+    }
+
+    fn write(&mut self, entry: &str) -> io::Result<()> {
+        // entries are stored one per line
+        let entry = entry.replace('\n', " ");
+        if entry.is_empty() {
+            return Ok(());
+        }
+        if self.deduplicate {
+            self.entries.retain(|old| *old != entry);
+        }
+        self.entries.push_back(entry);
+        self.trim();
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+        let mut history = FileHistory::open(&path)
+            .unwrap()
+            .max_entries(3)
+            .deduplicate(true);
+        for entry in &["ls", "cd", "ls", "pwd", "", "make"] {
+            history.write(entry).unwrap();
+        }
+        assert_eq!(history.read(0).as_deref(), Some("make"));
+        assert_eq!(history.read(2).as_deref(), Some("ls"));
+        assert_eq!(history.read(3), None);
+
+        let history = FileHistory::open(&path).unwrap();
+        assert_eq!(
+            history.entries().collect::<Vec<_>>(),
+            vec!["ls", "pwd", "make"]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! A common interface to run any configured prompt.
+use std::fmt::Display;
+
+use console::Term;
+
+use error::Result;
+use prompts::{ConfirmPhrase, Confirmation, Input, KeyPrompt, Message, PasswordInput, Pause};
+use search::Search;
+use select::{Checkboxes, OrderList, Select};
+use wizard::{Answers, Wizard};
+
+/// A configured prompt that asks for a value of type `T`.
+pub trait Interact<T> {
+    /// Asks for the value.
+    fn interact(&self) -> Result<T>;
+
+    /// Asks for the value on a specific terminal.
+    fn interact_on(&self, term: &Term) -> Result<T>;
+}
+
+impl<'a, T: Clone + Display + 'static> Interact<T> for Input<'a, T> {
+    fn interact(&self) -> Result<T> {
+        Input::interact(self)
+    }
+
+    fn interact_on(&self, term: &Term) -> Result<T> {
+        Input::interact_on(self, term)
+    }
+}
+
+macro_rules! impl_interact {
+    ($($prompt:ident => $value:ty,)*) => {
+        $(
+            impl<'a> Interact<$value> for $prompt<'a> {
+                fn interact(&self) -> Result<$value> {
+                    $prompt::interact(self)
+                }
+
+                fn interact_on(&self, term: &Term) -> Result<$value> {
+                    $prompt::interact_on(self, term)
+                }
+            }
+        )*
+    };
+}
+
+impl_interact! {
+    Confirmation => bool,
+    ConfirmPhrase => bool,
+    KeyPrompt => char,
+    PasswordInput => String,
+    Pause => (),
+    Message => (),
+    Select => usize,
+    Checkboxes => Vec<usize>,
+    OrderList => Vec<usize>,
+    Search => String,
+    Wizard => Answers,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::Key;
+    use testing::Script;
+
+    #[test]
+    fn test_interact() {
+        let term = Term::buffered_stderr();
+        let mut confirmation = Confirmation::new();
+        confirmation.with_text("Deploy?");
+        let mut phrase = ConfirmPhrase::new();
+        phrase.with_phrase("prod");
+        let prompts: Vec<&dyn Interact<bool>> = vec![&confirmation, &phrase];
+        let session = Script::new()
+            .expect_prompt("Deploy?")
+            .press(Key::Char('y'))
+            .expect_prompt("Type \"prod\" to confirm")
+            .type_line("prod")
+            .start()
+            .unwrap();
+        for prompt in prompts {
+            assert!(prompt.interact_on(&term).unwrap());
+        }
+        session.finish().unwrap();
+    }
+}
@@ -11,20 +11,119 @@
 //! # Crate Contents
 //!
 //! * Confirmation prompts
+//! * Prompts confirmed by typing a phrase
+//! * Messages waiting for acknowledgement
 //! * Input prompts (regular and password)
 //! * Input validation
+//! * Human-friendly duration inputs
+//! * Input history kept across invocations
 //! * Menu selections
+//! * Menus starting on the item chosen last time (with the `state`
+//!   feature)
 //! * Checkboxes
+//! * Menus searching for items as the query is typed
+//! * Pluggable matching of menu filters
 //! * Editor launching
+//! * Wizards chaining multiple prompts
+//! * Forms derived from structs (with the `derive` feature)
+//! * Recording and replaying sessions
+//! * Cancelling prompts from another thread
+//! * Restarting or answering prompts left idle, eg: in a kiosk
+//! * Translations of the built-in words
+//! * Scripted input for tests
+//! * Asking for values missing from the command line (with the `clap`
+//!   feature)
+//!
+//! # Common Options
+//!
+//! The prompts share a few builder methods:
+//!
+//! * `on`, `stdout` and `stderr` pick the terminal a prompt is rendered
+//!   on.  The default is stderr, which keeps the output of a program on
+//!   stdout clean for piping.
+//! * `max_width` confines a prompt to fewer columns than the terminal
+//!   has, eg: in a split pane.  Text is wrapped and items are cut off at
+//!   this width.
+//! * `report(false)` clears a prompt once it is answered instead of
+//!   printing the answer in its place.  `report_cancelled(false)` does
+//!   the same for cancelled prompts.
+//! * `on_complete` is invoked with the prompt and the answer once a
+//!   prompt is answered.  Together with `report(false)` this allows
+//!   printing a custom summary.
+//! * `localization` translates the words built into a prompt and the
+//!   theme.
+#[cfg(feature = "clap")]
+extern crate clap;
 extern crate console;
+#[cfg(feature = "derive")]
+extern crate dialoguer_derive;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+#[cfg(feature = "serde_yaml")]
+extern crate serde_yaml;
 extern crate tempfile;
+#[cfg(feature = "toml")]
+extern crate toml;
+extern crate unicode_segmentation;
+extern crate unicode_width;
+#[cfg(feature = "clap")]
+pub use args::{ArgMatchesExt, OrPrompt};
+pub use cancel::PromptHandle;
+#[cfg(feature = "derive")]
+pub use dialoguer_derive::Prompt;
+pub use duration::{HumanDuration, ParseDurationError};
 pub use edit::Editor;
-pub use prompts::{Confirmation, Input, KeyPrompt, PasswordInput};
-pub use select::{Checkboxes, OrderList, Select};
+pub use error::{Error, Result};
+pub use form::Prompt;
+pub use fuzzy::{FuzzyMatcher, Matcher, SubstringMatcher};
+pub use history::{FileHistory, History};
+pub use interact::Interact;
+pub use localization::Localization;
+pub use prompts::{
+    ConfirmPhrase, Confirmation, ConfirmationSeries, IdleAction, Input, KeyItem, KeyPrompt,
+    Message, PasswordInput, Pause, SeriesAnswer,
+};
+pub use search::Search;
+pub use select::{CheckState, Checkboxes, OrderList, Select};
+pub use session::{Recording, Replay, Session};
+#[cfg(feature = "state")]
+pub use state::{FileState, StateStore};
+pub use theme::{get_default_theme, set_default_theme};
 pub use validate::Validator;
+pub use wizard::{Answer, Answers, AnswersFormat, Wizard, WizardStep};
 
+#[cfg(feature = "clap")]
+mod args;
+mod cancel;
+mod duration;
 mod edit;
+mod env;
+mod error;
+mod form;
+mod fuzzy;
+mod guard;
+mod history;
+mod interact;
+mod line;
+mod localization;
+mod mask;
 mod prompts;
+mod search;
 mod select;
+mod session;
+#[cfg(feature = "state")]
+mod state;
+pub mod testing;
 pub mod theme;
+mod theme_colors;
+#[cfg(feature = "serde")]
+mod theme_config;
+#[cfg(feature = "presets")]
+mod theme_presets;
 mod validate;
+mod wait;
+mod wizard;
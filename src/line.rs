@@ -0,0 +1,639 @@
+//! Line editing with readline style key bindings.
+use std::io;
+
+use console::{measure_text_width, truncate_str, Key, Term};
+use history::History;
+use mask::Mask;
+use session;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// An editing action bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditAction {
+    /// Moves the cursor one character left
+    Left,
+    /// Moves the cursor one character right
+    Right,
+    /// Moves the cursor to the start of the line
+    Home,
+    /// Moves the cursor to the end of the line
+    End,
+    /// Moves the cursor to the start of the previous word
+    WordLeft,
+    /// Moves the cursor to the end of the next word
+    WordRight,
+    /// Deletes the character before the cursor
+    DeleteLeft,
+    /// Deletes the character under the cursor
+    DeleteRight,
+    /// Deletes the word before the cursor
+    DeleteWord,
+    /// Deletes everything before the cursor
+    DeleteToStart,
+}
+
+/// Returns the editing action bound to a key.
+///
+/// Besides the arrow and editing keys this knows Ctrl-A/E (home/end),
+/// Ctrl-W (delete word), Ctrl-U (kill line) and Alt-B/F (word movement).
+pub(crate) fn edit_action(key: &Key) -> Option<EditAction> {
+    Some(match *key {
+        Key::ArrowLeft => EditAction::Left,
+        Key::ArrowRight => EditAction::Right,
+        Key::Home => EditAction::Home,
+        Key::End => EditAction::End,
+        Key::Backspace => EditAction::DeleteLeft,
+        Key::Del => EditAction::DeleteRight,
+        Key::Char('\u{17}') => EditAction::DeleteWord,
+        Key::Char('\u{15}') => EditAction::DeleteToStart,
+        Key::UnknownEscSeq(ref seq) => match seq.as_slice() {
+            ['b'] => EditAction::WordLeft,
+            ['f'] => EditAction::WordRight,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// The text of a line being edited and the cursor position in it.
+///
+/// The cursor is a byte offset that always sits between two grapheme
+/// clusters, so emoji and combining characters are never split.  Edits
+/// are kept in a history so they can be undone and redone.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    text: String,
+    pos: usize,
+    undo: Vec<(String, usize)>,
+    redo: Vec<(String, usize)>,
+    inserting: bool,
+}
+
+impl LineBuffer {
+    /// Creates a buffer holding `text` with the cursor at its end.
+    pub fn new(text: &str) -> LineBuffer {
+        LineBuffer {
+            text: text.into(),
+            pos: text.len(),
+            ..LineBuffer::default()
+        }
+    }
+
+    /// Returns the text of the line.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the text before the cursor.
+    pub fn before_cursor(&self) -> &str {
+        &self.text[..self.pos]
+    }
+
+    /// Returns the text after the cursor.
+    pub fn after_cursor(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    /// Inserts a character at the cursor.
+    ///
+    /// Characters typed in a row are undone together.
+    pub fn insert(&mut self, c: char) {
+        if !self.inserting {
+            self.undo.push((self.text.clone(), self.pos));
+            self.redo.clear();
+            self.inserting = true;
+        }
+        self.text.insert(self.pos, c);
+        self.pos += c.len_utf8();
+    }
+
+    /// Replaces the text and moves the cursor to its end.
+    pub fn set_text(&mut self, text: &str) {
+        self.inserting = false;
+        if self.text != text {
+            self.undo.push((self.text.clone(), self.pos));
+            self.redo.clear();
+        }
+        self.text = text.into();
+        self.pos = text.len();
+    }
+
+    /// Reverts the last edit.
+    pub fn undo(&mut self) {
+        self.inserting = false;
+        if let Some(state) = self.undo.pop() {
+            let old = self.replace(state);
+            self.redo.push(old);
+        }
+    }
+
+    /// Restores the last undone edit.
+    pub fn redo(&mut self) {
+        self.inserting = false;
+        if let Some(state) = self.redo.pop() {
+            let old = self.replace(state);
+            self.undo.push(old);
+        }
+    }
+
+    fn replace(&mut self, (text, pos): (String, usize)) -> (String, usize) {
+        let old_text = std::mem::replace(&mut self.text, text);
+        let old_pos = std::mem::replace(&mut self.pos, pos);
+        (old_text, old_pos)
+    }
+
+    /// Applies an editing action.
+    pub fn apply(&mut self, action: EditAction) {
+        self.inserting = false;
+        let before = (self.text.clone(), self.pos);
+        match action {
+            EditAction::Left => self.pos = self.prev_boundary(),
+            EditAction::Right => self.pos = self.next_boundary(),
+            EditAction::Home => self.pos = 0,
+            EditAction::End => self.pos = self.text.len(),
+            EditAction::WordLeft => self.pos = self.word_start(char::is_alphanumeric),
+            EditAction::WordRight => self.pos = self.word_end(char::is_alphanumeric),
+            EditAction::DeleteLeft => {
+                let start = self.prev_boundary();
+                self.text.drain(start..self.pos);
+                self.pos = start;
+            }
+            EditAction::DeleteRight => {
+                let end = self.next_boundary();
+                self.text.drain(self.pos..end);
+            }
+            EditAction::DeleteWord => {
+                let start = self.word_start(|c| !c.is_whitespace());
+                self.text.drain(start..self.pos);
+                self.pos = start;
+            }
+            EditAction::DeleteToStart => {
+                self.text.drain(..self.pos);
+                self.pos = 0;
+            }
+        }
+        if self.text != before.0 {
+            self.undo.push(before);
+            self.redo.clear();
+        }
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.before_cursor()
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.after_cursor()
+            .graphemes(true)
+            .next()
+            .map_or(self.pos, |g| self.pos + g.len())
+    }
+
+    /// Returns the start of the word before the cursor, skipping any
+    /// graphemes not part of a word first.
+    fn word_start<F: Fn(char) -> bool>(&self, is_word: F) -> usize {
+        let is_word = |g: &str| g.chars().next().is_some_and(&is_word);
+        let mut pos = self.pos;
+        let mut in_word = false;
+        for (idx, g) in self.before_cursor().grapheme_indices(true).rev() {
+            if is_word(g) {
+                in_word = true;
+            } else if in_word {
+                break;
+            }
+            pos = idx;
+        }
+        pos
+    }
+
+    /// Returns the end of the word after the cursor, skipping any
+    /// graphemes not part of a word first.
+    fn word_end<F: Fn(char) -> bool>(&self, is_word: F) -> usize {
+        let is_word = |g: &str| g.chars().next().is_some_and(&is_word);
+        let mut pos = self.pos;
+        let mut in_word = false;
+        for g in self.after_cursor().graphemes(true) {
+            if is_word(g) {
+                in_word = true;
+            } else if in_word {
+                break;
+            }
+            pos += g.len();
+        }
+        pos
+    }
+}
+
+/// Suggestions listed below a line while it is edited.
+pub(crate) struct Suggestions<'a> {
+    /// The suggestions, of which those containing the text are listed
+    pub items: &'a [String],
+    /// The number of suggestions listed at once
+    pub max: usize,
+    /// Formats a suggestion, highlighting it if it is the active one
+    pub format: &'a dyn Fn(&str, bool) -> io::Result<String>,
+}
+
+impl<'a> Suggestions<'a> {
+    /// Returns the suggestions containing `text`, ignoring case.  Those
+    /// starting with it come first.
+    fn matching(&self, text: &str) -> Vec<&'a str> {
+        let text = text.to_lowercase();
+        let mut matches: Vec<_> = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let pos = item.to_lowercase().find(&text)?;
+                Some((pos > 0, item.as_str()))
+            })
+            .collect();
+        matches.sort_by_key(|&(inside, _)| inside);
+        matches.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Formats the listed suggestions, scrolling to the active one.
+    fn lines(&self, matches: &[&str], active: Option<usize>) -> io::Result<Vec<String>> {
+        let offset = active.map_or(0, |active| (active + 1).saturating_sub(self.max));
+        matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(self.max)
+            .map(|(idx, item)| (self.format)(item, active == Some(idx)))
+            .collect()
+    }
+}
+
+/// Formats the status lines of a text.
+type StatusFn<'a> = dyn Fn(&str) -> io::Result<Vec<String>> + 'a;
+
+/// Formats what is shown after a text.
+type SuffixFn<'a> = dyn Fn(&str) -> io::Result<String> + 'a;
+
+/// What is offered below a line while it is edited.
+#[derive(Default)]
+pub(crate) struct Assists<'a> {
+    /// The suggestions listed below the line
+    pub suggestions: Option<&'a Suggestions<'a>>,
+    /// The history recalled with the arrow keys
+    pub history: Option<&'a dyn History>,
+    /// Formats status lines shown right below the line for its text
+    pub status: Option<&'a StatusFn<'a>>,
+    /// Tells which typed characters are let into the line
+    pub accepts: Option<&'a dyn Fn(char) -> bool>,
+    /// The number of characters after which no more can be typed
+    pub max_len: Option<usize>,
+    /// Formats what is shown after the text while it is edited
+    pub suffix: Option<&'a SuffixFn<'a>>,
+}
+
+/// Reads a line from the terminal starting out with `initial` text.
+///
+/// The line starts at `column` of the current row and may wrap over
+/// several rows; the cursor can be moved anywhere within it.  `first` is
+/// a key that was already read and is handled before any other, and
+/// `undo_keys` are the keys undoing and redoing edits.  The line is
+/// terminated with a newline once enter is pressed.
+///
+/// With `suggestions` the matching ones are listed below the line.  The
+/// arrow keys move through them, tab copies the active one into the line
+/// and enter accepts it.  With a `history` the up arrow recalls earlier
+/// entries and the down arrow goes back to later ones.  The `status`
+/// lines are updated after every key.  Characters that `accepts` returns
+/// `false` for are ignored, as are all once the line is `max_len`
+/// characters long.  A `suffix` is shown after the text until enter.
+///
+/// With `allow_quit` Esc erases the line and returns `None` unless a
+/// suggestion is active.
+pub(crate) fn read_line(
+    term: &Term,
+    column: usize,
+    initial: &str,
+    first: Option<Key>,
+    undo_keys: &(Key, Key),
+    allow_quit: bool,
+    assists: &Assists,
+) -> io::Result<Option<String>> {
+    let Assists {
+        suggestions,
+        history,
+        status,
+        accepts,
+        max_len,
+        suffix,
+    } = *assists;
+    let mut line = LineBuffer::new(initial);
+    let mut active = None;
+    // the recalled entry and the text typed before recalling it
+    let mut recalled: Option<(usize, String)> = None;
+    let mut next = first;
+    let mut row = 0;
+    loop {
+        let matches = suggestions.map_or(vec![], |s| s.matching(line.text()));
+        let mut below = match status {
+            Some(status) => status(line.text())?,
+            None => vec![],
+        };
+        if let Some(suggestions) = suggestions {
+            below.extend(suggestions.lines(&matches, active)?);
+        }
+        let shown = match suffix {
+            Some(suffix) => format!("{} {}", line.text(), suffix(line.text())?),
+            None => line.text().into(),
+        };
+        row = redraw(term, &shown, line.before_cursor(), column, row, &below)?;
+        let key = match next.take() {
+            Some(key) => key,
+            None => session::read_key(term)?,
+        };
+        match key {
+            Key::Enter => {
+                if let Some(idx) = active {
+                    line.set_text(matches[idx]);
+                }
+                let row = redraw(term, line.text(), line.text(), column, row, &[])?;
+                finish(term, line.text(), column, row)?;
+                return Ok(Some(line.text().into()));
+            }
+            Key::Escape if allow_quit && active.is_none() => {
+                let row = redraw(term, "", "", column, row, &[])?;
+                finish(term, "", column, row)?;
+                return Ok(None);
+            }
+            Key::ArrowUp if active.is_none() && history.is_some() => {
+                let pos = recalled.as_ref().map_or(0, |&(pos, _)| pos + 1);
+                if let Some(entry) = history.and_then(|history| history.read(pos)) {
+                    let typed = match recalled.take() {
+                        Some((_, typed)) => typed,
+                        None => line.text().into(),
+                    };
+                    line.set_text(&entry);
+                    recalled = Some((pos, typed));
+                }
+                continue;
+            }
+            Key::ArrowDown if recalled.is_some() => {
+                if let Some((pos, typed)) = recalled.take() {
+                    match pos.checked_sub(1) {
+                        Some(pos) => {
+                            let entry = history.and_then(|history| history.read(pos));
+                            line.set_text(&entry.unwrap_or_default());
+                            recalled = Some((pos, typed));
+                        }
+                        None => line.set_text(&typed),
+                    }
+                }
+                continue;
+            }
+            Key::ArrowDown if !matches.is_empty() => {
+                active = Some(active.map_or(0, |idx| (idx + 1).min(matches.len() - 1)));
+                continue;
+            }
+            Key::ArrowUp => {
+                active = active.and_then(|idx| idx.checked_sub(1));
+                continue;
+            }
+            Key::Escape => {
+                active = None;
+                continue;
+            }
+            Key::Tab => match active.or(if matches.is_empty() { None } else { Some(0) }) {
+                Some(idx) => line.set_text(matches[idx]),
+                None => continue,
+            },
+            ref key if *key == undo_keys.0 => line.undo(),
+            ref key if *key == undo_keys.1 => line.redo(),
+            Key::Char(c) if !c.is_control() => {
+                let full = max_len.is_some_and(|max| line.text().chars().count() >= max);
+                if full || accepts.is_some_and(|accepts| !accepts(c)) {
+                    continue;
+                }
+                line.insert(c)
+            }
+            ref key => match edit_action(key) {
+                Some(action) => line.apply(action),
+                None => continue,
+            },
+        }
+        active = None;
+    }
+}
+
+/// Reads a value for `mask` from the terminal starting out with the
+/// `initial` characters.
+///
+/// Only characters allowed by the mask can be typed and enter is ignored
+/// until the mask is filled in or empty.  Returns the value as formatted
+/// by the mask or an empty string if nothing was typed.
+pub(crate) fn read_masked(
+    term: &Term,
+    column: usize,
+    mask: &Mask,
+    initial: &str,
+) -> io::Result<String> {
+    let mut raw = initial.to_string();
+    let mut text = mask.format(&raw);
+    let mut row = redraw(term, &text, &text, column, 0, &[])?;
+    loop {
+        match session::read_key(term)? {
+            Key::Enter if raw.is_empty() || mask.is_complete(&raw) => {
+                finish(term, &text, column, row)?;
+                return Ok(if raw.is_empty() { raw } else { text });
+            }
+            Key::Char(c) if mask.accepts(&raw, c) => raw.push(c),
+            Key::Backspace => {
+                raw.pop();
+            }
+            Key::Char('\u{15}') => raw.clear(),
+            _ => continue,
+        }
+        text = mask.format(&raw);
+        row = redraw(term, &text, &text, column, row, &[])?;
+    }
+}
+
+/// Moves the cursor below a line once it was entered.
+fn finish(term: &Term, text: &str, column: usize, row: usize) -> io::Result<()> {
+    let (end_row, end_col) = position(column + measure_text_width(text), term_width(term));
+    term.move_cursor_down(end_row - row)?;
+    if end_col > 0 || end_row == 0 {
+        term.write_line("")?;
+    }
+    Ok(())
+}
+
+/// Redraws a line starting at `column` while the cursor is `row` rows
+/// below its start and returns the row the cursor is left on, which is
+/// after the `before_cursor` part of the text.  The `below` lines are
+/// written under the line, cut off at the width of the terminal.
+fn redraw(
+    term: &Term,
+    text: &str,
+    before_cursor: &str,
+    column: usize,
+    row: usize,
+    below: &[String],
+) -> io::Result<usize> {
+    let width = term_width(term);
+    term.move_cursor_up(row)?;
+    term.write_str("\r")?;
+    term.move_cursor_right(column)?;
+    term.write_str("\x1b[J")?;
+    term.write_str(text)?;
+    let (end_row, end_col) = position(column + measure_text_width(text), width);
+    if end_col == 0 && end_row > 0 {
+        // the terminal holds the cursor on the last column until the
+        // next character, so move it onto the next row explicitly.
+        term.write_str("\r\n")?;
+    }
+    for line in below {
+        term.write_str("\r\n")?;
+        if width > 0 {
+            term.write_str(&truncate_str(line, width - 1, ""))?;
+        } else {
+            term.write_str(line)?;
+        }
+    }
+    let (row, col) = position(column + measure_text_width(before_cursor), width);
+    term.move_cursor_up(end_row + below.len() - row)?;
+    term.write_str("\r")?;
+    term.move_cursor_right(col)?;
+    Ok(row)
+}
+
+fn term_width(term: &Term) -> usize {
+    term.size().1 as usize
+}
+
+/// Returns the row and column of an offset into a line wrapped at
+/// `width` columns.
+fn position(offset: usize, width: usize) -> (usize, usize) {
+    match offset.checked_div(width) {
+        Some(row) => (row, offset % width),
+        None => (0, offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(text: &str, pos: usize, action: EditAction) -> (String, usize) {
+        let mut line = LineBuffer::new(text);
+        line.pos = text
+            .char_indices()
+            .nth(pos)
+            .map_or(text.len(), |(idx, _)| idx);
+        line.apply(action);
+        (line.text().into(), line.before_cursor().chars().count())
+    }
+
+    #[test]
+    fn test_edit_actions() {
+        assert_eq!(
+            edit("hello world", 11, EditAction::DeleteWord),
+            ("hello ".into(), 6)
+        );
+        assert_eq!(
+            edit("hello world  ", 13, EditAction::DeleteWord),
+            ("hello ".into(), 6)
+        );
+        assert_eq!(
+            edit("hello world", 5, EditAction::DeleteToStart),
+            (" world".into(), 0)
+        );
+        assert_eq!(
+            edit("foo-bar baz", 11, EditAction::WordLeft),
+            ("foo-bar baz".into(), 8)
+        );
+        assert_eq!(
+            edit("foo-bar baz", 8, EditAction::WordLeft),
+            ("foo-bar baz".into(), 4)
+        );
+        assert_eq!(
+            edit("foo-bar baz", 0, EditAction::WordRight),
+            ("foo-bar baz".into(), 3)
+        );
+        assert_eq!(
+            edit("foo-bar baz", 3, EditAction::WordRight),
+            ("foo-bar baz".into(), 7)
+        );
+        assert_eq!(edit("abc", 0, EditAction::DeleteLeft), ("abc".into(), 0));
+        assert_eq!(edit("abc", 1, EditAction::DeleteRight), ("ac".into(), 1));
+    }
+
+    #[test]
+    fn test_graphemes() {
+        assert_eq!(
+            edit("cafe\u{301}", 5, EditAction::DeleteLeft),
+            ("caf".into(), 3)
+        );
+        assert_eq!(
+            edit("cafe\u{301}", 5, EditAction::Left),
+            ("cafe\u{301}".into(), 3)
+        );
+        assert_eq!(edit("a👍🏽b", 1, EditAction::Right), ("a👍🏽b".into(), 3));
+        assert_eq!(edit("a👍🏽b", 1, EditAction::DeleteRight), ("ab".into(), 1));
+        assert_eq!(
+            edit("日本語", 3, EditAction::DeleteLeft),
+            ("日本".into(), 2)
+        );
+        assert_eq!(
+            edit("hé\u{301}llo wörld", 0, EditAction::WordRight),
+            ("hé\u{301}llo wörld".into(), 6)
+        );
+
+        let mut line = LineBuffer::new("cafe");
+        line.insert('\u{301}');
+        line.apply(EditAction::Left);
+        assert_eq!(line.before_cursor(), "caf");
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut line = LineBuffer::new("foo");
+        line.insert(' ');
+        line.insert('b');
+        line.apply(EditAction::Left);
+        line.apply(EditAction::DeleteLeft);
+        assert_eq!(line.text(), "foob");
+        line.undo();
+        assert_eq!((line.text(), line.before_cursor()), ("foo b", "foo "));
+        line.undo();
+        assert_eq!(line.text(), "foo");
+        line.undo();
+        assert_eq!(line.text(), "foo");
+        line.redo();
+        line.redo();
+        assert_eq!(line.text(), "foob");
+        line.undo();
+        line.insert('x');
+        line.redo();
+        assert_eq!(line.text(), "foo xb");
+    }
+
+    #[test]
+    fn test_position() {
+        assert_eq!(position(5, 10), (0, 5));
+        assert_eq!(position(10, 10), (1, 0));
+        assert_eq!(position(25, 10), (2, 5));
+        assert_eq!(position(25, 0), (0, 25));
+    }
+
+    #[test]
+    fn test_key_map() {
+        assert_eq!(edit_action(&Key::Home), Some(EditAction::Home));
+        assert_eq!(
+            edit_action(&Key::Char('\u{17}')),
+            Some(EditAction::DeleteWord)
+        );
+        assert_eq!(
+            edit_action(&Key::UnknownEscSeq(vec!['f'])),
+            Some(EditAction::WordRight)
+        );
+        assert_eq!(edit_action(&Key::Char('w')), None);
+    }
+}
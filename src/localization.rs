@@ -0,0 +1,223 @@
+//! Translations of the words that prompts and themes show.
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+/// The words and sentences built into prompts and themes.
+///
+/// Placeholders in braces such as `{min}` are replaced with values.  A
+/// localization is attached to a prompt with its `localization` method or
+/// to a theme with `ExtendedTheme::localization`.  The default is English.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::{Confirmation, Localization};
+///
+/// let german = Localization {
+///     yes: "ja".into(),
+///     no: "nein".into(),
+///     yes_key: 'j',
+///     no_key: 'n',
+///     error: "Fehler".into(),
+///     ..Localization::default()
+/// };
+/// if Confirmation::new()
+///     .with_text("Weiter?")
+///     .localization(&german)
+///     .interact()?
+/// {
+///     println!("Weiter geht's");
+/// }
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Localization {
+    /// The affirmative answer, defaults to `yes`
+    pub yes: Cow<'static, str>,
+    /// The negative answer, defaults to `no`
+    pub no: Cow<'static, str>,
+    /// The key for the affirmative answer, defaults to `y`
+    pub yes_key: char,
+    /// The key for the negative answer, defaults to `n`
+    pub no_key: char,
+    /// An affirmative answer shown as a value, defaults to `true`
+    pub true_value: Cow<'static, str>,
+    /// A negative answer shown as a value, defaults to `false`
+    pub false_value: Cow<'static, str>,
+    /// The title of errors, defaults to `error`
+    pub error: Cow<'static, str>,
+    /// The title of notices, defaults to `info`
+    pub info: Cow<'static, str>,
+    /// The title of warnings, defaults to `warning`
+    pub warning: Cow<'static, str>,
+    /// Shown after a cancelled prompt, defaults to `cancelled`
+    pub cancelled: Cow<'static, str>,
+    /// Shown in place of a password, defaults to `[hidden]`
+    pub hidden: Cow<'static, str>,
+    /// The label of the filter of a menu, defaults to `Filter`
+    pub filter: Cow<'static, str>,
+    /// Defaults to `Expected a value between {min} and {max}`
+    pub range_between: Cow<'static, str>,
+    /// Defaults to `Expected a value of at least {min}`
+    pub range_min: Cow<'static, str>,
+    /// Defaults to `Expected a value of at most {max}`
+    pub range_max: Cow<'static, str>,
+    /// The page of a list, defaults to `page {page}/{pages}`
+    pub page: Cow<'static, str>,
+    /// The length of a list, defaults to `{total} items`
+    pub items: Cow<'static, str>,
+    /// The checked items of a list, defaults to `{count} of {total} selected`
+    pub selected: Cow<'static, str>,
+    /// The checked items out of those allowed, defaults to
+    /// `{count}/{max} chosen`
+    pub chosen: Cow<'static, str>,
+    /// The length of a typed text, defaults to `{count} characters`
+    pub characters: Cow<'static, str>,
+    /// Shown while the items of a search are fetched, defaults to
+    /// `Loading...`
+    pub loading: Cow<'static, str>,
+    /// The text of a pause prompt, defaults to `Press any key to continue...`
+    pub press_any_key: Cow<'static, str>,
+    /// The hint below a message, defaults to `Press enter to continue...`
+    pub press_enter: Cow<'static, str>,
+    /// The time left until a prompt picks its default, defaults to
+    /// `auto-selecting '{default}' in {seconds}s`
+    pub countdown: Cow<'static, str>,
+    /// Asks for the number of an item without cursor movement, defaults
+    /// to `Enter choice [1-{total}]`
+    pub enter_choice: Cow<'static, str>,
+    /// Asks for the numbers of checked items without cursor movement,
+    /// defaults to `Enter choices [1-{total}] separated by commas`
+    pub enter_choices: Cow<'static, str>,
+    /// Asks for the order of items without cursor movement, defaults to
+    /// `Enter the order [1-{total}] separated by commas`
+    pub enter_order: Cow<'static, str>,
+    /// Defaults to `enter the number of one item`
+    pub invalid_choice: Cow<'static, str>,
+    /// Defaults to `enter the numbers of items, eg: 1,3-4`
+    pub invalid_choices: Cow<'static, str>,
+    /// Defaults to `enter the numbers of items, eg: 3,1-2`
+    pub invalid_order: Cow<'static, str>,
+    /// Shown when a search finds nothing, defaults to `nothing was found,
+    /// try another query`
+    pub nothing_found: Cow<'static, str>,
+    /// The prompt of a confirmation phrase, defaults to
+    /// `Type "{phrase}" to confirm`
+    pub confirm_phrase: Cow<'static, str>,
+    /// Defaults to `The text does not match`
+    pub phrase_mismatch: Cow<'static, str>,
+    /// Shown when a text does not fit its mask, defaults to
+    /// `Expected a value like {mask}`
+    pub mask_mismatch: Cow<'static, str>,
+    /// Shown when more items are checked than allowed, defaults to
+    /// `choose at most {max} items`
+    pub too_many: Cow<'static, str>,
+    /// Asked by a wizard with defaults, defaults to `Accept all defaults?`
+    pub accept_defaults: Cow<'static, str>,
+}
+
+const ENGLISH: Localization = Localization {
+    yes: Cow::Borrowed("yes"),
+    no: Cow::Borrowed("no"),
+    yes_key: 'y',
+    no_key: 'n',
+    true_value: Cow::Borrowed("true"),
+    false_value: Cow::Borrowed("false"),
+    error: Cow::Borrowed("error"),
+    info: Cow::Borrowed("info"),
+    warning: Cow::Borrowed("warning"),
+    cancelled: Cow::Borrowed("cancelled"),
+    hidden: Cow::Borrowed("[hidden]"),
+    filter: Cow::Borrowed("Filter"),
+    range_between: Cow::Borrowed("Expected a value between {min} and {max}"),
+    range_min: Cow::Borrowed("Expected a value of at least {min}"),
+    range_max: Cow::Borrowed("Expected a value of at most {max}"),
+    page: Cow::Borrowed("page {page}/{pages}"),
+    items: Cow::Borrowed("{total} items"),
+    selected: Cow::Borrowed("{count} of {total} selected"),
+    chosen: Cow::Borrowed("{count}/{max} chosen"),
+    characters: Cow::Borrowed("{count} characters"),
+    loading: Cow::Borrowed("Loading..."),
+    press_any_key: Cow::Borrowed("Press any key to continue..."),
+    press_enter: Cow::Borrowed("Press enter to continue..."),
+    countdown: Cow::Borrowed("auto-selecting '{default}' in {seconds}s"),
+    enter_choice: Cow::Borrowed("Enter choice [1-{total}]"),
+    enter_choices: Cow::Borrowed("Enter choices [1-{total}] separated by commas"),
+    enter_order: Cow::Borrowed("Enter the order [1-{total}] separated by commas"),
+    invalid_choice: Cow::Borrowed("enter the number of one item"),
+    invalid_choices: Cow::Borrowed("enter the numbers of items, eg: 1,3-4"),
+    invalid_order: Cow::Borrowed("enter the numbers of items, eg: 3,1-2"),
+    nothing_found: Cow::Borrowed("nothing was found, try another query"),
+    confirm_phrase: Cow::Borrowed("Type \"{phrase}\" to confirm"),
+    phrase_mismatch: Cow::Borrowed("The text does not match"),
+    mask_mismatch: Cow::Borrowed("Expected a value like {mask}"),
+    too_many: Cow::Borrowed("choose at most {max} items"),
+    accept_defaults: Cow::Borrowed("Accept all defaults?"),
+};
+
+impl Default for Localization {
+    fn default() -> Localization {
+        ENGLISH
+    }
+}
+
+impl Localization {
+    /// Replaces the placeholders of a template with values.
+    pub(crate) fn fill(template: &str, values: &[(&str, &dyn ToString)]) -> String {
+        let mut rv = template.to_string();
+        for &(name, value) in values {
+            rv = rv.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        rv
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Vec<Localization>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls `f` with the localization of the prompt being rendered.
+pub(crate) fn with<R, F: FnOnce(&Localization) -> R>(f: F) -> R {
+    ACTIVE.with(|active| match active.borrow().last() {
+        Some(localization) => f(localization),
+        None => f(&ENGLISH),
+    })
+}
+
+/// Keeps a localization active on the current thread until dropped.
+pub(crate) struct Scope(());
+
+impl Scope {
+    pub(crate) fn enter(localization: &Localization) -> Scope {
+        ACTIVE.with(|active| active.borrow_mut().push(localization.clone()));
+        Scope(())
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| active.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        let german = Localization {
+            yes: "ja".into(),
+            ..Localization::default()
+        };
+        assert_eq!(with(|l| l.yes.to_string()), "yes");
+        {
+            let _scope = Scope::enter(&german);
+            assert_eq!(with(|l| l.yes.to_string()), "ja");
+        }
+        assert_eq!(with(|l| l.yes.to_string()), "yes");
+        let text = Localization::fill(&german.range_between, &[("min", &1), ("max", &"9")]);
+        assert_eq!(text, "Expected a value between 1 and 9");
+    }
+}
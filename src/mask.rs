@@ -0,0 +1,148 @@
+//! Format templates restricting what can be typed into an input.
+use std::fmt;
+
+/// A position in a mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl Slot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Slot::Digit => c.is_ascii_digit(),
+            Slot::Letter => c.is_alphabetic(),
+            Slot::Alphanumeric => c.is_alphanumeric(),
+            Slot::Literal(_) => false,
+        }
+    }
+}
+
+/// A mask like `####-##-##` or `(###) ###-####`.
+///
+/// `#` stands for a digit, `a` for a letter and `*` for a letter or
+/// digit.  All other characters are literals inserted automatically; a
+/// backslash makes the following character a literal.
+#[derive(Debug, Clone)]
+pub(crate) struct Mask {
+    mask: String,
+    slots: Vec<Slot>,
+}
+
+impl Mask {
+    /// Parses a mask.
+    pub fn new(mask: &str) -> Mask {
+        let mut slots = vec![];
+        let mut chars = mask.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '#' => Slot::Digit,
+                'a' => Slot::Letter,
+                '*' => Slot::Alphanumeric,
+                '\\' => match chars.next() {
+                    Some(c) => Slot::Literal(c),
+                    None => break,
+                },
+                c => Slot::Literal(c),
+            });
+        }
+        Mask {
+            mask: mask.into(),
+            slots,
+        }
+    }
+
+    fn inputs(&self) -> impl Iterator<Item = Slot> + '_ {
+        self.slots
+            .iter()
+            .cloned()
+            .filter(|slot| !matches!(slot, Slot::Literal(_)))
+    }
+
+    /// Checks if `c` can be typed after the characters in `raw`.
+    pub fn accepts(&self, raw: &str, c: char) -> bool {
+        self.inputs()
+            .nth(raw.chars().count())
+            .is_some_and(|slot| slot.accepts(c))
+    }
+
+    /// Checks if `raw` fills all positions of the mask.
+    pub fn is_complete(&self, raw: &str) -> bool {
+        raw.chars().count() == self.inputs().count()
+    }
+
+    /// Places the typed characters in the mask.
+    ///
+    /// Literals are filled in up to the next position still to be typed.
+    pub fn format(&self, raw: &str) -> String {
+        let mut rv = String::new();
+        let mut chars = raw.chars();
+        for slot in &self.slots {
+            match *slot {
+                Slot::Literal(c) => rv.push(c),
+                _ => match chars.next() {
+                    Some(c) => rv.push(c),
+                    None => break,
+                },
+            }
+        }
+        rv
+    }
+
+    /// Returns the typed characters of a value with or without the
+    /// literals of the mask, or `None` if it does not fit the mask.
+    pub fn parse(&self, value: &str) -> Option<String> {
+        let mut raw = String::new();
+        let mut slots = self.slots.iter().cloned();
+        'chars: for c in value.chars() {
+            for slot in slots.by_ref() {
+                match slot {
+                    Slot::Literal(lit) if lit == c => continue 'chars,
+                    Slot::Literal(_) => {}
+                    slot if slot.accepts(c) => {
+                        raw.push(c);
+                        continue 'chars;
+                    }
+                    _ => return None,
+                }
+            }
+            return None;
+        }
+        Some(raw)
+    }
+}
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        let mask = Mask::new("(###) ###-####");
+        assert_eq!(mask.format(""), "(");
+        assert_eq!(mask.format("555"), "(555) ");
+        assert_eq!(mask.format("5551"), "(555) 1");
+        assert!(mask.accepts("555", '1'));
+        assert!(!mask.accepts("555", 'x'));
+        assert!(!mask.accepts("5551234567", '8'));
+        assert!(mask.is_complete("5551234567"));
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+
+        assert_eq!(mask.parse("(555) 123-4567"), Some("5551234567".into()));
+        assert_eq!(mask.parse("5551234567"), Some("5551234567".into()));
+        assert_eq!(mask.parse("555-1"), None);
+
+        let mask = Mask::new("a\\#*");
+        assert_eq!(mask.format("x9"), "x#9");
+        assert!(!mask.accepts("", '1'));
+    }
+}
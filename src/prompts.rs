@@ -1,11 +1,28 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::io;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
-use console::Term;
-use theme::{get_default_theme, TermThemeRenderer, Theme};
+use console::{measure_text_width, Key, Term};
+use env;
+use error::{Error, Result};
+use history::History;
+use line;
+use localization::{self, Localization};
+use mask::Mask;
+use session::{self, KeyInput};
+use theme::{
+    get_default_theme, CompleteCallback, ConfirmationLabels, InvalidKeyFeedback, SelectionStyle,
+    Severity, TermThemeRenderer, Theme,
+};
 use validate::Validator;
 
+type ValidatorCallback = Box<dyn Fn(&str) -> Option<String>>;
+type ParserCallback<T> = Box<dyn Fn(&str) -> Result<T, String>>;
+type CharFilterCallback = Box<dyn Fn(char) -> bool>;
+
 /// Renders a simple confirmation prompt.
 ///
 /// ## Example usage
@@ -25,7 +42,55 @@ pub struct Confirmation<'a> {
     text: String,
     default: bool,
     show_default: bool,
+    labels: ConfirmationLabels,
+    invalid_key: InvalidKeyFeedback,
+    help: Option<String>,
+    theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    report_cancelled: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<bool>,
+    assume_default: bool,
+    env_key: Option<String>,
+    default_without_tty: bool,
+    timeout: Option<Duration>,
+    idle: Option<(Duration, IdleAction)>,
+}
+
+/// Renders a prompt confirmed by typing a phrase, such as the name of
+/// something about to be deleted.
+///
+/// A mismatching text is shown as an error and asked for again.  An
+/// empty line cancels the prompt.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::ConfirmPhrase;
+///
+/// if ConfirmPhrase::new().with_phrase("my-project").interact()? {
+///     println!("Deleting my-project");
+/// }
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct ConfirmPhrase<'a> {
+    prompt: Option<String>,
+    phrase: String,
+    mismatch_err: Option<String>,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    report_cancelled: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<bool>,
+    assume_default: bool,
 }
 
 /// Renders a confirmation prompt with several options.
@@ -34,7 +99,7 @@ pub struct Confirmation<'a> {
 ///
 /// ```rust,no_run
 /// # fn test() -> Result<(), Box<std::error::Error>> {
-/// use dialoguer::Confirmation;
+/// use dialoguer::{theme::ColoredTheme, KeyPrompt};
 ///
 /// let rv = KeyPrompt::with_theme(&ColoredTheme::default())
 ///     .with_text("Execute or preview?")
@@ -51,13 +116,191 @@ pub struct Confirmation<'a> {
 pub struct KeyPrompt<'a> {
     text: String,
     default: usize,
-    items: Vec<char>,
+    items: Vec<Key>,
+    descriptions: Vec<Option<String>>,
     show_default: bool,
+    timeout: Option<Duration>,
+    invalid_key: InvalidKeyFeedback,
+    theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<Key>,
+    assume_default: bool,
+}
+
+/// An item of a `KeyPrompt`.
+///
+/// This is implemented for plain characters and keys and for
+/// `(char, &str)` and `(Key, &str)` pairs of a key and its description.
+pub trait KeyItem {
+    /// The key selecting the item.
+    fn key(&self) -> Key;
+
+    /// The description of the key shown on the help line.
+    fn description(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl KeyItem for char {
+    fn key(&self) -> Key {
+        Key::Char(*self)
+    }
+}
+
+impl KeyItem for Key {
+    fn key(&self) -> Key {
+        self.clone()
+    }
+}
+
+impl KeyItem for (char, &str) {
+    fn key(&self) -> Key {
+        Key::Char(self.0)
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(self.1)
+    }
+}
+
+impl KeyItem for (Key, &str) {
+    fn key(&self) -> Key {
+        self.0.clone()
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(self.1)
+    }
+}
+
+/// Asks a series of confirmations, eg: for each file about to be removed.
+///
+/// Besides `y` and `n` each prompt can be answered with `a` to confirm
+/// it and all remaining ones or with `q` to decline it and all remaining
+/// ones.  The remaining prompts are then not shown anymore.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::{ConfirmationSeries, SeriesAnswer};
+///
+/// let mut series = ConfirmationSeries::new();
+/// for file in &["a.txt", "b.txt", "c.txt"] {
+///     match series.ask(&format!("Remove {}?", file))? {
+///         SeriesAnswer::Yes | SeriesAnswer::All => println!("Removing {}", file),
+///         SeriesAnswer::No => {}
+///         SeriesAnswer::Quit => break,
+///     }
+/// }
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct ConfirmationSeries<'a> {
+    default: Option<bool>,
+    theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    answered: Option<SeriesAnswer>,
+}
+
+/// The answer to a prompt of a `ConfirmationSeries`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesAnswer {
+    /// The prompt was confirmed
+    Yes,
+    /// The prompt was declined
+    No,
+    /// The prompt and all remaining ones were confirmed
+    All,
+    /// The prompt and all remaining ones were declined
+    Quit,
+}
+
+/// What a prompt does once no key was pressed for the time set with
+/// `idle_timeout`, eg: in a kiosk left unattended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    /// Shows the prompt again as if it was just asked
+    Restart,
+    /// Answers with the default
+    SelectDefault,
+}
+
+impl SeriesAnswer {
+    /// Returns `true` if the prompt was confirmed.
+    pub fn is_yes(self) -> bool {
+        self == SeriesAnswer::Yes || self == SeriesAnswer::All
+    }
+}
+
+/// Renders a message and waits for a key press.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::Pause;
+///
+/// println!("The configuration was written.");
+/// Pause::new().interact()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Pause<'a> {
+    text: Option<String>,
+    key: Option<Key>,
+    theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+}
+
+/// Renders a notice and waits until it is acknowledged with enter.
+///
+/// The message can span multiple lines and is styled by the theme
+/// according to its severity.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::theme::Severity;
+/// use dialoguer::Message;
+///
+/// Message::new()
+///     .with_text("All data on /dev/sdb will be erased.\nMake sure you have a backup.")
+///     .severity(Severity::Warning)
+///     .boxed(true)
+///     .interact()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Message<'a> {
+    text: String,
+    severity: Severity,
+    boxed: bool,
+    hint: Option<String>,
+    key: Key,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
 }
 
 /// Renders a simple input prompt.
 ///
+/// The value can be edited with the arrow keys and the readline
+/// shortcuts Ctrl-A/E, Ctrl-W, Ctrl-U and Alt-B/F.  Edits can be undone
+/// with Ctrl-Z and redone with Ctrl-Y.
+///
 /// ## Example usage
 ///
 /// ```rust,no_run
@@ -73,10 +316,39 @@ pub struct Input<'a, T> {
     default: Option<T>,
     show_default: bool,
     initial_text: Option<String>,
+    placeholder: Option<String>,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<T>,
+    assume_default: bool,
+    env_key: Option<String>,
+    default_without_tty: bool,
     permit_empty: bool,
-    validator: Option<Box<dyn Fn(&str) -> Option<String>>>,
+    validator: Option<ValidatorCallback>,
+    typing_validator: Option<ValidatorCallback>,
+    char_filter: Option<CharFilterCallback>,
+    max_length: Option<usize>,
+    length_counter: bool,
+    parser: ParserCallback<T>,
+    undo_keys: (Key, Key),
+    mask: Option<Mask>,
+    strip_mask: bool,
+    max_attempts: Option<usize>,
+    suggestions: Vec<String>,
+    max_suggestions: usize,
+    history: Option<RefCell<Box<dyn History>>>,
+    live_feedback: bool,
+    raw_mode: bool,
+    min: Option<T>,
+    max: Option<T>,
+    compare: Option<fn(&T, &T) -> Option<Ordering>>,
 }
+
 /// Renders a password input prompt.
 ///
 /// ## Example usage
@@ -93,8 +365,19 @@ pub struct Input<'a, T> {
 /// ```
 pub struct PasswordInput<'a> {
     prompt: String,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<String>,
+    env_key: Option<String>,
     allow_empty_password: bool,
+    allow_reveal: bool,
+    read_piped: bool,
+    mask: Option<char>,
     confirmation_prompt: Option<(String, String)>,
 }
 
@@ -116,11 +399,29 @@ impl<'a> Confirmation<'a> {
             text: "".into(),
             default: true,
             show_default: true,
+            labels: ConfirmationLabels::default(),
+            invalid_key: InvalidKeyFeedback::Silent,
+            help: None,
             theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            report_cancelled: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
+            env_key: None,
+            default_without_tty: false,
+            timeout: None,
+            idle: None,
         }
     }
 
     /// Sets the confirmation text.
+    ///
+    /// Lines before the last one of a multi-line text are shown above the
+    /// line the question is asked on.
     pub fn with_text(&mut self, text: &str) -> &mut Confirmation<'a> {
         self.text = text.into();
         self
@@ -142,190 +443,1394 @@ impl<'a> Confirmation<'a> {
         self
     }
 
-    /// Enables user interaction and returns the result.
+    /// Sets the words reported for the answers.
     ///
-    /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<bool> {
-        self.interact_on(&Term::stderr())
+    /// The keys to answer with are set to the first letter of the
+    /// words.  Use `with_keys` to pick other keys.
+    pub fn with_labels(&mut self, yes: &str, no: &str) -> &mut Confirmation<'a> {
+        self.labels.yes = Some(yes.into());
+        self.labels.no = Some(no.into());
+        if let Some(c) = yes.chars().next() {
+            self.labels.yes_key = c.to_lowercase().next().unwrap_or(c);
+        }
+        if let Some(c) = no.chars().next() {
+            self.labels.no_key = c.to_lowercase().next().unwrap_or(c);
+        }
+        self
     }
 
-    /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<bool> {
-        let mut render = TermThemeRenderer::new(term, self.theme);
+    /// Sets how the prompt reacts to keys it ignores.
+    ///
+    /// The default is to ignore them silently.
+    pub fn on_invalid_key(&mut self, val: InvalidKeyFeedback) -> &mut Confirmation<'a> {
+        self.invalid_key = val;
+        self
+    }
 
-        render.confirmation_prompt(
-            &self.text,
-            if self.show_default {
-                Some(self.default)
-            } else {
-                None
-            },
-        )?;
-        loop {
-            let input = term.read_char()?;
-            let rv = match input {
-                'y' | 'Y' => true,
-                'n' | 'N' => false,
-                '\n' | '\r' => self.default,
-                _ => {
-                    continue;
-                }
-            };
-            term.clear_line()?;
-            render.confirmation_prompt_selection(&self.text, rv)?;
-            return Ok(rv);
-        }
+    /// Sets the keys confirming and declining the prompt.
+    ///
+    /// The keys are matched case insensitively.  The defaults are `y`
+    /// and `n`.
+    pub fn with_keys(&mut self, yes: char, no: char) -> &mut Confirmation<'a> {
+        self.labels.yes_key = yes;
+        self.labels.no_key = no;
+        self
     }
-}
 
-impl<'a> Default for KeyPrompt<'a> {
-    fn default() -> KeyPrompt<'a> {
-        KeyPrompt::new()
+    /// Shows a help line above the prompt.
+    ///
+    /// The line is cleared along with the prompt.
+    pub fn with_help(&mut self, text: &str) -> &mut Confirmation<'a> {
+        self.help = Some(text.into());
+        self
     }
-}
 
-impl<'a> KeyPrompt<'a> {
-    /// Creates the prompt with a specific text.
-    pub fn new() -> KeyPrompt<'static> {
-        KeyPrompt::with_theme(get_default_theme())
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Confirmation<'a> {
+        self.report = val;
+        self
     }
 
-    /// Sets a theme other than the default one.
-    pub fn with_theme(theme: &'a dyn Theme) -> KeyPrompt<'a> {
-        KeyPrompt {
-            text: "".into(),
-            default: 100,
-            items: vec![],
-            show_default: true,
-            theme,
-        }
+    /// Indicates whether to report a cancelled prompt.
+    pub fn report_cancelled(&mut self, val: bool) -> &mut Confirmation<'a> {
+        self.report_cancelled = val;
+        self
     }
 
-    /// Sets the KeyPrompt text.
-    pub fn with_text(&mut self, text: &str) -> &mut KeyPrompt<'a> {
-        self.text = text.into();
+    /// Sets a callback invoked with the prompt and the answer.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Confirmation<'a> {
+        self.on_complete = Some(Box::new(f));
         self
     }
 
-    /// Adds multiple items to the selector.
-    pub fn items(&mut self, items: &[char]) -> &mut KeyPrompt<'a> {
-        for item in items {
-            self.items.push(*item);
-        }
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: bool) -> &mut Confirmation<'a> {
+        self.assumed = Some(value);
         self
     }
 
-    /// Overrides the default.
-    pub fn default(&mut self, val: usize) -> &mut KeyPrompt<'a> {
-        self.default = val;
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume_default(&mut self) -> &mut Confirmation<'a> {
+        self.assume_default = true;
         self
     }
 
-    /// Disables or enables the default value display.
+    /// Takes the answer from an environment variable if it is set.
     ///
-    /// The default is to append `[y/n]` to the prompt to tell the
-    /// user which keys to press.  This also renders the default choice
-    /// in uppercase.  The default is selected on enter.
-    pub fn show_default(&mut self, val: bool) -> &mut KeyPrompt<'a> {
-        self.show_default = val;
+    /// The prompt is not shown then but the answer is still validated
+    /// and reported.  An invalid value is returned as an error.
+    ///
+    /// Besides the keys of the prompt the values `yes`, `no`, `true`,
+    /// `false`, `1` and `0` are understood.
+    pub fn env_key(&mut self, key: &str) -> &mut Confirmation<'a> {
+        self.env_key = Some(key.into());
+        self
+    }
+
+    /// Answers with the default without asking if stdin is not a
+    /// terminal, eg: when run in CI.
+    ///
+    /// The answer is still reported.
+    pub fn accept_default_without_tty(&mut self, val: bool) -> &mut Confirmation<'a> {
+        self.default_without_tty = val;
+        self
+    }
+
+    /// Picks the default once no key was pressed for the given time.
+    ///
+    /// The remaining seconds are shown after the prompt until a key is
+    /// pressed.  If the prompt is not rendered on a terminal the default
+    /// is picked right away.  On platforms other than unix the prompt
+    /// cannot wait for keys with a timeout and waits for a key instead.
+    pub fn timeout(&mut self, val: Duration) -> &mut Confirmation<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
+    /// Restarts the prompt or answers with the default once no key was
+    /// pressed for the given time.
+    ///
+    /// The timer starts over with every key.  A restart also starts the
+    /// countdown of `timeout` over.  On platforms other than unix the
+    /// prompt cannot wait for keys with a timeout and never goes idle.
+    pub fn idle_timeout(&mut self, timeout: Duration, action: IdleAction) -> &mut Confirmation<'a> {
+        self.idle = Some((timeout, action));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Confirmation<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Confirmation<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Confirmation<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Confirmation<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Confirmation<'a> {
+        self.term = Term::stderr();
         self
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<char> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<bool> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but lets the user back out with Esc or `q`.
+    ///
+    /// Returns `None` if the user backed out, in which case the prompt is
+    /// reported as cancelled.
+    pub fn interact_opt(&self) -> Result<Option<bool>> {
+        Ok(self._interact_on(&self.term, true)?)
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<char> {
-        if self.items.is_empty() {
-            panic!("Expected items to be specified")
-        }
+    pub fn interact_on(&self, term: &Term) -> Result<bool> {
+        Ok(self
+            ._interact_on(term, false)?
+            .ok_or_else(|| io::Error::other("Quit not allowed in this case"))?)
+    }
+
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<bool>> {
+        Ok(self._interact_on(term, true)?)
+    }
+
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<bool>> {
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_report_cancelled(self.report_cancelled);
+        render.set_on_complete(self.on_complete.as_deref());
+        let labels = self.labels.localized();
 
-        render.key_prompt(
+        if let Some((key, value)) = env::answer(self.env_key.as_deref()) {
+            let rv = self
+                .parse_answer(&labels, &value)
+                .ok_or_else(|| env::invalid(key, "expected yes or no"))?;
+            render.confirmation_prompt_selection(&self.text, rv, &labels)?;
+            return Ok(Some(rv));
+        }
+        let assume_default =
+            self.assume_default || self.default_without_tty && !session::stdin_is_term();
+        if let Some(rv) = self
+            .assumed
+            .or(Some(self.default).filter(|_| assume_default))
+        {
+            render.confirmation_prompt_selection(&self.text, rv, &labels)?;
+            return Ok(Some(rv));
+        }
+
+        session::question(&self.text)?;
+        self.show(&mut render, &labels)?;
+        let mut deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let default_label = localization::with(|l| {
+            let fallback = if self.default { &l.yes } else { &l.no };
+            labels.word(self.default, fallback).to_string()
+        });
+        loop {
+            // the countdown stops once a key was pressed
+            let input = match deadline.take() {
+                Some(deadline) if session::is_term(term) => {
+                    read_key_until(&mut render, term, deadline, &default_label)?
+                        .unwrap_or(Key::Enter)
+                }
+                Some(_) => Key::Enter,
+                None if session::is_term(term) => {
+                    let idle = self.idle.map(|(idle, _)| idle);
+                    match (session::read_key_input(term, idle)?, self.idle) {
+                        (KeyInput::Pressed(key), _) => key,
+                        (KeyInput::Resized, _) => continue,
+                        (KeyInput::Idle, Some((_, IdleAction::SelectDefault))) => Key::Enter,
+                        (KeyInput::Idle, _) => {
+                            term.clear_line()?;
+                            render.clear()?;
+                            self.show(&mut render, &labels)?;
+                            deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+                            continue;
+                        }
+                    }
+                }
+                None => Key::Char(session::read_char(term)?),
+            };
+            let rv = match input {
+                Key::Enter | Key::Char('\n') | Key::Char('\r') => self.default,
+                Key::Char(c) if same_key(c, labels.yes_key) => true,
+                Key::Char(c) if same_key(c, labels.no_key) => false,
+                Key::Escape | Key::Char('q') if allow_quit => {
+                    term.clear_line()?;
+                    render.clear()?;
+                    render.cancelled(&self.text)?;
+                    return Ok(None);
+                }
+                _ => {
+                    render.invalid_key(self.invalid_key)?;
+                    continue;
+                }
+            };
+            term.clear_line()?;
+            render.clear()?;
+            render.confirmation_prompt_selection(&self.text, rv, &labels)?;
+            return Ok(Some(rv));
+        }
+    }
+
+    /// Shows the help and the prompt.
+    fn show(&self, render: &mut TermThemeRenderer, labels: &ConfirmationLabels) -> io::Result<()> {
+        if let Some(ref help) = self.help {
+            render.help(help)?;
+        }
+        render.confirmation_prompt(
             &self.text,
             if self.show_default {
                 Some(self.default)
             } else {
                 None
             },
-            &self.items,
-        )?;
-        loop {
-            let input = term.read_char()?.to_ascii_lowercase();
-            let rv = if input == '\n' || input == '\r' {
-                let c = self.items.get(self.default);
-                match c {
-                    Some(c) => c,
-                    _ => {
-                        continue;
-                    }
-                }
-            } else if self.items.contains(&input) {
-                &input
-            } else {
-                continue;
-            };
-            term.clear_line()?;
-            render.key_prompt_selection(&self.text, *rv)?;
-            return Ok(*rv);
+            labels,
+        )
+    }
+
+    /// Parses a written answer.
+    fn parse_answer(&self, labels: &ConfirmationLabels, value: &str) -> Option<bool> {
+        let value = value.trim().to_lowercase();
+        let is = |val: bool| {
+            let key = if val { labels.yes_key } else { labels.no_key };
+            let mut chars = value.chars();
+            let word = localization::with(|l| {
+                labels
+                    .word(val, if val { &l.yes } else { &l.no })
+                    .to_lowercase()
+            });
+            matches!((chars.next(), chars.next()), (Some(c), None) if same_key(c, key))
+                || word == value
+        };
+        match value.as_str() {
+            "yes" | "true" | "1" => Some(true),
+            "no" | "false" | "0" => Some(false),
+            _ if is(true) => Some(true),
+            _ if is(false) => Some(false),
+            _ => None,
         }
     }
 }
 
-impl<'a, T> Default for Input<'a, T>
-where
-    T: Clone + FromStr + Display,
-    T::Err: Display + Debug,
-{
-    fn default() -> Input<'a, T> {
-        Input::new()
+impl<'a> Default for ConfirmPhrase<'a> {
+    fn default() -> ConfirmPhrase<'a> {
+        ConfirmPhrase::new()
     }
 }
 
-impl<'a, T> Input<'a, T>
-where
-    T: Clone + FromStr + Display,
-    T::Err: Display + Debug,
-{
-    /// Creates a new input prompt.
-    pub fn new() -> Input<'static, T> {
-        Input::with_theme(get_default_theme())
+impl<'a> ConfirmPhrase<'a> {
+    /// Creates the prompt.
+    pub fn new() -> ConfirmPhrase<'static> {
+        ConfirmPhrase::with_theme(get_default_theme())
     }
 
-    /// Creates an input with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Input<'a, T> {
-        Input {
-            prompt: "".into(),
-            default: None,
-            show_default: true,
-            initial_text: None,
+    /// Sets a theme other than the default one.
+    pub fn with_theme(theme: &'a dyn Theme) -> ConfirmPhrase<'a> {
+        ConfirmPhrase {
+            prompt: None,
+            phrase: "".into(),
+            mismatch_err: None,
+            help: None,
             theme,
-            permit_empty: false,
-            validator: None,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            report_cancelled: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
         }
     }
 
-    /// Sets the input prompt.
-    pub fn with_prompt(&mut self, prompt: &str) -> &mut Input<'a, T> {
-        self.prompt = prompt.into();
+    /// Sets the phrase that has to be typed.
+    pub fn with_phrase(&mut self, phrase: &str) -> &mut ConfirmPhrase<'a> {
+        self.phrase = phrase.into();
         self
     }
 
-    /// Sets whether the default can be editable.
-    pub fn with_initial_text(&mut self, val: &str) -> &mut Input<'a, T> {
-        self.initial_text = Some(val.into());
+    /// Sets the prompt.
+    ///
+    /// The default is `Type "<phrase>" to confirm`.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut ConfirmPhrase<'a> {
+        self.prompt = Some(prompt.into());
         self
     }
 
-    /// Sets a default.
+    /// Sets the error shown when the typed text does not match.
     ///
-    /// Out of the box the prompt does not have a default and will continue
+    /// The default is `The text does not match`.
+    pub fn mismatch_error(&mut self, err: &str) -> &mut ConfirmPhrase<'a> {
+        self.mismatch_err = Some(err.into());
+        self
+    }
+
+    /// Shows a help line above the prompt.
+    ///
+    /// The line is cleared along with the prompt.
+    pub fn with_help(&mut self, text: &str) -> &mut ConfirmPhrase<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut ConfirmPhrase<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Indicates whether to report a cancelled prompt.
+    pub fn report_cancelled(&mut self, val: bool) -> &mut ConfirmPhrase<'a> {
+        self.report_cancelled = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut ConfirmPhrase<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: bool) -> &mut ConfirmPhrase<'a> {
+        self.assumed = Some(value);
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.  The
+    /// default is to not confirm.
+    pub fn assume_default(&mut self) -> &mut ConfirmPhrase<'a> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut ConfirmPhrase<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut ConfirmPhrase<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut ConfirmPhrase<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut ConfirmPhrase<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut ConfirmPhrase<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Returns `true` once the phrase was typed and `false` if the
+    /// prompt was cancelled with an empty line.
+    pub fn interact(&self) -> Result<bool> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<bool> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_report_cancelled(self.report_cancelled);
+        render.set_on_complete(self.on_complete.as_deref());
+        let prompt = match self.prompt {
+            Some(ref prompt) => prompt.clone(),
+            None => localization::with(|l| {
+                Localization::fill(&l.confirm_phrase, &[("phrase", &self.phrase)])
+            }),
+        };
+
+        match self.assumed.or(Some(false).filter(|_| self.assume_default)) {
+            Some(true) => {
+                let labels = ConfirmationLabels::default();
+                render.confirmation_prompt_selection(&prompt, true, &labels)?;
+                return Ok(true);
+            }
+            Some(false) => {
+                render.cancelled(&prompt)?;
+                return Ok(false);
+            }
+            None => {}
+        }
+
+        session::question(&prompt)?;
+        loop {
+            if let Some(ref help) = self.help {
+                render.help(help)?;
+            }
+            render.input_prompt(&prompt, None)?;
+            let input = if session::is_term(term) {
+                let undo_keys = (Key::Char('\u{1a}'), Key::Char('\u{19}'));
+                let assists = line::Assists::default();
+                line::read_line(term, render.column(), "", None, &undo_keys, false, &assists)?
+                    .unwrap_or_default()
+            } else {
+                session::read_line(term, None)?
+            };
+            render.add_input(&input);
+            term.clear_line()?;
+            render.clear()?;
+            if input.is_empty() {
+                render.cancelled(&prompt)?;
+                return Ok(false);
+            } else if input == self.phrase {
+                render.confirmation_prompt_selection(
+                    &prompt,
+                    true,
+                    &ConfirmationLabels::default(),
+                )?;
+                return Ok(true);
+            }
+            match self.mismatch_err {
+                Some(ref err) => render.error(err)?,
+                None => render.error(&localization::with(|l| l.phrase_mismatch.to_string()))?,
+            }
+        }
+    }
+}
+
+/// Creates the error returned when a default is assumed but none is
+/// set.
+pub(crate) fn no_default() -> io::Error {
+    Error::InvalidDefault("no default to assume".into()).into()
+}
+
+/// Compares two keys ignoring their case.
+fn same_key(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Reads a key while counting down to picking `default` at `deadline`.
+///
+/// Returns `None` if no key was pressed in time.  The countdown is
+/// removed as soon as a key is pressed.
+fn read_key_until(
+    render: &mut TermThemeRenderer,
+    term: &Term,
+    deadline: Instant,
+    default: &str,
+) -> io::Result<Option<Key>> {
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        let seconds = left.as_millis().div_ceil(1000) as u64;
+        if seconds == 0 {
+            render.clear_countdown()?;
+            return Ok(None);
+        }
+        render.countdown(default, seconds)?;
+        // wake up when the countdown has to be updated
+        let tick = left.saturating_sub(Duration::from_secs(seconds - 1));
+        if session::key_ready(tick)? {
+            render.clear_countdown()?;
+            return session::read_key(term).map(Some);
+        }
+    }
+}
+
+impl<'a> Default for KeyPrompt<'a> {
+    fn default() -> KeyPrompt<'a> {
+        KeyPrompt::new()
+    }
+}
+
+impl<'a> KeyPrompt<'a> {
+    /// Creates the prompt with a specific text.
+    pub fn new() -> KeyPrompt<'static> {
+        KeyPrompt::with_theme(get_default_theme())
+    }
+
+    /// Sets a theme other than the default one.
+    pub fn with_theme(theme: &'a dyn Theme) -> KeyPrompt<'a> {
+        KeyPrompt {
+            text: "".into(),
+            default: 100,
+            items: vec![],
+            descriptions: vec![],
+            show_default: true,
+            timeout: None,
+            invalid_key: InvalidKeyFeedback::Silent,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
+        }
+    }
+
+    /// Sets the KeyPrompt text.
+    pub fn with_text(&mut self, text: &str) -> &mut KeyPrompt<'a> {
+        self.text = text.into();
+        self
+    }
+
+    /// Adds multiple items to the selector.
+    ///
+    /// Items can either be plain keys or `(key, description)` pairs.
+    /// Descriptions are rendered on a help line below the prompt.
+    pub fn items<I: KeyItem>(&mut self, items: &[I]) -> &mut KeyPrompt<'a> {
+        for item in items {
+            self.items.push(item.key());
+            self.descriptions
+                .push(item.description().map(|d| d.to_string()));
+        }
+        self
+    }
+
+    /// Overrides the default.
+    pub fn default(&mut self, val: usize) -> &mut KeyPrompt<'a> {
+        self.default = val;
+        self
+    }
+
+    /// Disables or enables the default value display.
+    ///
+    /// The default is to append `[y/n]` to the prompt to tell the
+    /// user which keys to press.  This also renders the default choice
+    /// in uppercase.  The default is selected on enter.
+    pub fn show_default(&mut self, val: bool) -> &mut KeyPrompt<'a> {
+        self.show_default = val;
+        self
+    }
+
+    /// Sets how the prompt reacts to keys it ignores.
+    ///
+    /// The default is to ignore them silently.
+    pub fn on_invalid_key(&mut self, val: InvalidKeyFeedback) -> &mut KeyPrompt<'a> {
+        self.invalid_key = val;
+        self
+    }
+
+    /// Picks the default once no key was pressed for the given time.
+    ///
+    /// The remaining seconds are shown after the prompt until a key is
+    /// pressed.  This has no effect without a default.  If the prompt is
+    /// not rendered on a terminal the default is picked right away.  On
+    /// platforms other than unix the prompt cannot wait for keys with a
+    /// timeout and waits for a key instead.
+    pub fn timeout(&mut self, val: Duration) -> &mut KeyPrompt<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut KeyPrompt<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut KeyPrompt<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `key` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume<K: KeyItem>(&mut self, key: K) -> &mut KeyPrompt<'a> {
+        self.assumed = Some(key.key());
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.  Without
+    /// a default the prompt fails.
+    pub fn assume_default(&mut self) -> &mut KeyPrompt<'a> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut KeyPrompt<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut KeyPrompt<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut KeyPrompt<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut KeyPrompt<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut KeyPrompt<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Returns the character of the pressed key.  Use `interact_key`
+    /// if some of the items are not characters.
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<char> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<char> {
+        match self.interact_key_on(term)? {
+            Key::Char(c) => Ok(c),
+            _ => Err(io::Error::other("the selected key is not a character").into()),
+        }
+    }
+
+    /// Like `interact` but returns the pressed key.
+    ///
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact_key(&self) -> Result<Key> {
+        self.interact_key_on(&self.term)
+    }
+
+    /// Like `interact_key` but allows a specific terminal to be set.
+    pub fn interact_key_on(&self, term: &Term) -> Result<Key> {
+        if self.items.is_empty() {
+            panic!("Expected items to be specified")
+        }
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+        let help: Vec<(Key, &str)> = self
+            .items
+            .iter()
+            .zip(self.descriptions.iter())
+            .filter_map(|(key, desc)| desc.as_ref().map(|desc| (key.clone(), desc.as_str())))
+            .collect();
+
+        let assumed = match self.assumed {
+            Some(ref key) => Some(key.clone()),
+            None if self.assume_default => match self.items.get(self.default) {
+                Some(key) => Some(key.clone()),
+                None => return Err(no_default().into()),
+            },
+            None => None,
+        };
+        if let Some(rv) = assumed {
+            render.key_prompt_selection(&self.text, &rv)?;
+            return Ok(rv);
+        }
+
+        session::question(&self.text)?;
+        render.key_prompt(
+            &self.text,
+            if self.show_default {
+                Some(self.default)
+            } else {
+                None
+            },
+            &self.items,
+            &help,
+        )?;
+        let mut deadline = match self.items.get(self.default) {
+            Some(_) => self.timeout.map(|timeout| Instant::now() + timeout),
+            None => None,
+        };
+        let default_label = match self.descriptions.get(self.default).and_then(Option::as_ref) {
+            Some(desc) => desc.clone(),
+            None => {
+                let mut buf = String::new();
+                if let Some(key) = self.items.get(self.default) {
+                    self.theme
+                        .format_key(&mut buf, key)
+                        .map_err(io::Error::other)?;
+                }
+                buf
+            }
+        };
+        loop {
+            // the countdown stops once a key was pressed
+            let key = match deadline.take() {
+                Some(deadline) if session::is_term(term) => {
+                    read_key_until(&mut render, term, deadline, &default_label)?
+                        .unwrap_or(Key::Enter)
+                }
+                Some(_) => Key::Enter,
+                None => session::read_key(term)?,
+            };
+            let rv = match key {
+                Key::Enter if self.items.get(self.default).is_some() => {
+                    self.items[self.default].clone()
+                }
+                Key::Char(c) if self.items.contains(&Key::Char(c.to_ascii_lowercase())) => {
+                    Key::Char(c.to_ascii_lowercase())
+                }
+                key if self.items.contains(&key) => key,
+                _ => {
+                    render.invalid_key(self.invalid_key)?;
+                    continue;
+                }
+            };
+            term.clear_line()?;
+            render.clear_below()?;
+            render.key_prompt_selection(&self.text, &rv)?;
+            return Ok(rv);
+        }
+    }
+}
+
+impl<'a> Default for ConfirmationSeries<'a> {
+    fn default() -> ConfirmationSeries<'a> {
+        ConfirmationSeries::new()
+    }
+}
+
+impl<'a> ConfirmationSeries<'a> {
+    /// Creates a new series of confirmations.
+    pub fn new() -> ConfirmationSeries<'static> {
+        ConfirmationSeries::with_theme(get_default_theme())
+    }
+
+    /// Sets a theme other than the default one.
+    pub fn with_theme(theme: &'a dyn Theme) -> ConfirmationSeries<'a> {
+        ConfirmationSeries {
+            default: None,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            answered: None,
+        }
+    }
+
+    /// Sets the answer picked on enter.
+    ///
+    /// By default enter is ignored.
+    pub fn default(&mut self, val: bool) -> &mut ConfirmationSeries<'a> {
+        self.default = Some(val);
+        self
+    }
+
+    /// Indicates whether to report the answers after interaction.
+    ///
+    /// Prompts answered by an earlier `a` are reported too.  The default
+    /// is to report the answers.
+    pub fn report(&mut self, val: bool) -> &mut ConfirmationSeries<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets the terminal the prompts are rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut ConfirmationSeries<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut ConfirmationSeries<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut ConfirmationSeries<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Asks the next confirmation of the series.
+    ///
+    /// Once a prompt was answered with `a` or `q` the following ones
+    /// return `All` or `Quit` right away.  Fails if the yes or no key of
+    /// the localization is `a` or `q`.
+    pub fn ask(&mut self, text: &str) -> Result<SeriesAnswer> {
+        let term = self.term.clone();
+        self.ask_on(&term, text)
+    }
+
+    /// Like `ask` but allows a specific terminal to be set.
+    pub fn ask_on(&mut self, term: &Term, text: &str) -> Result<SeriesAnswer> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        let (yes, no, yes_key, no_key) =
+            localization::with(|l| (l.yes.to_string(), l.no.to_string(), l.yes_key, l.no_key));
+        match self.answered {
+            Some(SeriesAnswer::All) => {
+                render.single_prompt_selection(text, &yes)?;
+                return Ok(SeriesAnswer::All);
+            }
+            Some(answer) => return Ok(answer),
+            None => {}
+        }
+        let keys = [yes_key, no_key, 'a', 'q'];
+        if keys[..2]
+            .iter()
+            .any(|&key| same_key(key, 'a') || same_key(key, 'q'))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the yes and no keys must not be 'a' or 'q'",
+            )
+            .into());
+        }
+        let mut prompt = KeyPrompt::with_theme(self.theme);
+        prompt
+            .with_text(text)
+            .items(&keys)
+            .default(match self.default {
+                Some(true) => 0,
+                Some(false) => 1,
+                None => keys.len(),
+            })
+            .report(false);
+        if let Some(cols) = self.max_width {
+            prompt.max_width(cols);
+        }
+        if let Some(localization) = self.localization {
+            prompt.localization(localization);
+        }
+        let key = prompt.interact_on(term)?;
+        let rv = match key {
+            c if c == yes_key => SeriesAnswer::Yes,
+            c if c == no_key => SeriesAnswer::No,
+            'a' => SeriesAnswer::All,
+            'q' => SeriesAnswer::Quit,
+            _ => unreachable!("the key prompt only returns its items"),
+        };
+        if rv == SeriesAnswer::Quit {
+            render.cancelled(text)?;
+        } else {
+            render.single_prompt_selection(text, if rv.is_yes() { &yes } else { &no })?;
+        }
+        if rv == SeriesAnswer::All || rv == SeriesAnswer::Quit {
+            self.answered = Some(rv);
+        }
+        Ok(rv)
+    }
+}
+
+impl<'a> Default for Pause<'a> {
+    fn default() -> Pause<'a> {
+        Pause::new()
+    }
+}
+
+impl<'a> Pause<'a> {
+    /// Creates the pause prompt.
+    pub fn new() -> Pause<'static> {
+        Pause::with_theme(get_default_theme())
+    }
+
+    /// Sets a theme other than the default one.
+    pub fn with_theme(theme: &'a dyn Theme) -> Pause<'a> {
+        Pause {
+            text: None,
+            key: None,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+        }
+    }
+
+    /// Sets the message.
+    pub fn with_text(&mut self, text: &str) -> &mut Pause<'a> {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Waits for a specific key instead of any key.
+    pub fn wait_for(&mut self, key: Key) -> &mut Pause<'a> {
+        self.key = Some(key);
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Pause<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The answer passed to the callback is empty.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Pause<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Pause<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Pause<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Pause<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Pause<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Pause<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Enables user interaction and waits for the key press.
+    ///
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<()> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<()> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+        let text = match self.text {
+            Some(ref text) => text.clone(),
+            None => localization::with(|l| l.press_any_key.to_string()),
+        };
+
+        session::question(&text)?;
+        render.pause_prompt(&text)?;
+        loop {
+            let input = session::read_key(term)?;
+            if let Some(ref key) = self.key {
+                if *key != input {
+                    continue;
+                }
+            }
+            term.clear_line()?;
+            render.pause_prompt_selection(&text)?;
+            return Ok(());
+        }
+    }
+}
+
+impl<'a> Default for Message<'a> {
+    fn default() -> Message<'a> {
+        Message::new()
+    }
+}
+
+impl<'a> Message<'a> {
+    /// Creates the message.
+    pub fn new() -> Message<'static> {
+        Message::with_theme(get_default_theme())
+    }
+
+    /// Sets a theme other than the default one.
+    pub fn with_theme(theme: &'a dyn Theme) -> Message<'a> {
+        Message {
+            text: "".into(),
+            severity: Severity::Info,
+            boxed: false,
+            hint: None,
+            key: Key::Enter,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+        }
+    }
+
+    /// Sets the text of the message.
+    pub fn with_text(&mut self, text: &str) -> &mut Message<'a> {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets the severity of the message.  The default is info.
+    pub fn severity(&mut self, val: Severity) -> &mut Message<'a> {
+        self.severity = val;
+        self
+    }
+
+    /// Draws a box around the message.
+    pub fn boxed(&mut self, val: bool) -> &mut Message<'a> {
+        self.boxed = val;
+        self
+    }
+
+    /// Sets the hint shown below the message.
+    pub fn with_hint(&mut self, hint: &str) -> &mut Message<'a> {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Waits for a key other than enter.
+    pub fn wait_for(&mut self, key: Key) -> &mut Message<'a> {
+        self.key = key;
+        self
+    }
+
+    /// Indicates whether to keep the message after it was acknowledged.
+    ///
+    /// When disabled the message is cleared.  The default is to keep it.
+    pub fn report(&mut self, val: bool) -> &mut Message<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the text and an empty answer.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Message<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the terminal the message is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Message<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Message<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Message<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the message on stdout.
+    pub fn stdout(&mut self) -> &mut Message<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the message on stderr.
+    pub fn stderr(&mut self) -> &mut Message<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Renders the message and waits until it is acknowledged.
+    ///
+    /// If the terminal is not a tty the message is only printed.
+    pub fn interact(&self) -> Result<()> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<()> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+
+        render.message(self.severity, &self.text, self.boxed)?;
+        if !session::is_term(term) {
+            return Ok(());
+        }
+        session::question(&self.text)?;
+        let hint = match self.hint {
+            Some(ref hint) => hint.clone(),
+            None => localization::with(|l| l.press_enter.to_string()),
+        };
+        render.pause_prompt(&hint)?;
+        while session::read_key(term)? != self.key {}
+        term.clear_line()?;
+        Ok(render.message_acknowledged(&self.text)?)
+    }
+}
+
+impl<'a, T> Default for Input<'a, T>
+where
+    T: Clone + FromStr + Display + 'static,
+    T::Err: Display + Debug,
+{
+    fn default() -> Input<'a, T> {
+        Input::new()
+    }
+}
+
+impl<'a, T> Input<'a, T>
+where
+    T: Clone + FromStr + Display + 'static,
+    T::Err: Display + Debug,
+{
+    /// Creates a new input prompt.
+    pub fn new() -> Input<'static, T> {
+        Input::with_theme(get_default_theme())
+    }
+
+    /// Creates an input with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> Input<'a, T> {
+        Input::with_theme_and_parser(theme, |value| {
+            value.parse::<T>().map_err(|err| err.to_string())
+        })
+    }
+}
+
+impl<'a, T> Input<'a, T>
+where
+    T: Clone + Display + PartialOrd + 'static,
+{
+    /// Rejects values less than `val`.
+    ///
+    /// The error shown is formatted by the theme.
+    pub fn min(&mut self, val: T) -> &mut Input<'a, T> {
+        self.min = Some(val);
+        self.compare = Some(T::partial_cmp);
+        self
+    }
+
+    /// Rejects values greater than `val`.
+    ///
+    /// The error shown is formatted by the theme.
+    pub fn max(&mut self, val: T) -> &mut Input<'a, T> {
+        self.max = Some(val);
+        self.compare = Some(T::partial_cmp);
+        self
+    }
+}
+
+impl<'a, T> Input<'a, T>
+where
+    T: Clone + Display + 'static,
+{
+    /// Creates an input prompt for a value parsed by a custom function.
+    ///
+    /// Unlike `new` this does not need the value to implement `FromStr`.
+    pub fn with_parser<F>(parser: F) -> Input<'static, T>
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        Input::with_theme_and_parser(get_default_theme(), parser)
+    }
+
+    /// Creates an input with a specific theme and a custom parse function.
+    pub fn with_theme_and_parser<F>(theme: &'a dyn Theme, parser: F) -> Input<'a, T>
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        Input {
+            prompt: "".into(),
+            default: None,
+            show_default: true,
+            initial_text: None,
+            placeholder: None,
+            help: None,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
+            env_key: None,
+            default_without_tty: false,
+            permit_empty: false,
+            validator: None,
+            typing_validator: None,
+            char_filter: None,
+            max_length: None,
+            length_counter: false,
+            parser: Box::new(parser),
+            undo_keys: (Key::Char('\u{1a}'), Key::Char('\u{19}')),
+            mask: None,
+            strip_mask: false,
+            max_attempts: None,
+            suggestions: vec![],
+            max_suggestions: 5,
+            history: None,
+            live_feedback: false,
+            raw_mode: true,
+            min: None,
+            max: None,
+            compare: None,
+        }
+    }
+
+    /// Sets the input prompt.
+    ///
+    /// Lines before the last one of a multi-line text are shown above the
+    /// line the question is asked on.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut Input<'a, T> {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Sets whether the default can be editable.
+    pub fn with_initial_text(&mut self, val: &str) -> &mut Input<'a, T> {
+        self.initial_text = Some(val.into());
+        self
+    }
+
+    /// Sets the keys undoing and redoing edits.
+    ///
+    /// The defaults are Ctrl-Z and Ctrl-Y.
+    pub fn undo_keys(&mut self, undo: Key, redo: Key) -> &mut Input<'a, T> {
+        self.undo_keys = (undo, redo);
+        self
+    }
+
+    /// Sets a mask the value has to be typed into.
+    ///
+    /// In the mask `#` stands for a digit, `a` for a letter and `*` for a
+    /// letter or digit.  Other characters like the dashes of `####-##-##`
+    /// are inserted automatically and a backslash makes the following
+    /// character one of them.  The value is only accepted once the mask is
+    /// filled in.  The placeholder is not shown for masked inputs.
+    pub fn with_mask(&mut self, mask: &str) -> &mut Input<'a, T> {
+        self.mask = Some(Mask::new(mask));
+        self
+    }
+
+    /// Returns the value of a masked input without the inserted characters.
+    ///
+    /// The default is to return the value as formatted by the mask.
+    pub fn strip_mask(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.strip_mask = val;
+        self
+    }
+
+    /// Lists the suggestions containing the typed text below the field.
+    ///
+    /// The arrow keys move through the list, tab copies the active
+    /// suggestion into the field and enter accepts it.  Any other text
+    /// can still be typed.
+    pub fn suggestions<S: ToString>(&mut self, items: &[S]) -> &mut Input<'a, T> {
+        self.suggestions = items.iter().map(|item| item.to_string()).collect();
+        self
+    }
+
+    /// Sets the number of suggestions listed at once.
+    ///
+    /// The default is 5.
+    pub fn max_suggestions(&mut self, val: usize) -> &mut Input<'a, T> {
+        self.max_suggestions = val;
+        self
+    }
+
+    /// Lets the arrow keys recall the entries of a history.
+    ///
+    /// Accepted values are recorded in it, a failure to record one is
+    /// ignored.  While a suggestion is active the arrow keys move
+    /// through the suggestions instead.
+    pub fn history<H: History + 'static>(&mut self, history: H) -> &mut Input<'a, T> {
+        self.history = Some(RefCell::new(Box::new(history)));
+        self
+    }
+
+    /// Shows the number of typed characters below the field and whether
+    /// the value would be accepted, updated with every key.
+    pub fn live_feedback(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.live_feedback = val;
+        self
+    }
+
+    /// Sets whether the field is edited key by key on a terminal.
+    ///
+    /// The default is `true`.  With `false` the line is read as the
+    /// terminal lets it be typed, without masks, suggestions, a history
+    /// or live feedback.
+    pub fn raw_mode(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.raw_mode = val;
+        self
+    }
+
+    /// Sets a placeholder shown in the empty field.
+    ///
+    /// The placeholder is only a hint and disappears as soon as a key
+    /// is pressed.  It is not used if an initial text is set.
+    pub fn with_placeholder(&mut self, val: &str) -> &mut Input<'a, T> {
+        self.placeholder = Some(val.into());
+        self
+    }
+
+    /// Sets a default.
+    ///
+    /// Out of the box the prompt does not have a default and will continue
     /// to display until the user hit enter.  If a default is set the user
     /// can instead accept the default with enter.
     pub fn default(&mut self, value: Option<T>) -> &mut Input<'a, T> {
@@ -339,6 +1844,15 @@ where
         self.permit_empty = val;
         self
     }
+    /// Limits the number of invalid values that can be entered.
+    ///
+    /// Once the limit is reached the default is picked.  Without a
+    /// default `Error::AttemptsExceeded` is returned.  This keeps
+    /// unattended runs from asking again forever.
+    pub fn max_attempts(&mut self, val: usize) -> &mut Input<'a, T> {
+        self.max_attempts = Some(val);
+        self
+    }
     /// Disables or enables the default value display.
     ///
     /// The default is to append `[default]` to the prompt to tell the
@@ -348,6 +1862,17 @@ where
         self
     }
 
+    /// Sets the function parsing the value.
+    ///
+    /// An error returned by it is shown and the user is asked again.
+    pub fn parse_with<F>(&mut self, parser: F) -> &mut Input<'a, T>
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        self.parser = Box::new(parser);
+        self
+    }
+
     /// Registers a validator.
     pub fn validate_with<V: Validator + 'static>(&mut self, validator: V) -> &mut Input<'a, T> {
         let old_validator_func = self.validator.take();
@@ -365,18 +1890,260 @@ where
         self
     }
 
+    /// Registers a cheap validator that is run on every key.
+    ///
+    /// Its error is shown as a warning below the field while typing but
+    /// does not keep the value from being entered; the validators of
+    /// `validate_with` are still only run on enter.
+    pub fn validate_while_typing<V>(&mut self, validator: V) -> &mut Input<'a, T>
+    where
+        V: Validator + 'static,
+    {
+        self.typing_validator = Some(Box::new(move |value: &str| {
+            validator.validate(value).err().map(|err| err.to_string())
+        }));
+        self
+    }
+
+    /// Only lets the given characters be typed, eg: `"0123456789"` for a
+    /// port.  Other keys are ignored.
+    pub fn allowed_chars(&mut self, chars: &str) -> &mut Input<'a, T> {
+        let chars = chars.to_string();
+        self.filter(move |c| chars.contains(c))
+    }
+
+    /// Only lets the characters be typed for which `filter` returns
+    /// `true`.  Other keys are ignored.
+    ///
+    /// Where the line cannot be edited key by key the rejected characters
+    /// are removed from it once entered.
+    pub fn filter<F>(&mut self, filter: F) -> &mut Input<'a, T>
+    where
+        F: Fn(char) -> bool + 'static,
+    {
+        self.char_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Limits the value to `n` characters.  Further keys are ignored
+    /// once the limit is reached.
+    pub fn max_length(&mut self, n: usize) -> &mut Input<'a, T> {
+        self.max_length = Some(n);
+        self
+    }
+
+    /// Shows the number of typed characters out of the `max_length`
+    /// after the text, eg: `12/64`.
+    pub fn length_counter(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.length_counter = val;
+        self
+    }
+
+    /// Shows a help line above the prompt.
+    ///
+    /// The line is cleared along with the prompt.
+    pub fn with_help(&mut self, text: &str) -> &mut Input<'a, T> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Input<'a, T> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: T) -> &mut Input<'a, T> {
+        self.assumed = Some(value);
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.  Without
+    /// a default the prompt fails.
+    pub fn assume_default(&mut self) -> &mut Input<'a, T> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Takes the answer from an environment variable if it is set.
+    ///
+    /// The prompt is not shown then but the answer is still validated
+    /// and reported.  An invalid value is returned as an error.
+    pub fn env_key(&mut self, key: &str) -> &mut Input<'a, T> {
+        self.env_key = Some(key.into());
+        self
+    }
+
+    /// Answers with the default without asking if stdin is not a
+    /// terminal, eg: when run in CI.
+    ///
+    /// The answer is still reported.  Without a default the prompt is
+    /// asked as usual.
+    pub fn accept_default_without_tty(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.default_without_tty = val;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Input<'a, T> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Input<'a, T> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Input<'a, T> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Input<'a, T> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Input<'a, T> {
+        self.term = Term::stderr();
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<T> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<T> {
+        self.interact_on(&self.term)
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<T> {
+    pub fn interact_on(&self, term: &Term) -> Result<T> {
+        Ok(self
+            ._interact_on(term, false)?
+            .ok_or_else(|| io::Error::other("Quit not allowed in this case"))?)
+    }
+
+    /// Like `interact` but lets the user back out with Esc.
+    ///
+    /// Returns `None` if the user backed out, in which case the prompt is
+    /// reported as cancelled.  Only the line editor of terminals that can
+    /// move the cursor supports backing out.
+    pub fn interact_opt(&self) -> Result<Option<T>> {
+        self.interact_on_opt(&self.term)
+    }
+
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<T>> {
+        self._interact_on(term, true)
+    }
+
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> Result<Option<T>> {
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+
+        if let Some((key, value)) = env::answer(self.env_key.as_deref()) {
+            if value.is_empty() {
+                if let Some(ref default) = self.default {
+                    render.single_prompt_selection(&self.prompt, &default.to_string())?;
+                    return Ok(Some(default.clone()));
+                } else if !self.permit_empty {
+                    return Err(env::invalid(key, "the value must not be empty").into());
+                }
+            }
+            let (input, value) = self.check(value).map_err(|err| env::invalid(key, &err))?;
+            render.single_prompt_selection(&self.prompt, &input)?;
+            return Ok(Some(value));
+        }
+        let assumed = match self.assumed {
+            Some(ref value) => Some(value),
+            None if self.assume_default => Some(self.default.as_ref().ok_or_else(no_default)?),
+            None if self.default_without_tty && !session::stdin_is_term() => self.default.as_ref(),
+            None => None,
+        };
+        if let Some(value) = assumed {
+            render.single_prompt_selection(&self.prompt, &value.to_string())?;
+            return Ok(Some(value.clone()));
+        }
+
+        let format = |text: &str, active| {
+            let style = if active {
+                SelectionStyle::MenuSelected
+            } else {
+                SelectionStyle::MenuUnselected
+            };
+            let mut buf = String::new();
+            self.theme
+                .format_selection(&mut buf, text, style)
+                .map_err(io::Error::other)?;
+            Ok(buf)
+        };
+        let suggestions = line::Suggestions {
+            items: &self.suggestions,
+            max: self.max_suggestions,
+            format: &format,
+        };
+        let status = |text: &str| {
+            let mut lines = vec![];
+            if self.live_feedback {
+                let error = match text {
+                    "" => None,
+                    _ => self.check(text.into()).err(),
+                };
+                let mut buf = String::new();
+                self.theme
+                    .format_input_feedback(&mut buf, text, error.as_deref())
+                    .map_err(io::Error::other)?;
+                lines.push(buf);
+            }
+            let warning = self
+                .typing_validator
+                .as_ref()
+                .and_then(|validator| validator(text));
+            if let Some(warning) = warning {
+                let mut buf = String::new();
+                self.theme
+                    .format_input_warning(&mut buf, &warning)
+                    .map_err(io::Error::other)?;
+                lines.push(buf);
+            }
+            Ok(lines)
+        };
+        let counter = |text: &str| {
+            let mut buf = String::new();
+            let max = self.max_length.unwrap_or_default();
+            self.theme
+                .format_length_counter(&mut buf, text.chars().count(), max)
+                .map_err(io::Error::other)?;
+            Ok(buf)
+        };
+
+        session::question(&self.prompt)?;
+        let mut attempts = 0;
         loop {
+            if let Some(ref help) = self.help {
+                render.help(help)?;
+            }
             let default_string = self.default.as_ref().map(|x| x.to_string());
             render.input_prompt(
                 &self.prompt,
@@ -386,41 +2153,162 @@ where
                     None
                 },
             )?;
-            let input = if let Some(initial_text) = self.initial_text.as_ref() {
-                term.read_line_initial_text(initial_text)?
+            let recall = self.history.as_ref().map(|history| history.borrow());
+            let assists = line::Assists {
+                suggestions: if self.suggestions.is_empty() {
+                    None
+                } else {
+                    Some(&suggestions)
+                },
+                history: recall.as_ref().map(|history| &***history),
+                status: if self.live_feedback || self.typing_validator.is_some() {
+                    Some(&status)
+                } else {
+                    None
+                },
+                accepts: self.char_filter.as_deref(),
+                max_len: self.max_length,
+                suffix: match self.max_length {
+                    Some(_) if self.length_counter => Some(&counter),
+                    _ => None,
+                },
+            };
+            let undo_keys = &self.undo_keys;
+            let input = if !self.raw_mode || !session::is_term(term) {
+                let mut input = session::read_line(term, self.initial_text.as_deref())?;
+                if let Some(ref filter) = self.char_filter {
+                    input.retain(filter);
+                }
+                if let Some((idx, _)) = self.max_length.and_then(|n| input.char_indices().nth(n)) {
+                    input.truncate(idx);
+                }
+                Some(input)
+            } else if let Some(ref mask) = self.mask {
+                let initial = self
+                    .initial_text
+                    .as_ref()
+                    .and_then(|text| mask.parse(text))
+                    .unwrap_or_default();
+                Some(line::read_masked(term, render.column(), mask, &initial)?)
             } else {
-                term.read_line()?
+                let (initial, first) = match (&self.initial_text, &self.placeholder) {
+                    (Some(initial_text), _) => (initial_text.as_str(), None),
+                    (None, Some(placeholder)) => {
+                        render.input_placeholder(placeholder)?;
+                        let key = session::read_key(term)?;
+                        render.clear_input_placeholder(placeholder)?;
+                        ("", Some(key))
+                    }
+                    (None, None) => ("", None),
+                };
+                let column = render.column();
+                line::read_line(
+                    term, column, initial, first, undo_keys, allow_quit, &assists,
+                )?
             };
-            render.add_line();
+            drop(recall);
+            let input = match input {
+                Some(input) => input,
+                None => {
+                    render.add_line();
+                    render.clear()?;
+                    render.cancelled(&self.prompt)?;
+                    return Ok(None);
+                }
+            };
+            render.add_input(&input);
             term.clear_line()?;
             if input.is_empty() {
                 render.clear()?;
                 if let Some(ref default) = self.default {
                     render.single_prompt_selection(&self.prompt, &default.to_string())?;
-                    return Ok(default.clone());
+                    return Ok(Some(default.clone()));
                 } else if !self.permit_empty {
+                    attempts += 1;
+                    if self.max_attempts.is_some_and(|max| attempts >= max) {
+                        return Ok(Some(self.give_up(&mut render, attempts)?));
+                    }
                     continue;
                 }
             }
             render.clear()?;
-            if let Some(ref validator) = self.validator {
-                if let Some(err) = validator(&input) {
-                    render.error(&err)?;
-                    continue;
-                }
-            }
-            match input.parse::<T>() {
-                Ok(value) => {
+            match self.check(input) {
+                Ok((input, value)) => {
+                    if let Some(ref history) = self.history {
+                        // the answer is kept even if it cannot be recorded
+                        history.borrow_mut().write(&input).ok();
+                    }
                     render.single_prompt_selection(&self.prompt, &input)?;
-                    return Ok(value);
+                    return Ok(Some(value));
                 }
                 Err(err) => {
-                    render.error(&err.to_string())?;
+                    render.error(&err)?;
+                    attempts += 1;
+                    if self.max_attempts.is_some_and(|max| attempts >= max) {
+                        return Ok(Some(self.give_up(&mut render, attempts)?));
+                    }
                     continue;
                 }
             }
         }
     }
+
+    /// Picks the default or fails once all attempts are used up.
+    fn give_up(&self, render: &mut TermThemeRenderer, attempts: usize) -> io::Result<T> {
+        match self.default {
+            Some(ref default) => {
+                render.single_prompt_selection(&self.prompt, &default.to_string())?;
+                Ok(default.clone())
+            }
+            None => Err(Error::AttemptsExceeded { attempts }.into()),
+        }
+    }
+
+    /// Applies the mask, validator and parser to an entered text.
+    ///
+    /// Returns the text to report along with the parsed value.
+    fn check(&self, input: String) -> Result<(String, T), String> {
+        let input = match self.mask {
+            Some(ref mask) if !input.is_empty() => {
+                match mask.parse(&input).filter(|raw| mask.is_complete(raw)) {
+                    Some(raw) if self.strip_mask => raw,
+                    Some(raw) => mask.format(&raw),
+                    None => {
+                        return Err(localization::with(|l| {
+                            Localization::fill(&l.mask_mismatch, &[("mask", mask)])
+                        }))
+                    }
+                }
+            }
+            _ => input,
+        };
+        if let Some(ref validator) = self.validator {
+            if let Some(err) = validator(&input) {
+                return Err(err);
+            }
+        }
+        let value = (self.parser)(&input)?;
+        if let Some(compare) = self.compare {
+            let below = self
+                .min
+                .as_ref()
+                .is_some_and(|min| matches!(compare(&value, min), Some(Ordering::Less) | None));
+            let above = self
+                .max
+                .as_ref()
+                .is_some_and(|max| matches!(compare(&value, max), Some(Ordering::Greater) | None));
+            if below || above {
+                let min = self.min.as_ref().map(|min| min.to_string());
+                let max = self.max.as_ref().map(|max| max.to_string());
+                let mut err = String::new();
+                self.theme
+                    .format_range_error(&mut err, min.as_deref(), max.as_deref())
+                    .map_err(|err| err.to_string())?;
+                return Err(err);
+            }
+        }
+        Ok((input, value))
+    }
 }
 
 impl<'a> Default for PasswordInput<'a> {
@@ -439,8 +2327,19 @@ impl<'a> PasswordInput<'a> {
     pub fn with_theme(theme: &'a dyn Theme) -> PasswordInput<'a> {
         PasswordInput {
             prompt: "".into(),
+            help: None,
             theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            assumed: None,
+            env_key: None,
             allow_empty_password: false,
+            allow_reveal: false,
+            read_piped: false,
+            mask: None,
             confirmation_prompt: None,
         }
     }
@@ -469,31 +2368,163 @@ impl<'a> PasswordInput<'a> {
         self
     }
 
+    /// Sets the character echoed for every typed character.
+    ///
+    /// By default nothing is echoed.  The theme decides how the masked
+    /// password is displayed.
+    pub fn with_mask(&mut self, mask: Option<char>) -> &mut PasswordInput<'a> {
+        self.mask = mask;
+        self
+    }
+
+    /// Allows/Disables revealing the password while typing.
+    ///
+    /// When enabled Ctrl-R shows the typed password and a second press
+    /// hides it again.  This is disabled by default.
+    pub fn allow_reveal(&mut self, val: bool) -> &mut PasswordInput<'a> {
+        self.allow_reveal = val;
+        self
+    }
+
+    /// Reads the password from stdin when it is not a terminal.
+    ///
+    /// This lets a secret be piped in like `echo "$TOKEN" | app login`.
+    /// The first line is read without rendering the prompt and without
+    /// asking for confirmation.  This is disabled by default.
+    pub fn read_piped(&mut self, val: bool) -> &mut PasswordInput<'a> {
+        self.read_piped = val;
+        self
+    }
+
+    /// Shows a help line above the prompt.
+    ///
+    /// The line is cleared along with the prompt.
+    pub fn with_help(&mut self, text: &str) -> &mut PasswordInput<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut PasswordInput<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The password is not passed to the callback.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut PasswordInput<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: &str) -> &mut PasswordInput<'a> {
+        self.assumed = Some(value.into());
+        self
+    }
+
+    /// Takes the answer from an environment variable if it is set.
+    ///
+    /// The prompt is not shown then but the answer is still validated
+    /// and reported.  An empty value is an error unless empty passwords
+    /// are allowed.
+    pub fn env_key(&mut self, key: &str) -> &mut PasswordInput<'a> {
+        self.env_key = Some(key.into());
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut PasswordInput<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut PasswordInput<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut PasswordInput<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut PasswordInput<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut PasswordInput<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<String> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<String> {
+        self.interact_on(&self.term)
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<String> {
+    pub fn interact_on(&self, term: &Term) -> Result<String> {
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
         render.set_prompts_reset_height(false);
+
+        if let Some((key, password)) = env::answer(self.env_key.as_deref()) {
+            if password.is_empty() && !self.allow_empty_password {
+                return Err(env::invalid(key, "the password must not be empty").into());
+            }
+            render.password_prompt_selection(&self.prompt, self.mask)?;
+            return Ok(password);
+        }
+        if let Some(ref password) = self.assumed {
+            render.password_prompt_selection(&self.prompt, self.mask)?;
+            return Ok(password.clone());
+        }
+
+        if self.read_piped {
+            if let Some(password) = session::read_piped_line()? {
+                if password.is_empty() && !self.allow_empty_password {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "the piped password must not be empty",
+                    )
+                    .into());
+                }
+                return Ok(password);
+            }
+        }
+
+        session::question(&self.prompt)?;
+        if let Some(ref help) = self.help {
+            render.help(help)?;
+        }
         loop {
             let password = self.prompt_password(&mut render, &self.prompt)?;
             if let Some((ref prompt, ref err)) = self.confirmation_prompt {
-                let pw2 = self.prompt_password(&mut render, &prompt)?;
+                let pw2 = self.prompt_password(&mut render, prompt)?;
                 if password == pw2 {
                     render.clear()?;
-                    render.password_prompt_selection(&self.prompt)?;
+                    render.password_prompt_selection(&self.prompt, self.mask)?;
                     return Ok(password);
                 }
                 render.error(err)?;
             } else {
                 render.clear()?;
-                render.password_prompt_selection(&self.prompt)?;
+                render.password_prompt_selection(&self.prompt, self.mask)?;
                 return Ok(password);
             }
         }
@@ -502,11 +2533,656 @@ impl<'a> PasswordInput<'a> {
     fn prompt_password(&self, render: &mut TermThemeRenderer, prompt: &str) -> io::Result<String> {
         loop {
             render.password_prompt(prompt)?;
-            let input = render.term().read_secure_line()?;
+            let input = self.read_password(render)?;
             render.add_line();
             if !input.is_empty() || self.allow_empty_password {
                 return Ok(input);
             }
         }
     }
+
+    fn read_password(&self, render: &TermThemeRenderer) -> io::Result<String> {
+        let term = render.term();
+        if !(self.allow_reveal || self.mask.is_some()) || !session::is_term(term) {
+            return session::read_secure_line(term);
+        }
+        let mut input = String::new();
+        let mut revealed = false;
+        let mut shown = String::new();
+        loop {
+            match session::read_secure_key(term)? {
+                Key::Enter => {
+                    term.write_line("")?;
+                    return Ok(input);
+                }
+                Key::Backspace => {
+                    input.pop();
+                }
+                // Ctrl-R
+                Key::Char('\u{12}') if self.allow_reveal => {
+                    revealed = !revealed;
+                }
+                Key::Char(c) if !c.is_control() => {
+                    input.push(c);
+                }
+                _ => {}
+            }
+            let display = if revealed {
+                input.clone()
+            } else {
+                render.password_mask(self.mask, input.chars().count())?
+            };
+            if display != shown {
+                term.clear_chars(measure_text_width(&shown))?;
+                term.write_str(&display)?;
+                shown = display;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use history::FileHistory;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    #[cfg(unix)]
+    use testing::Capture;
+    use testing::Script;
+    use theme::{ExtendedTheme, SimpleTheme};
+
+    #[test]
+    fn test_confirm_phrase() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Type \"prod\" to confirm")
+            .type_line("Prod")
+            .type_line("prod")
+            .start()
+            .unwrap();
+        assert!(ConfirmPhrase::new()
+            .with_phrase("prod")
+            .interact_on(&term)
+            .unwrap());
+        session.finish().unwrap();
+
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("")
+            .start()
+            .unwrap();
+        let confirmed = ConfirmPhrase::new()
+            .with_prompt("Name")
+            .with_phrase("prod")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(!confirmed);
+    }
+
+    #[test]
+    fn test_default_without_tty() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .without_tty()
+            .expect_prompt("Host")
+            .type_line("db")
+            .start()
+            .unwrap();
+        let save = Confirmation::new()
+            .with_text("Save?")
+            .default(true)
+            .accept_default_without_tty(true)
+            .interact_on(&term)
+            .unwrap();
+        let port: u16 = Input::new()
+            .with_prompt("Port")
+            .default(Some(8080))
+            .accept_default_without_tty(true)
+            .interact_on(&term)
+            .unwrap();
+        let host: String = Input::new()
+            .with_prompt("Host")
+            .accept_default_without_tty(true)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(save);
+        assert_eq!(port, 8080);
+        assert_eq!(host, "db");
+    }
+
+    #[test]
+    fn test_assume() {
+        let term = Term::buffered_stderr();
+        let session = Script::new().start().unwrap();
+        let save = Confirmation::new()
+            .with_text("Save?")
+            .default(true)
+            .assume(false)
+            .interact_on(&term)
+            .unwrap();
+        let confirmed = ConfirmPhrase::new()
+            .with_phrase("prod")
+            .assume(true)
+            .interact_on(&term)
+            .unwrap();
+        let key = KeyPrompt::new()
+            .with_text("Run?")
+            .items(&['y', 'n', 'p'])
+            .default(2)
+            .assume_default()
+            .interact_on(&term)
+            .unwrap();
+        let port: u16 = Input::new()
+            .with_prompt("Port")
+            .default(Some(8080))
+            .assume_default()
+            .interact_on(&term)
+            .unwrap();
+        let err = Input::<String>::new()
+            .with_prompt("Host")
+            .assume_default()
+            .interact_on(&term)
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(!save);
+        assert!(confirmed);
+        assert_eq!(key, 'p');
+        assert_eq!(port, 8080);
+        assert!(matches!(err, Error::InvalidDefault(_)));
+    }
+
+    #[test]
+    fn test_confirmation_series() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Remove a?")
+            .press(Key::Char('n'))
+            .expect_prompt("Remove b?")
+            .press(Key::Char('a'))
+            .start()
+            .unwrap();
+        let mut series = ConfirmationSeries::new();
+        assert_eq!(series.ask_on(&term, "Remove a?").unwrap(), SeriesAnswer::No);
+        assert_eq!(
+            series.ask_on(&term, "Remove b?").unwrap(),
+            SeriesAnswer::All
+        );
+        assert_eq!(
+            series.ask_on(&term, "Remove c?").unwrap(),
+            SeriesAnswer::All
+        );
+        session.finish().unwrap();
+
+        let session = Script::new()
+            .expect_prompt("Remove a?")
+            .press(Key::Enter)
+            .press(Key::Char('q'))
+            .start()
+            .unwrap();
+        let mut series = ConfirmationSeries::new();
+        assert_eq!(
+            series.ask_on(&term, "Remove a?").unwrap(),
+            SeriesAnswer::Quit
+        );
+        assert_eq!(
+            series.ask_on(&term, "Remove b?").unwrap(),
+            SeriesAnswer::Quit
+        );
+        session.finish().unwrap();
+    }
+
+    #[test]
+    fn test_localization() {
+        let term = Term::buffered_stderr();
+        let german = Localization {
+            yes: "ja".into(),
+            no: "nein".into(),
+            yes_key: 'j',
+            no_key: 'n',
+            ..Localization::default()
+        };
+        let session = Script::new()
+            .expect_prompt("Weiter?")
+            .press(Key::Char('y'))
+            .press(Key::Char('j'))
+            .start()
+            .unwrap();
+        let answer = Rc::new(RefCell::new(String::new()));
+        let rv = {
+            let answer = answer.clone();
+            Confirmation::new()
+                .with_text("Weiter?")
+                .localization(&german)
+                .on_complete(move |_, val| *answer.borrow_mut() = val.into())
+                .interact_on(&term)
+                .unwrap()
+        };
+        session.finish().unwrap();
+        assert!(rv);
+        assert_eq!(*answer.borrow(), "ja");
+    }
+
+    #[test]
+    fn test_series_key_collision() {
+        let term = Term::buffered_stderr();
+        let localization = Localization {
+            yes_key: 'a',
+            ..Localization::default()
+        };
+        let session = Script::new().start().unwrap();
+        let err = ConfirmationSeries::new()
+            .localization(&localization)
+            .ask_on(&term, "Remove a?")
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(matches!(err, Error::Io(ref err) if err.kind() == io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_localized_phrase() {
+        let term = Term::buffered_stderr();
+        let german = Localization {
+            confirm_phrase: "Tippe \"{phrase}\" zum Bestätigen".into(),
+            ..Localization::default()
+        };
+        let session = Script::new()
+            .expect_prompt("Tippe \"prod\" zum Bestätigen")
+            .type_line("prod")
+            .start()
+            .unwrap();
+        let confirmed = ConfirmPhrase::new()
+            .with_phrase("prod")
+            .localization(&german)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(confirmed);
+    }
+
+    #[test]
+    fn test_range() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Port")
+            .type_line("80")
+            .type_line("70000")
+            .type_line("8080")
+            .start()
+            .unwrap();
+        let port = Input::<u32>::new()
+            .with_prompt("Port")
+            .min(1024)
+            .max(65535)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(port, 8080);
+
+        let mut err = String::new();
+        SimpleTheme
+            .format_range_error(&mut err, Some("1024"), None)
+            .unwrap();
+        assert_eq!(err, "Expected a value of at least 1024");
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Port")
+            .type_line("a")
+            .type_line("b")
+            .start()
+            .unwrap();
+        let err = Input::<u16>::new()
+            .with_prompt("Port")
+            .max_attempts(2)
+            .interact_on(&term)
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(matches!(err, Error::AttemptsExceeded { attempts: 2 }));
+
+        let session = Script::new()
+            .expect_prompt("Port")
+            .type_line("a")
+            .start()
+            .unwrap();
+        let port = Input::<u16>::new()
+            .with_prompt("Port")
+            .default(Some(80))
+            .max_attempts(1)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(port, 80);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let term = Term::buffered_stderr();
+        let fruits = ["grape", "apricot", "apple"];
+        let session = Script::new()
+            .expect_prompt("Fruit")
+            .press(Key::Char('p'))
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .expect_prompt("Fruit")
+            .press(Key::Char('g'))
+            .press(Key::Tab)
+            .type_line("s")
+            .start()
+            .unwrap();
+        let mut input = Input::<String>::new();
+        input.with_prompt("Fruit").suggestions(&fruits);
+        assert_eq!(input.interact_on(&term).unwrap(), "apricot");
+        assert_eq!(input.interact_on(&term).unwrap(), "grapes");
+        session.finish().unwrap();
+    }
+    #[test]
+    fn test_history() {
+        let term = Term::buffered_stderr();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+        let session = Script::new()
+            .expect_prompt(">")
+            .type_line("ls")
+            .expect_prompt(">")
+            .type_line("cd")
+            .expect_prompt(">")
+            .press(Key::ArrowUp)
+            .press(Key::ArrowUp)
+            .press(Key::Enter)
+            .expect_prompt(">")
+            .press(Key::Char('x'))
+            .press(Key::ArrowUp)
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt(">")
+            .history(FileHistory::open(&path).unwrap());
+        assert_eq!(input.interact_on(&term).unwrap(), "ls");
+        assert_eq!(input.interact_on(&term).unwrap(), "cd");
+        assert_eq!(input.interact_on(&term).unwrap(), "ls");
+        assert_eq!(input.interact_on(&term).unwrap(), "x");
+        session.finish().unwrap();
+
+        let history = FileHistory::open(&path).unwrap();
+        assert_eq!(
+            history.entries().collect::<Vec<_>>(),
+            vec!["ls", "cd", "ls", "x"]
+        );
+    }
+
+    #[test]
+    fn test_history_write_fails() {
+        struct ReadOnly;
+
+        impl History for ReadOnly {
+            fn read(&self, _pos: usize) -> Option<String> {
+                None
+            }
+
+            fn write(&mut self, _entry: &str) -> io::Result<()> {
+                Err(io::Error::from(io::ErrorKind::PermissionDenied))
+            }
+        }
+
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt(">")
+            .type_line("ls")
+            .start()
+            .unwrap();
+        let value: String = Input::new()
+            .with_prompt(">")
+            .history(ReadOnly)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(value, "ls");
+    }
+
+    #[test]
+    fn test_live_feedback() {
+        let term = Term::buffered_stderr();
+        let seen = Arc::new(Mutex::new(vec![]));
+        let record = seen.clone();
+        let theme = ExtendedTheme::extending(SimpleTheme).with_format_input_feedback(
+            move |base, f, text, error| {
+                record
+                    .lock()
+                    .unwrap()
+                    .push((text.to_string(), error.is_some()));
+                base.format_input_feedback(f, text, error)
+            },
+        );
+        let session = Script::new()
+            .expect_prompt("Age")
+            .type_line("1x")
+            .start()
+            .unwrap();
+        let err = Input::<u8>::with_theme(&theme)
+            .with_prompt("Age")
+            .live_feedback(true)
+            .max_attempts(1)
+            .interact_on(&term)
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(matches!(err, Error::AttemptsExceeded { attempts: 1 }));
+        let expected = [("", false), ("1", false), ("1x", true)];
+        let expected: Vec<_> = expected.iter().map(|&(t, e)| (t.to_string(), e)).collect();
+        assert_eq!(*seen.lock().unwrap(), expected);
+
+        let mut buf = String::new();
+        SimpleTheme
+            .format_input_feedback(&mut buf, "1x", Some("bad"))
+            .unwrap();
+        assert_eq!(buf, "2 characters - bad");
+
+        let session = Script::new()
+            .expect_prompt("Age")
+            .type_line("7")
+            .start()
+            .unwrap();
+        let age = Input::<u8>::with_theme(&theme)
+            .with_prompt("Age")
+            .live_feedback(true)
+            .raw_mode(false)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(age, 7);
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_validate_while_typing() {
+        let term = Term::buffered_stderr();
+        let seen = Arc::new(Mutex::new(vec![]));
+        let record = seen.clone();
+        let theme = ExtendedTheme::extending(SimpleTheme).with_format_input_warning(
+            move |base, f, warning| {
+                record.lock().unwrap().push(warning.to_string());
+                base.format_input_warning(f, warning)
+            },
+        );
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("ab")
+            .start()
+            .unwrap();
+        let name = Input::<String>::with_theme(&theme)
+            .with_prompt("Name")
+            .validate_while_typing(|text: &str| match text.len() {
+                0 | 1 => Err("too short"),
+                _ => Ok(()),
+            })
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(name, "ab");
+        assert_eq!(*seen.lock().unwrap(), vec!["too short", "too short"]);
+    }
+
+    #[test]
+    fn test_allowed_chars() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Port")
+            .type_line("8a0-8")
+            .expect_prompt("Port")
+            .type_line("4x43")
+            .start()
+            .unwrap();
+        let mut input = Input::<u16>::new();
+        input.with_prompt("Port").allowed_chars("0123456789");
+        assert_eq!(input.interact_on(&term).unwrap(), 808);
+        assert_eq!(input.raw_mode(false).interact_on(&term).unwrap(), 443);
+        session.finish().unwrap();
+    }
+
+    #[test]
+    fn test_max_length() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Code")
+            .type_line("abcdef")
+            .expect_prompt("Code")
+            .type_line("uvwxyz")
+            .start()
+            .unwrap();
+        let mut input = Input::<String>::new();
+        input.with_prompt("Code").max_length(4).length_counter(true);
+        assert_eq!(input.interact_on(&term).unwrap(), "abcd");
+        assert_eq!(input.raw_mode(false).interact_on(&term).unwrap(), "uvwx");
+        session.finish().unwrap();
+
+        let mut buf = String::new();
+        SimpleTheme.format_length_counter(&mut buf, 12, 64).unwrap();
+        assert_eq!(buf, "12/64");
+    }
+
+    #[test]
+    fn test_timeout() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Deploy?")
+            .expect_prompt("Run?")
+            .press(Key::Char('x'))
+            .press(Key::Char('n'))
+            .start()
+            .unwrap();
+        let deploy = Confirmation::new()
+            .with_text("Deploy?")
+            .default(false)
+            .timeout(Duration::from_millis(50))
+            .interact_on(&term)
+            .unwrap();
+        // the countdown stops at the first key, even an invalid one
+        let key = KeyPrompt::new()
+            .with_text("Run?")
+            .items(&['y', 'n'])
+            .timeout(Duration::from_millis(50))
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(!deploy);
+        assert_eq!(key, 'n');
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_key_prompt_help() {
+        let (capture, term) = Capture::new();
+        let session = Script::new()
+            .expect_prompt("Deploy?")
+            .press(Key::Char('n'))
+            .start()
+            .unwrap();
+        let key = KeyPrompt::new()
+            .with_text("Deploy?")
+            .items(&[('y', "deploy the release"), ('n', "keep the old release")])
+            .max_width(20)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(key, 'n');
+        // the help wraps over three rows that are all cleared again
+        let output = capture.output();
+        assert!(output.contains("\nold release\x1b[3A\r\x1b[14C"));
+        assert!(output.ends_with("\x1b[1B\r\x1b[2K\x1b[3ADeploy?: n\n"));
+    }
+
+    #[test]
+    fn test_idle_timeout() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Still there?")
+            .idle()
+            .press(Key::Char('n'))
+            .expect_prompt("Still there?")
+            .press(Key::Char('x'))
+            .idle()
+            .start()
+            .unwrap();
+        let mut confirm = Confirmation::new();
+        confirm.with_text("Still there?");
+        let restarted = confirm
+            .idle_timeout(Duration::from_millis(20), IdleAction::Restart)
+            .interact_on(&term)
+            .unwrap();
+        let picked = confirm
+            .idle_timeout(Duration::from_millis(20), IdleAction::SelectDefault)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert!(!restarted);
+        assert!(picked);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_password_reveal() {
+        for &reveal in &[false, true] {
+            let (capture, term) = Capture::new();
+            let session = Script::new()
+                .expect_prompt("Password")
+                .type_line("se\u{12}cret")
+                .start()
+                .unwrap();
+            let password = PasswordInput::new()
+                .with_prompt("Password")
+                .with_mask(Some('*'))
+                .allow_reveal(reveal)
+                .interact_on(&term)
+                .unwrap();
+            session.finish().unwrap();
+            assert_eq!(password, "secret");
+            assert_eq!(capture.output().contains("secret"), reveal);
+        }
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Name")
+            .press(Key::CtrlC)
+            .start()
+            .unwrap();
+        let err = Input::<String>::new()
+            .with_prompt("Name")
+            .interact_on(&term)
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(matches!(err, Error::Interrupted));
+        assert_eq!(session::take_interrupt(), None);
+    }
 }
@@ -0,0 +1,540 @@
+//! A menu whose items are searched for as the query is typed.
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use console::{Key, Term};
+use error::Result;
+use localization::{self, Localization};
+use select::{line_mode, menu_style, next, prev, scroll_top, viewport};
+use session::{self, KeyInput};
+use theme::{get_default_theme, CompleteCallback, ListItem, TermThemeRenderer, Theme};
+
+/// How often the results of a running search are checked for.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Fetches the items for a query.
+type FetchCallback = Arc<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
+/// Fetches the items for the queries of a prompt on one background
+/// thread.
+///
+/// Queries replaced before the thread got to them are skipped and the
+/// items found for replaced queries are dropped.  The thread exits once
+/// the worker is dropped and the running fetch returned.
+struct Worker {
+    queries: Sender<(usize, String)>,
+    found: Receiver<(usize, Vec<String>)>,
+    latest: usize,
+}
+
+impl Worker {
+    fn new(fetch: FetchCallback) -> Worker {
+        let (queries, pending) = mpsc::channel::<(usize, String)>();
+        let (results, found) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(mut query) = pending.recv() {
+                while let Ok(newer) = pending.try_recv() {
+                    query = newer;
+                }
+                if results.send((query.0, fetch(&query.1))).is_err() {
+                    break;
+                }
+            }
+        });
+        Worker {
+            queries,
+            found,
+            latest: 0,
+        }
+    }
+
+    /// Starts fetching the items for `query` in place of earlier queries.
+    fn fetch(&mut self, query: &str) {
+        self.latest += 1;
+        self.queries.send((self.latest, query.into())).ok();
+    }
+
+    /// Returns the items found for the latest query if they arrived.
+    fn try_recv(&self) -> std::result::Result<Vec<String>, TryRecvError> {
+        loop {
+            let (id, items) = self.found.try_recv()?;
+            if id == self.latest {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Waits for the items found for the latest query.
+    fn recv(&self) -> Vec<String> {
+        while let Ok((id, items)) = self.found.recv() {
+            if id == self.latest {
+                return items;
+            }
+        }
+        vec![]
+    }
+}
+
+/// Renders a menu whose items are fetched for the typed query.
+///
+/// The items are fetched by a callback on a background thread once no
+/// key was pressed for a short while, so that slow sources like a web
+/// API do not hold up typing.  A loading line is shown until they
+/// arrive.  An async source can be awaited with the `block_on` of its
+/// runtime inside the callback.
+///
+/// On terminals that cannot move the cursor and in accessible mode the
+/// query is asked for and the items are numbered instead.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::Search;
+///
+/// let fruits = ["apple", "apricot", "banana", "cherry"];
+/// let fruit = Search::new(move |query: &str| {
+///     fruits
+///         .iter()
+///         .filter(|fruit| fruit.starts_with(query))
+///         .map(|fruit| fruit.to_string())
+///         .collect()
+/// })
+/// .with_prompt("Fruit")
+/// .interact()?;
+/// println!("You picked {}", fruit);
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Search<'a> {
+    fetch: FetchCallback,
+    query: String,
+    debounce: Duration,
+    prompt: Option<String>,
+    clear: bool,
+    help: Option<String>,
+    theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    accessible: bool,
+}
+
+impl<'a> Search<'a> {
+    /// Creates a search fetching the items for a query with `fetch`.
+    pub fn new<F>(fetch: F) -> Search<'static>
+    where
+        F: Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    {
+        Search::with_theme(get_default_theme(), fetch)
+    }
+
+    /// Same as `new` but with a specific theme.
+    pub fn with_theme<F>(theme: &'a dyn Theme, fetch: F) -> Search<'a>
+    where
+        F: Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    {
+        Search {
+            fetch: Arc::new(fetch),
+            query: String::new(),
+            debounce: Duration::from_millis(250),
+            prompt: None,
+            clear: true,
+            help: None,
+            theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            accessible: false,
+        }
+    }
+
+    /// Starts out with `text` typed into the query.
+    pub fn with_query(&mut self, text: &str) -> &mut Search<'a> {
+        self.query = text.into();
+        self
+    }
+
+    /// Sets how long no key has to be pressed before the items are
+    /// fetched for the changed query.
+    ///
+    /// The default is 250 milliseconds.
+    pub fn debounce(&mut self, val: Duration) -> &mut Search<'a> {
+        self.debounce = val;
+        self
+    }
+
+    /// Prefaces the menu with a prompt.
+    ///
+    /// When a prompt is set the system also prints out a confirmation after
+    /// the selection.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut Search<'a> {
+        self.prompt = Some(prompt.to_string());
+        self
+    }
+
+    /// Shows a help line below the menu, eg: which keys to press.
+    ///
+    /// The line is cleared along with the menu.
+    pub fn with_help(&mut self, text: &str) -> &mut Search<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Sets the clear behavior of the menu.
+    ///
+    /// The default is to clear the menu.
+    pub fn clear(&mut self, val: bool) -> &mut Search<'a> {
+        self.clear = val;
+        self
+    }
+
+    /// Enables or disables the accessible mode.
+    ///
+    /// The query is then asked as a question and the items found are
+    /// printed once with numbers.  Setting `DIALOGUER_ACCESSIBLE`
+    /// enables it for all menus.
+    pub fn accessible(&mut self, val: bool) -> &mut Search<'a> {
+        self.accessible = val;
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Search<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The callback is only invoked if a prompt is set.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Search<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Search<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Search<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Search<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Search<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Search<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Enables user interaction and returns the picked item.
+    pub fn interact(&self) -> Result<String> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but returns `None` if the user cancelled with Esc.
+    pub fn interact_opt(&self) -> Result<Option<String>> {
+        self.interact_on_opt(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<String> {
+        Ok(self
+            ._interact_on(term, false)?
+            .ok_or_else(|| io::Error::other("Quit not allowed in this case"))?)
+    }
+
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<String>> {
+        Ok(self._interact_on(term, true)?)
+    }
+
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<String>> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+        if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
+        }
+        if line_mode(term, self.accessible) {
+            return self.interact_lines(&mut render);
+        }
+        render.hide_cursor()?;
+        if let Some(ref prompt) = self.prompt {
+            render.prompt(prompt)?;
+        }
+        let mut query = self.query.clone();
+        let mut items: Vec<String> = vec![];
+        let mut sel = 0;
+        let mut top = 0;
+        // when the changed query is fetched and whether a fetch is under way
+        let mut due = Some(Instant::now());
+        let mut loading = false;
+        let mut worker = Worker::new(self.fetch.clone());
+        loop {
+            render.filter(&query)?;
+            let waiting = due.is_some() || loading;
+            if waiting {
+                render.loading()?;
+            }
+            let reserved = 1 + waiting as usize + self.help.is_some() as usize;
+            let capacity = viewport(&render, items.len(), reserved);
+            top = scroll_top(top, sel, items.len(), capacity, !0);
+            for (idx, item) in items.iter().enumerate().skip(top).take(capacity) {
+                let pos = ListItem {
+                    index: idx,
+                    count: items.len(),
+                    disabled: false,
+                    score: None,
+                };
+                render.selection(item, menu_style(sel == idx), pos)?;
+            }
+            if let Some(ref help) = self.help {
+                render.help(help)?;
+            }
+            let key = if let Some(at) = due {
+                let now = Instant::now();
+                if at > now && session::key_ready(at - now)? {
+                    Some(session::read_key(term)?)
+                } else {
+                    worker.fetch(&query);
+                    loading = true;
+                    due = None;
+                    None
+                }
+            } else if loading {
+                match worker.try_recv() {
+                    Ok(found) => {
+                        items = found;
+                        sel = 0;
+                        loading = false;
+                        None
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        items.clear();
+                        loading = false;
+                        None
+                    }
+                    Err(TryRecvError::Empty) => {
+                        if session::key_ready(POLL)? {
+                            Some(session::read_key(term)?)
+                        } else {
+                            None
+                        }
+                    }
+                }
+            } else {
+                match session::read_key_input(term, None)? {
+                    KeyInput::Pressed(key) => Some(key),
+                    _ => None,
+                }
+            };
+            match key {
+                Some(Key::Char(c)) if !c.is_control() => {
+                    query.push(c);
+                    due = Some(Instant::now() + self.debounce);
+                    loading = false;
+                }
+                Some(Key::Backspace) => {
+                    query.pop();
+                    due = Some(Instant::now() + self.debounce);
+                    loading = false;
+                }
+                Some(Key::ArrowDown) if !items.is_empty() => sel = next(sel, items.len(), true),
+                Some(Key::ArrowUp) if !items.is_empty() => sel = prev(sel, items.len(), true),
+                Some(Key::Escape) if allow_quit => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+                    if let Some(ref prompt) = self.prompt {
+                        render.cancelled(prompt)?;
+                    }
+                    return Ok(None);
+                }
+                Some(Key::Enter) => {
+                    // waits for the items of the query as it was typed
+                    if due.take().is_some() {
+                        worker.fetch(&query);
+                        loading = true;
+                    }
+                    if loading {
+                        items = worker.recv();
+                        loading = false;
+                        sel = 0;
+                    }
+                    if let Some(item) = items.get(sel) {
+                        if self.clear {
+                            render.clear()?;
+                        }
+                        if let Some(ref prompt) = self.prompt {
+                            render.single_prompt_selection(prompt, item)?;
+                        }
+                        return Ok(Some(item.clone()));
+                    }
+                }
+                _ => {}
+            }
+            render.clear_preserve_prompt()?;
+        }
+    }
+
+    /// Asks for the query and the number of an item found on terminals
+    /// that cannot move the cursor.
+    fn interact_lines(&self, render: &mut TermThemeRenderer) -> io::Result<Option<String>> {
+        if let Some(ref prompt) = self.prompt {
+            render.prompt(prompt)?;
+        }
+        let mut worker = Worker::new(self.fetch.clone());
+        loop {
+            let label = localization::with(|l| l.filter.to_string());
+            let default = Some(self.query.as_str()).filter(|query| !query.is_empty());
+            render.input_prompt(&label, default)?;
+            let mut query = session::read_line(render.term(), None)?;
+            render.add_input(&query);
+            if query.trim().is_empty() {
+                query = self.query.clone();
+            }
+            worker.fetch(&query);
+            let items = worker.recv();
+            if items.is_empty() {
+                render.error(&localization::with(|l| l.nothing_found.to_string()))?;
+                continue;
+            }
+            for (idx, item) in items.iter().enumerate() {
+                render.numbered_line(item, idx + 1)?;
+            }
+            let prompt = localization::with(|l| {
+                Localization::fill(&l.enter_choice, &[("total", &items.len())])
+            });
+            render.input_prompt(&prompt, None)?;
+            let input = session::read_line(render.term(), None)?;
+            render.add_input(&input);
+            match input.trim().parse::<usize>() {
+                Ok(num) if num >= 1 && num <= items.len() => {
+                    let item = items[num - 1].clone();
+                    if let Some(ref prompt) = self.prompt {
+                        render.single_prompt_selection(prompt, &item)?;
+                    }
+                    return Ok(Some(item));
+                }
+                _ => render.error(&localization::with(|l| l.invalid_choice.to_string()))?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::Script;
+
+    fn fruits(query: &str) -> Vec<String> {
+        ["apple", "apricot", "banana"]
+            .iter()
+            .filter(|fruit| fruit.starts_with(query))
+            .map(|fruit| fruit.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_worker() {
+        use std::sync::Mutex;
+
+        let threads = Arc::new(Mutex::new(vec![]));
+        let fetch: FetchCallback = {
+            let threads = threads.clone();
+            Arc::new(move |query: &str| {
+                threads.lock().unwrap().push(thread::current().id());
+                fruits(query)
+            })
+        };
+        let mut worker = Worker::new(fetch);
+        worker.fetch("a");
+        worker.fetch("b");
+        assert_eq!(worker.recv(), ["banana"]);
+        worker.fetch("ap");
+        assert_eq!(worker.recv(), ["apple", "apricot"]);
+        let threads = threads.lock().unwrap();
+        assert!(threads.iter().all(|id| *id == threads[0]));
+    }
+
+    #[test]
+    fn test_enter_while_debouncing() {
+        use std::sync::Mutex;
+
+        let term = Term::buffered_stderr();
+        let threads = Arc::new(Mutex::new(vec![]));
+        let record = threads.clone();
+        let session = Script::new()
+            .expect_prompt("Fruit")
+            .press(Key::Char('b'))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let fruit = Search::new(move |query: &str| {
+            record.lock().unwrap().push(thread::current().id());
+            fruits(query)
+        })
+        .with_prompt("Fruit")
+        .debounce(Duration::from_secs(60))
+        .interact_on(&term)
+        .unwrap();
+        session.finish().unwrap();
+        assert_eq!(fruit, "banana");
+        let threads = threads.lock().unwrap();
+        assert!(threads.iter().all(|id| *id != thread::current().id()));
+    }
+
+    #[test]
+    fn test_search() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Fruit")
+            .press(Key::Char('a'))
+            .press(Key::Char('p'))
+            .press(Key::Enter)
+            .expect_prompt("Fruit")
+            .press(Key::Char('r'))
+            .press(Key::Enter)
+            .expect_prompt("Fruit")
+            .press(Key::Char('c'))
+            .press(Key::Enter)
+            .press(Key::Escape)
+            .start()
+            .unwrap();
+        let mut search = Search::new(fruits);
+        search.with_prompt("Fruit");
+        assert_eq!(search.interact_on(&term).unwrap(), "apple");
+        assert_eq!(
+            search.with_query("ap").interact_on(&term).unwrap(),
+            "apricot"
+        );
+        search.with_query("");
+        assert_eq!(search.interact_on_opt(&term).unwrap(), None);
+        session.finish().unwrap();
+    }
+}
@@ -1,38 +1,180 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::io;
 use std::iter::repeat;
-use std::ops::Rem;
+use std::time::Duration;
 
-use theme::{get_default_theme, SelectionStyle, TermThemeRenderer, Theme};
+use env;
+use error::{Error, Result};
+use fuzzy::{FuzzyMatcher, Matcher};
+use localization::{self, Localization};
+use prompts::{no_default, IdleAction};
+use session::{self, KeyInput};
+#[cfg(feature = "state")]
+use state::StateStore;
+use theme::{
+    get_default_theme, CompleteCallback, InvalidKeyFeedback, ListItem, SelectionStyle,
+    TermThemeRenderer, Theme,
+};
 
-use console::{Key, Term};
+use console::{measure_text_width, Key, Term};
 
 /// Renders a selection menu.
+///
+/// On terminals that cannot move the cursor (eg: with `TERM=dumb`) and
+/// in accessible mode the items are numbered instead and the choice is
+/// read as a line.
 pub struct Select<'a> {
     default: usize,
     items: Vec<String>,
+    rows: Vec<Vec<String>>,
+    header: Option<Vec<String>>,
     prompt: Option<String>,
     clear: bool,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    report_cancelled: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<usize>,
+    assume_default: bool,
+    env_key: Option<String>,
+    default_without_tty: bool,
     paged: bool,
+    scroll_offset: usize,
+    alternate_screen: bool,
+    hide_cursor: bool,
+    wrap: bool,
+    shortcuts: bool,
+    grid: bool,
+    preview: Option<PreviewCallback<'a>>,
+    preview_height: usize,
+    invalid_key: InvalidKeyFeedback,
+    accessible: bool,
+    truncate: bool,
+    lazy: Option<RefCell<LazyItems<'a>>>,
+    idle: Option<(Duration, IdleAction)>,
+    #[cfg(feature = "state")]
+    remember: Option<(String, RefCell<Box<dyn StateStore>>)>,
+}
+
+/// Renders the preview of the item with the given index.
+type PreviewCallback<'a> = Box<dyn Fn(usize) -> String + 'a>;
+
+/// Items of a `Select` that are loaded on demand.
+struct LazyItems<'a> {
+    iter: Box<dyn Iterator<Item = String> + 'a>,
+    loaded: Vec<String>,
+    total: Option<usize>,
+    done: bool,
 }
 
 /// Renders a multi select checkbox menu.
+///
+/// On terminals that cannot move the cursor (eg: with `TERM=dumb`) and
+/// in accessible mode the items are numbered instead and the choices
+/// are read as a line.
 pub struct Checkboxes<'a> {
-    defaults: Vec<bool>,
+    defaults: Vec<CheckState>,
     items: Vec<String>,
     prompt: Option<String>,
     clear: bool,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<Vec<usize>>,
+    assume_default: bool,
+    env_key: Option<String>,
+    default_without_tty: bool,
     paged: bool,
+    scroll_offset: usize,
+    alternate_screen: bool,
+    hide_cursor: bool,
+    wrap: bool,
+    accessible: bool,
+    truncate: bool,
+    filterable: bool,
+    filter_text: String,
+    matcher: Box<dyn Matcher>,
+    max_selections: Option<usize>,
+}
+
+/// The state of a checkbox item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    /// The item is not checked
+    Unchecked,
+    /// The item is checked
+    Checked,
+    /// The item stands for a group of which only some entries are checked
+    Partial,
+}
+
+impl CheckState {
+    /// Returns the state after pressing space, partial items get checked.
+    fn toggled(self) -> CheckState {
+        match self {
+            CheckState::Checked => CheckState::Unchecked,
+            _ => CheckState::Checked,
+        }
+    }
+
+    /// Returns the style of an item in this state.
+    fn style(self, selected: bool) -> SelectionStyle {
+        match (self, selected) {
+            (CheckState::Checked, true) => SelectionStyle::CheckboxCheckedSelected,
+            (CheckState::Checked, false) => SelectionStyle::CheckboxCheckedUnselected,
+            (CheckState::Partial, true) => SelectionStyle::CheckboxPartialSelected,
+            (CheckState::Partial, false) => SelectionStyle::CheckboxPartialUnselected,
+            (CheckState::Unchecked, true) => SelectionStyle::CheckboxUncheckedSelected,
+            (CheckState::Unchecked, false) => SelectionStyle::CheckboxUncheckedUnselected,
+        }
+    }
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> CheckState {
+        if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
 }
 
 /// Renders a list to order.
+///
+/// On terminals that cannot move the cursor (eg: with `TERM=dumb`) and
+/// in accessible mode the items are numbered instead and the order is
+/// read as a line.
 pub struct OrderList<'a> {
     items: Vec<String>,
     prompt: Option<String>,
     clear: bool,
+    help: Option<String>,
     theme: &'a dyn Theme,
+    term: Term,
+    max_width: Option<usize>,
+    localization: Option<&'a Localization>,
+    report: bool,
+    on_complete: Option<CompleteCallback>,
+    assumed: Option<Vec<usize>>,
+    assume_default: bool,
     paged: bool,
+    scroll_offset: usize,
+    alternate_screen: bool,
+    hide_cursor: bool,
+    wrap: bool,
+    positions: bool,
+    accessible: bool,
+    truncate: bool,
 }
 
 impl<'a> Default for Select<'a> {
@@ -43,7 +185,7 @@ impl<'a> Default for Select<'a> {
 
 impl<'a> Select<'a> {
     /// Creates the prompt with a specific text.
-    pub fn new() -> Select<'static> {
+    pub fn new() -> Select<'a> {
         Select::with_theme(get_default_theme())
     }
 
@@ -52,17 +194,154 @@ impl<'a> Select<'a> {
         Select {
             default: !0,
             items: vec![],
+            rows: vec![],
+            header: None,
             prompt: None,
             clear: true,
+            help: None,
             theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            report_cancelled: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
+            env_key: None,
+            default_without_tty: false,
             paged: false,
+            scroll_offset: !0,
+            alternate_screen: false,
+            hide_cursor: true,
+            wrap: true,
+            shortcuts: false,
+            grid: false,
+            preview: None,
+            preview_height: 5,
+            invalid_key: InvalidKeyFeedback::Silent,
+            accessible: false,
+            truncate: false,
+            lazy: None,
+            idle: None,
+            #[cfg(feature = "state")]
+            remember: None,
         }
     }
-    /// Enables or disables paging
+    /// Enables or disables paging.
+    ///
+    /// Lists taller than the terminal always scroll.  Paging also lets
+    /// left and right move the cursor by a page and shows the page
+    /// below the list.
     pub fn paged(&mut self, val: bool) -> &mut Select<'a> {
         self.paged = val;
         self
     }
+    /// Sets the number of items kept visible above and below the
+    /// cursor when paging.
+    ///
+    /// By default the cursor is kept centered.
+    pub fn scroll_offset(&mut self, val: usize) -> &mut Select<'a> {
+        self.scroll_offset = val;
+        self
+    }
+    /// Shows the menu on the alternate screen of the terminal.
+    ///
+    /// This keeps long menus out of the scrollback.  The answer is
+    /// reported on the normal screen.
+    pub fn alternate_screen(&mut self, val: bool) -> &mut Select<'a> {
+        self.alternate_screen = val;
+        self
+    }
+
+    /// Hides the cursor while the menu is navigated.
+    ///
+    /// The cursor is shown again once the menu is answered.  This is
+    /// enabled by default.
+    pub fn hide_cursor(&mut self, val: bool) -> &mut Select<'a> {
+        self.hide_cursor = val;
+        self
+    }
+
+    /// Enables or disables wrapping around at the first and last item.
+    ///
+    /// When disabled moving past the last item stays on it.  This is
+    /// enabled by default.
+    pub fn wrap(&mut self, val: bool) -> &mut Select<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Enables or disables the accessible mode.
+    ///
+    /// The menu is then not redrawn in place.  The items are printed
+    /// once with numbers and the answer is asked as a question, which
+    /// screen readers can follow.  Setting `DIALOGUER_ACCESSIBLE` enables
+    /// it for all menus.
+    pub fn accessible(&mut self, val: bool) -> &mut Select<'a> {
+        self.accessible = val;
+        self
+    }
+
+    /// Cuts off items at the width of the terminal instead of wrapping
+    /// them, so that each item takes up a single line.
+    pub fn truncate(&mut self, val: bool) -> &mut Select<'a> {
+        self.truncate = val;
+        self
+    }
+
+    /// Sets how the menu reacts to keys it ignores.
+    ///
+    /// The default is to ignore them silently.
+    pub fn on_invalid_key(&mut self, val: InvalidKeyFeedback) -> &mut Select<'a> {
+        self.invalid_key = val;
+        self
+    }
+    /// Enables or disables number shortcuts.
+    ///
+    /// When enabled the first nine visible items are numbered and
+    /// pressing a number selects its item right away.
+    pub fn number_shortcuts(&mut self, val: bool) -> &mut Select<'a> {
+        self.shortcuts = val;
+        self
+    }
+    /// Arranges short items in as many columns as fit the terminal.
+    ///
+    /// The items run down the columns.  Left and right move the cursor
+    /// across columns instead of paging.  Tables, lazily loaded items
+    /// and menus with number shortcuts are always shown one per line.
+    pub fn grid(&mut self, val: bool) -> &mut Select<'a> {
+        self.grid = val;
+        self
+    }
+    /// Shows a preview of the highlighted item below the menu.
+    ///
+    /// The callback is passed the index of the item and returns the
+    /// text to show, eg: the start of a file or a description.
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<std::error::Error>> {
+    /// use dialoguer::Select;
+    ///
+    /// let files = &["Cargo.toml", "README.md"];
+    /// let selection = Select::new()
+    ///     .items(files)
+    ///     .preview(|idx| std::fs::read_to_string(files[idx]).unwrap_or_default())
+    ///     .interact()?;
+    /// # Ok(()) } fn main() { test().unwrap(); }
+    /// ```
+    pub fn preview<F: Fn(usize) -> String + 'a>(&mut self, f: F) -> &mut Select<'a> {
+        self.preview = Some(Box::new(f));
+        self
+    }
+    /// Sets the number of lines of the preview (5 by default).
+    ///
+    /// Longer previews are cut off, shorter ones are padded so that the
+    /// menu does not jump around.
+    pub fn preview_height(&mut self, val: usize) -> &mut Select<'a> {
+        self.preview_height = val;
+        self
+    }
     /// Sets the clear behavior of the menu.
     ///
     /// The default is to clear the menu.
@@ -80,157 +359,865 @@ impl<'a> Select<'a> {
     /// Add a single item to the selector.
     pub fn item(&mut self, item: &str) -> &mut Select<'a> {
         self.items.push(item.to_string());
+        self.rows.push(vec![item.to_string()]);
         self
     }
 
     /// Adds multiple items to the selector.
     pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Select<'a> {
         for item in items {
-            self.items.push(item.to_string());
+            self.item(&item.to_string());
+        }
+        self
+    }
+
+    /// Adds items from an iterator that is only advanced as far as the
+    /// items are shown.
+    ///
+    /// `total` is the number of items the iterator yields if known.
+    /// Without it the cursor does not wrap around from the first to the
+    /// last item, so the iterator may be endless.  The items follow the
+    /// ones added otherwise.
+    pub fn lazy_items<I, T>(&mut self, items: I, total: Option<usize>) -> &mut Select<'a>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'a,
+        T: ToString + 'a,
+    {
+        self.lazy = Some(RefCell::new(LazyItems {
+            iter: Box::new(items.into_iter().map(|item| item.to_string())),
+            loaded: vec![],
+            total,
+            done: false,
+        }));
+        self
+    }
+
+    /// Add a single row of columns to the selector.
+    ///
+    /// Columns of all rows (and the header) are aligned when rendered.
+    /// After the selection the cells of the row are reported separated
+    /// by a space.
+    pub fn row<T: ToString>(&mut self, row: &[T]) -> &mut Select<'a> {
+        let row: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+        self.items.push(row.join(" "));
+        self.rows.push(row);
+        self
+    }
+
+    /// Adds multiple rows of columns to the selector.
+    pub fn rows<T: ToString>(&mut self, rows: &[Vec<T>]) -> &mut Select<'a> {
+        for row in rows {
+            self.row(row);
         }
         self
     }
 
+    /// Sets a header row rendered above the items.
+    ///
+    /// The header takes part in the column alignment of the rows.
+    pub fn with_header<T: ToString>(&mut self, header: &[T]) -> &mut Select<'a> {
+        self.header = Some(header.iter().map(|cell| cell.to_string()).collect());
+        self
+    }
+
     /// Prefaces the menu with a prompt.
     ///
     /// When a prompt is set the system also prints out a confirmation after
-    /// the selection.
+    /// the selection.  A multi-line prompt is confirmed with its last line.
     pub fn with_prompt(&mut self, prompt: &str) -> &mut Select<'a> {
         self.prompt = Some(prompt.to_string());
         self
     }
 
+    /// Shows a help line below the menu, eg: which keys to press.
+    ///
+    /// The line is cleared along with the menu.
+    pub fn with_help(&mut self, text: &str) -> &mut Select<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Select<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Indicates whether to report a cancelled prompt.
+    pub fn report_cancelled(&mut self, val: bool) -> &mut Select<'a> {
+        self.report_cancelled = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The callback is only invoked if a prompt is set.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Select<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: usize) -> &mut Select<'a> {
+        self.assumed = Some(value);
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.  Without
+    /// a default the prompt fails.
+    pub fn assume_default(&mut self) -> &mut Select<'a> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Takes the answer from an environment variable if it is set.
+    ///
+    /// The variable has to hold the text of an item.  The menu is not
+    /// shown then but the answer is still reported.  Lazily loaded items
+    /// are only matched if their number is known.
+    pub fn env_key(&mut self, key: &str) -> &mut Select<'a> {
+        self.env_key = Some(key.into());
+        self
+    }
+
+    /// Answers with the default without asking if stdin is not a
+    /// terminal, eg: when run in CI.
+    ///
+    /// The answer is still reported.  Without a default the menu is
+    /// shown as usual.
+    pub fn accept_default_without_tty(&mut self, val: bool) -> &mut Select<'a> {
+        self.default_without_tty = val;
+        self
+    }
+
+    /// Restarts the menu or chooses the default once no key was pressed
+    /// for the given time, eg: in a kiosk left unattended.
+    ///
+    /// The timer starts over with every key.  A restart moves the cursor
+    /// back to where it started.  Without a default the menu restarts
+    /// either way.  Menus read line by line never go idle, nor do menus on
+    /// platforms other than unix, which cannot wait for keys with a
+    /// timeout.
+    pub fn idle_timeout(&mut self, timeout: Duration, action: IdleAction) -> &mut Select<'a> {
+        self.idle = Some((timeout, action));
+        self
+    }
+
+    /// Starts the cursor on the item chosen the last time the prompt
+    /// with this id was answered.
+    ///
+    /// The chosen item is remembered in `store`, a failure to remember it
+    /// is ignored.  The default is used if nothing is remembered or the
+    /// item is no longer listed.
+    #[cfg(feature = "state")]
+    pub fn remember<S: StateStore + 'static>(&mut self, id: &str, store: S) -> &mut Select<'a> {
+        self.remember = Some((id.into(), RefCell::new(Box::new(store))));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Select<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Select<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Select<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Select<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Select<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The index of the selected item.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<usize> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the terminal set with `on`.  A menu
+    /// without items fails with `Error::NoItems`.
+    pub fn interact(&self) -> Result<usize> {
+        self.interact_on(&self.term)
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// The index of the selected item. None if the user
     /// cancelled with Esc or 'q'.
-    /// The dialog is rendered on stderr.
-    pub fn interact_opt(&self) -> io::Result<Option<usize>> {
-        self._interact_on(&Term::stderr(), true)
+    /// The dialog is rendered on the terminal set with `on`.
+    pub fn interact_opt(&self) -> Result<Option<usize>> {
+        Ok(self._interact_on(&self.term, true)?)
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<usize> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &Term) -> Result<usize> {
+        Ok(self
+            ._interact_on(term, false)?
+            .ok_or_else(|| io::Error::other("Quit not allowed in this case"))?)
     }
 
     /// Like `interact_opt` but allows a specific terminal to be set.
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<usize>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<usize>> {
+        Ok(self._interact_on(term, true)?)
+    }
+
+    /// Like `interact` but returns the selected item along with its index.
+    pub fn interact_items(&self) -> Result<(usize, String)> {
+        self.interact_items_on(&self.term)
+    }
+
+    /// Like `interact_items` but allows a specific terminal to be set.
+    pub fn interact_items_on(&self, term: &Term) -> Result<(usize, String)> {
+        let idx = self.interact_on(term)?;
+        Ok((idx, self.item_text(idx)))
     }
 
     /// Like `interact` but allows a specific terminal to be set.
     fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
-        let mut page = 0;
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
+        let sel = self.run(term, allow_quit)?;
+        #[cfg(feature = "state")]
+        {
+            if let (Some(sel), Some((id, store))) = (sel, self.remember.as_ref()) {
+                // the answer is kept even if it cannot be remembered
+                store.borrow_mut().set(id, &self.item_text(sel)).ok();
+            }
+        }
+        Ok(sel)
+    }
+
+    /// Shows the menu and returns the index of the chosen item.
+    fn run(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
+        let (header, mut items) = self.aligned_rows();
+        if let Some(ref lazy) = self.lazy {
+            items.extend(lazy.borrow().loaded.iter().cloned());
+        }
+        let mut top = 0;
+        let len = if self.lazy.is_some() {
+            !0
         } else {
             self.items.len()
         };
-        let pages = (self.items.len() / capacity) + 1;
         let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = self.default;
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_report_cancelled(self.report_cancelled);
+        render.set_on_complete(self.on_complete.as_deref());
+        render.set_truncate(self.truncate);
+        if let Some((key, value)) = env::answer(self.env_key.as_deref()) {
+            self.load_all(&mut items);
+            let sel = (0..items.len())
+                .position(|idx| self.item_text(idx).trim() == value.trim())
+                .ok_or_else(|| env::invalid(key, &format!("no item named {:?}", value)))?;
+            if let Some(ref prompt) = self.prompt {
+                render.single_prompt_selection(prompt, &self.item_text(sel))?;
+            }
+            return Ok(Some(sel));
+        }
+        let mut sel = self.initial(&items);
+        let no_tty = self.default_without_tty && !session::stdin_is_term();
+        if self.assume_default || no_tty {
+            self.load_items(&mut items, sel.saturating_add(1));
+        }
+        let assumed = match self.assumed {
+            Some(idx) => Some(idx),
+            None if sel < items.len() && (self.assume_default || no_tty) => Some(sel),
+            None if self.assume_default => return Err(no_default()),
+            None => None,
+        };
+        if let Some(sel) = assumed {
+            self.load_items(&mut items, sel.saturating_add(1));
+            check_assumed(&[sel], items.len())?;
+            if let Some(ref prompt) = self.prompt {
+                render.single_prompt_selection(prompt, &self.item_text(sel))?;
+            }
+            return Ok(Some(sel));
+        }
+        self.load_items(&mut items, 1);
+        if items.is_empty() {
+            return Err(Error::NoItems.into());
+        }
+        if line_mode(term, self.accessible) {
+            return self.interact_lines(&mut render, &mut items, allow_quit);
+        }
+        if self.alternate_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.hide_cursor {
+            render.hide_cursor()?;
+        }
         if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
             render.prompt(prompt)?;
         }
-        let mut size_vec = Vec::new();
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(size.clone());
-        }
+        let grid = self.grid && self.lazy.is_none() && header.is_none() && !self.shortcuts;
+        let cell_width = if grid {
+            render.selection_width(&items)?
+        } else {
+            0
+        };
+        let preview_rows = if self.preview.is_some() {
+            self.preview_height
+        } else {
+            0
+        };
+        let below_rows = preview_rows + self.help.is_some() as usize;
         loop {
-            for (idx, item) in self
-                .items
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.selection(
-                    item,
-                    if sel == idx {
-                        SelectionStyle::MenuSelected
+            // the width is measured on every redraw to follow resizes
+            let columns = if grid {
+                grid_columns(items.len(), cell_width, render.width())
+            } else {
+                1
+            };
+            let grid_rows = items.len().div_ceil(columns);
+            let cur = if sel == !0 { 0 } else { sel };
+            let (capacity, visible) = if columns > 1 {
+                let capacity = viewport(&render, grid_rows, below_rows);
+                top = scroll_top(
+                    top,
+                    cur % grid_rows,
+                    grid_rows,
+                    capacity,
+                    self.scroll_offset,
+                );
+                for row in top..(top + capacity).min(grid_rows) {
+                    let cells: Vec<_> = (0..columns)
+                        .map(|col| row + col * grid_rows)
+                        .filter(|&idx| idx < items.len())
+                        .map(|idx| (items[idx].as_str(), menu_style(sel == idx)))
+                        .collect();
+                    render.selection_row(&cells, cell_width)?;
+                }
+                (capacity, 0)
+            } else {
+                let total = if self.paged {
+                    self.known_len(&items)
+                } else {
+                    None
+                };
+                let reserved = header.is_some() as usize + total.is_some() as usize;
+                let capacity = viewport(&render, len, reserved + below_rows);
+                self.load_items(&mut items, cur + capacity + 1);
+                if sel != !0 {
+                    top = scroll_top(top, sel, items.len(), capacity, self.scroll_offset);
+                }
+                if let Some(ref header) = header {
+                    if self.shortcuts {
+                        let padding = " ".repeat(render.shortcut_width()?);
+                        render.table_header(&format!("{}{}", padding, header))?;
                     } else {
-                        SelectionStyle::MenuUnselected
-                    },
-                )?;
+                        render.table_header(header)?;
+                    }
+                }
+                for (idx, item) in items.iter().enumerate().skip(top).take(capacity) {
+                    let style = menu_style(sel == idx);
+                    let pos = ListItem {
+                        index: idx,
+                        count: items.len(),
+                        disabled: false,
+                        score: None,
+                    };
+                    if self.shortcuts {
+                        render.numbered_selection(item, idx - top + 1, style, pos)?;
+                    } else {
+                        render.selection(item, style, pos)?;
+                    }
+                }
+                if let Some(total) = total {
+                    let capacity = capacity.max(1);
+                    render.list_footer(
+                        cur / capacity + 1,
+                        total.div_ceil(capacity),
+                        None,
+                        total,
+                    )?;
+                }
+                (capacity, capacity.min(items.len() - top))
+            };
+            if let Some(ref preview) = self.preview {
+                let text = if sel == !0 {
+                    String::new()
+                } else {
+                    preview(sel)
+                };
+                render.preview(&text, self.preview_height)?;
+            }
+            if let Some(ref help) = self.help {
+                render.help(help)?;
             }
-            match term.read_key()? {
+            let idle = self.idle.map(|(idle, _)| idle);
+            let key = match session::read_key_input(term, idle)? {
+                KeyInput::Pressed(Key::Char(c @ '1'..='9')) if self.shortcuts => {
+                    match c as usize - '1' as usize {
+                        num if num < visible => {
+                            sel = top + num;
+                            Key::Enter
+                        }
+                        _ => Key::Unknown,
+                    }
+                }
+                KeyInput::Pressed(key) => key,
+                KeyInput::Resized => {
+                    render.clear_preserve_prompt()?;
+                    continue;
+                }
+                KeyInput::Idle => {
+                    let initial = self.initial(&items);
+                    match self.idle {
+                        Some((_, IdleAction::SelectDefault)) if initial < items.len() => {
+                            sel = initial;
+                            Key::Enter
+                        }
+                        _ => {
+                            sel = initial;
+                            top = 0;
+                            render.clear_preserve_prompt()?;
+                            continue;
+                        }
+                    }
+                }
+            };
+            match key {
                 Key::ArrowDown | Key::Char('j') => {
                     if sel == !0 {
                         sel = 0;
                     } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+                        sel = next(sel, items.len(), self.wrap);
                     }
                 }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
-                        if self.clear {
-                            term.clear_last_lines(self.items.len())?;
-                        }
-                        return Ok(None);
+                Key::Escape | Key::Char('q') if allow_quit => {
+                    if self.clear {
+                        render.clear()?;
                     }
+                    if let Some(ref prompt) = self.prompt {
+                        render.cancelled(prompt)?;
+                    }
+                    return Ok(None);
                 }
                 Key::ArrowUp | Key::Char('k') => {
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
+                    if sel != !0 && sel > 0 {
+                        sel -= 1;
+                    } else if self.wrap && self.load_all(&mut items) {
+                        sel = items.len() - 1;
                     } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
+                        sel = 0;
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-                        sel = page * capacity;
+                Key::ArrowLeft | Key::Char('h') if columns > 1 && sel != !0 && sel >= grid_rows => {
+                    sel -= grid_rows;
+                }
+                Key::ArrowRight | Key::Char('l') if columns > 1 => {
+                    if sel == !0 {
+                        sel = 0;
+                    } else if sel + grid_rows < items.len() {
+                        sel += grid_rows;
                     }
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page -= 1;
-                        }
-                        sel = page * capacity;
+                Key::ArrowLeft | Key::Char('h') if self.paged && columns == 1 => {
+                    if cur > 0 || !self.wrap || self.load_all(&mut items) {
+                        sel = page_up(cur, items.len(), capacity, self.wrap);
+                    } else {
+                        render.invalid_key(self.invalid_key)?;
                     }
                 }
+                Key::ArrowRight | Key::Char('l') if self.paged && columns == 1 => {
+                    self.load_items(&mut items, cur + 2 * capacity + 1);
+                    sel = page_down(cur, items.len(), capacity, self.wrap);
+                }
 
                 Key::Enter | Key::Char(' ') if sel != !0 => {
                     if self.clear {
                         render.clear()?;
                     }
                     if let Some(ref prompt) = self.prompt {
-                        render.single_prompt_selection(prompt, &self.items[sel])?;
+                        render.single_prompt_selection(prompt, &self.item_text(sel))?;
                     }
                     return Ok(Some(sel));
                 }
-                _ => {}
+                _ => render.invalid_key(self.invalid_key)?,
+            }
+            render.clear_preserve_prompt()?;
+        }
+    }
+
+    /// Asks for the number of an item on terminals that cannot move the
+    /// cursor.
+    fn interact_lines(
+        &self,
+        render: &mut TermThemeRenderer,
+        items: &mut Vec<String>,
+        allow_quit: bool,
+    ) -> io::Result<Option<usize>> {
+        if !self.load_all(items) {
+            let count = viewport(render, !0, 1);
+            self.load_items(items, count);
+        }
+        if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
+            render.prompt(prompt)?;
+        }
+        for (idx, item) in items.iter().enumerate() {
+            render.numbered_line(item, idx + 1)?;
+        }
+        let initial = self.initial(items);
+        let default = items.get(initial).map(|_| (initial + 1).to_string());
+        loop {
+            let prompt = localization::with(|l| {
+                Localization::fill(&l.enter_choice, &[("total", &items.len())])
+            });
+            render.input_prompt(&prompt, default.as_deref())?;
+            let input = session::read_line(render.term(), None)?;
+            render.add_input(&input);
+            let sel = match input.trim() {
+                "" if default.is_some() => initial,
+                "" if !session::is_term(render.term()) => return Err(Error::NotATty.into()),
+                "q" if allow_quit => {
+                    if let Some(ref prompt) = self.prompt {
+                        render.cancelled(prompt)?;
+                    }
+                    return Ok(None);
+                }
+                input => match parse_choices(input, items.len()).as_deref() {
+                    Some(&[sel]) => sel,
+                    _ => {
+                        render.error(&localization::with(|l| l.invalid_choice.to_string()))?;
+                        continue;
+                    }
+                },
+            };
+            if let Some(ref prompt) = self.prompt {
+                render.single_prompt_selection(prompt, &self.item_text(sel))?;
+            }
+            return Ok(Some(sel));
+        }
+    }
+
+    /// Returns the number of items unless more are loaded on demand
+    /// without a known total.
+    fn known_len(&self, items: &[String]) -> Option<usize> {
+        match self.lazy {
+            Some(ref lazy) if !lazy.borrow().done => {
+                lazy.borrow().total.map(|total| self.items.len() + total)
+            }
+            _ => Some(items.len()),
+        }
+    }
+
+    /// Returns the text reported for an item.
+    pub(crate) fn item_text(&self, idx: usize) -> String {
+        match self.lazy {
+            Some(ref lazy) if idx >= self.items.len() => {
+                lazy.borrow().loaded[idx - self.items.len()].clone()
+            }
+            _ => self.items[idx].clone(),
+        }
+    }
+
+    /// Returns the index of the item the cursor starts on.
+    #[cfg_attr(not(feature = "state"), allow(unused_variables))]
+    fn initial(&self, items: &[String]) -> usize {
+        #[cfg(feature = "state")]
+        {
+            let last = self
+                .remember
+                .as_ref()
+                .and_then(|(id, store)| store.borrow().get(id));
+            if let Some(last) = last {
+                if let Some(idx) = (0..items.len()).find(|&idx| self.item_text(idx) == last) {
+                    return idx;
+                }
+            }
+        }
+        self.default
+    }
+
+    /// Loads lazy items into `items` until it holds `count` items or the
+    /// iterator is exhausted.
+    fn load_items(&self, items: &mut Vec<String>, count: usize) {
+        if let Some(ref lazy) = self.lazy {
+            let mut lazy = lazy.borrow_mut();
+            while !lazy.done && items.len() < count {
+                match lazy.iter.next() {
+                    Some(item) => {
+                        items.push(item.clone());
+                        lazy.loaded.push(item);
+                    }
+                    None => lazy.done = true,
+                }
             }
-            if sel != !0 && (sel < page * capacity || sel >= (page + 1) * capacity) {
-                page = sel / capacity;
+        }
+    }
+
+    /// Loads all items if their number is known.  Returns `false` if
+    /// the end of the items is unknown.
+    fn load_all(&self, items: &mut Vec<String>) -> bool {
+        let total = match self.lazy {
+            Some(ref lazy) if !lazy.borrow().done => match lazy.borrow().total {
+                Some(total) => total,
+                None => return false,
+            },
+            _ => return true,
+        };
+        self.load_items(items, self.items.len() + total);
+        true
+    }
+
+    /// Returns the header and the items as rendered, with the columns
+    /// of table rows aligned.
+    fn aligned_rows(&self) -> (Option<String>, Vec<String>) {
+        let mut lines = align_columns(self.header.iter().chain(self.rows.iter()));
+        let header = if self.header.is_some() {
+            Some(lines.remove(0))
+        } else {
+            None
+        };
+        (header, lines)
+    }
+}
+
+/// Returns the number of items shown at once.  This is all of the `len`
+/// items if they fit on the terminal next to `reserved` other lines.
+///
+/// The rows of the prompt and the row the cursor is left on are kept
+/// free as well, so the prompt stays visible while the items scroll.
+pub(crate) fn viewport(render: &TermThemeRenderer, len: usize, reserved: usize) -> usize {
+    let rows = render.term().size().0 as usize;
+    len.min(
+        rows.saturating_sub(render.prompt_rows() + 1 + reserved)
+            .max(1),
+    )
+}
+
+/// Parses item numbers separated by commas or spaces, including ranges
+/// like `2-4`, into sorted indexes.  Returns `None` if a number is not
+/// between 1 and `len`.
+fn parse_choices(input: &str, len: usize) -> Option<Vec<usize>> {
+    let mut rv = parse_numbers(input, len)?;
+    rv.sort_unstable();
+    rv.dedup();
+    Some(rv)
+}
+
+/// Returns the indexes of the checked items.
+fn checked_indexes(states: &[CheckState]) -> Vec<usize> {
+    (0..states.len())
+        .filter(|&idx| states[idx] == CheckState::Checked)
+        .collect()
+}
+
+/// Checks that assumed indexes refer to items.
+fn check_assumed(indexes: &[usize], len: usize) -> io::Result<()> {
+    match indexes.iter().find(|&&idx| idx >= len) {
+        Some(idx) => Err(Error::InvalidDefault(format!("no item {} to assume", idx)).into()),
+        None => Ok(()),
+    }
+}
+
+/// Returns the states of `len` items of which the `selected` ones are
+/// checked.
+fn checked_states(selected: &[usize], len: usize) -> Vec<CheckState> {
+    (0..len).map(|idx| selected.contains(&idx).into()).collect()
+}
+
+/// Returns the first and last index of the items between the cursor and
+/// the mark.
+fn marked_block(sel: usize, mark: Option<usize>) -> (usize, usize) {
+    match mark {
+        Some(mark) => (sel.min(mark), sel.max(mark)),
+        None => (sel, sel),
+    }
+}
+
+/// Moves the items between the cursor and the mark so that the first of
+/// them ends up at `to`, as far as they fit.  The cursor and the mark
+/// move along.
+fn move_block(order: &mut [usize], sel: &mut usize, mark: &mut Option<usize>, to: usize) {
+    let (start, end) = marked_block(*sel, *mark);
+    let to = to.min(order.len() - (end - start + 1));
+    if to < start {
+        order[to..=end].rotate_left(start - to);
+    } else {
+        order[start..end + 1 + to - start].rotate_left(end - start + 1);
+    }
+    *sel = *sel - start + to;
+    *mark = mark.map(|mark| mark - start + to);
+}
+
+/// Parses an order of items such as `3,1-2`.  Items that are left out
+/// follow in their original order.
+fn parse_order(input: &str, len: usize) -> Option<Vec<usize>> {
+    let mut rv = vec![];
+    for idx in parse_numbers(input, len)?.into_iter().chain(0..len) {
+        if !rv.contains(&idx) {
+            rv.push(idx);
+        }
+    }
+    Some(rv)
+}
+
+/// Parses the item numbers and ranges of numbers in `input` in the
+/// order they are given.
+fn parse_numbers(input: &str, len: usize) -> Option<Vec<usize>> {
+    let mut rv = vec![];
+    for part in input.split([',', ' ']).filter(|part| !part.is_empty()) {
+        let num = |s: &str| match s.trim().parse::<usize>() {
+            Ok(num) if num >= 1 && num <= len => Some(num - 1),
+            _ => None,
+        };
+        let (start, end) = match part.find('-') {
+            Some(pos) => (num(&part[..pos])?, num(&part[pos + 1..])?),
+            None => (num(part)?, num(part)?),
+        };
+        if start > end {
+            return None;
+        }
+        rv.extend(start..=end);
+    }
+    Some(rv)
+}
+
+/// Returns `true` if a menu is asked as a question instead of being
+/// drawn.
+pub(crate) fn line_mode(term: &Term, accessible: bool) -> bool {
+    accessible || env::accessible() || session::is_dumb(term)
+}
+
+/// Returns the style of a menu item.
+pub(crate) fn menu_style(selected: bool) -> SelectionStyle {
+    if selected {
+        SelectionStyle::MenuSelected
+    } else {
+        SelectionStyle::MenuUnselected
+    }
+}
+
+/// Returns the number of columns `len` items of the given width are
+/// arranged in so that they fit into `width` columns of the terminal.
+/// The items are spread evenly across the columns.
+fn grid_columns(len: usize, cell_width: usize, width: usize) -> usize {
+    let columns = ((width + GRID_GAP) / (cell_width + GRID_GAP)).clamp(1, len.max(1));
+    let rows = len.div_ceil(columns);
+    len.div_ceil(rows.max(1)).max(1)
+}
+
+/// The number of spaces between the columns of a grid.
+pub(crate) const GRID_GAP: usize = 2;
+
+/// Returns the index of the first visible item so that at least `margin`
+/// items stay visible above and below the cursor.  Margins larger than
+/// half the capacity keep the cursor centered.
+pub(crate) fn scroll_top(
+    top: usize,
+    sel: usize,
+    len: usize,
+    capacity: usize,
+    margin: usize,
+) -> usize {
+    if capacity == 0 || len <= capacity {
+        return 0;
+    }
+    let margin = margin.min((capacity - 1) / 2);
+    let mut top = top;
+    if sel < top + margin {
+        top = sel.saturating_sub(margin);
+    }
+    if sel + margin >= top + capacity {
+        top = sel + margin + 1 - capacity;
+    }
+    top.min(len - capacity)
+}
+
+/// Moves the cursor to the next item, wrapping around at the bottom if
+/// `wrap` is set.
+pub(crate) fn next(sel: usize, len: usize, wrap: bool) -> usize {
+    if sel + 1 < len {
+        sel + 1
+    } else if wrap {
+        0
+    } else {
+        sel
+    }
+}
+
+/// Moves the cursor to the previous item, wrapping around at the top if
+/// `wrap` is set.
+pub(crate) fn prev(sel: usize, len: usize, wrap: bool) -> usize {
+    if sel > 0 {
+        sel - 1
+    } else if wrap {
+        len - 1
+    } else {
+        0
+    }
+}
+
+/// Moves the cursor one page up, wrapping around at the top if `wrap` is
+/// set.
+fn page_up(sel: usize, len: usize, capacity: usize, wrap: bool) -> usize {
+    if sel == 0 && wrap {
+        len - 1
+    } else {
+        sel.saturating_sub(capacity)
+    }
+}
+
+/// Moves the cursor one page down, wrapping around at the bottom if
+/// `wrap` is set.
+fn page_down(sel: usize, len: usize, capacity: usize, wrap: bool) -> usize {
+    if sel + 1 >= len && wrap {
+        0
+    } else {
+        (sel + capacity).min(len - 1)
+    }
+}
+
+/// Pads the cells of the given rows so that the columns line up.
+fn align_columns<'b, I: Iterator<Item = &'b Vec<String>> + Clone>(rows: I) -> Vec<String> {
+    let mut widths: Vec<usize> = vec![];
+    for row in rows.clone() {
+        for (idx, cell) in row.iter().enumerate() {
+            let width = measure_text_width(cell);
+            if idx >= widths.len() {
+                widths.push(width);
+            } else if width > widths[idx] {
+                widths[idx] = width;
             }
-            render.clear_preserve_prompt(&size_vec)?;
         }
     }
+    rows.map(|row| {
+        let mut line = String::new();
+        for (idx, cell) in row.iter().enumerate() {
+            if idx > 0 {
+                line.push_str("  ");
+            }
+            line.push_str(cell);
+            if idx + 1 < row.len() {
+                line.push_str(&" ".repeat(widths[idx] - measure_text_width(cell)));
+            }
+        }
+        line
+    })
+    .collect()
 }
 
 impl<'a> Default for Checkboxes<'a> {
@@ -252,15 +1239,124 @@ impl<'a> Checkboxes<'a> {
             defaults: vec![],
             clear: true,
             prompt: None,
+            help: None,
             theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
+            env_key: None,
+            default_without_tty: false,
             paged: false,
+            scroll_offset: !0,
+            alternate_screen: false,
+            hide_cursor: true,
+            wrap: true,
+            accessible: false,
+            truncate: false,
+            filterable: false,
+            filter_text: String::new(),
+            matcher: Box::new(FuzzyMatcher),
+            max_selections: None,
         }
     }
-    /// Enables or disables paging
+    /// Enables or disables paging.
+    ///
+    /// Lists taller than the terminal always scroll.  Paging also lets
+    /// left and right move the cursor by a page and shows the page
+    /// below the list.
     pub fn paged(&mut self, val: bool) -> &mut Checkboxes<'a> {
         self.paged = val;
         self
     }
+    /// Sets the number of items kept visible above and below the
+    /// cursor when paging.
+    ///
+    /// By default the cursor is kept centered.
+    pub fn scroll_offset(&mut self, val: usize) -> &mut Checkboxes<'a> {
+        self.scroll_offset = val;
+        self
+    }
+    /// Shows the menu on the alternate screen of the terminal.
+    ///
+    /// This keeps long menus out of the scrollback.  The answer is
+    /// reported on the normal screen.
+    pub fn alternate_screen(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.alternate_screen = val;
+        self
+    }
+
+    /// Hides the cursor while the menu is navigated.
+    ///
+    /// The cursor is shown again once the menu is answered.  This is
+    /// enabled by default.
+    pub fn hide_cursor(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.hide_cursor = val;
+        self
+    }
+
+    /// Enables or disables wrapping around at the first and last item.
+    ///
+    /// When disabled moving past the last item stays on it.  This is
+    /// enabled by default.
+    pub fn wrap(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Enables or disables the accessible mode.
+    ///
+    /// The menu is then not redrawn in place.  The items are printed
+    /// once with numbers and the answer is asked as a question, which
+    /// screen readers can follow.  Setting `DIALOGUER_ACCESSIBLE` enables
+    /// it for all menus.
+    pub fn accessible(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.accessible = val;
+        self
+    }
+
+    /// Cuts off items at the width of the terminal instead of wrapping
+    /// them, so that each item takes up a single line.
+    pub fn truncate(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.truncate = val;
+        self
+    }
+    /// Enables or disables filtering the items by typing.
+    ///
+    /// Typed characters narrow down the shown items, space still checks
+    /// an item.  Items stay checked while they are filtered out.
+    pub fn filterable(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.filterable = val;
+        self
+    }
+    /// Enables filtering and starts out with `text` typed into the
+    /// filter, eg: a likely prefix known by the program.
+    ///
+    /// The text can be edited or cleared like a typed filter.
+    pub fn with_filter_text(&mut self, text: &str) -> &mut Checkboxes<'a> {
+        self.filterable = true;
+        self.filter_text = text.into();
+        self
+    }
+    /// Sets how the filter is matched against the items.
+    ///
+    /// The default is a `FuzzyMatcher`.
+    pub fn matcher<M: Matcher + 'static>(&mut self, matcher: M) -> &mut Checkboxes<'a> {
+        self.matcher = Box::new(matcher);
+        self
+    }
+    /// Allows at most `n` items to be checked.
+    ///
+    /// The prompt shows how many are checked, eg: `2/3 chosen`, and once
+    /// the limit is reached the unchecked items are dimmed and cannot be
+    /// checked.
+    pub fn max_selections(&mut self, n: usize) -> &mut Checkboxes<'a> {
+        self.max_selections = Some(n);
+        self
+    }
     /// Sets the clear behavior of the checkbox menu.
     ///
     /// The default is to clear the checkbox menu.
@@ -272,10 +1368,9 @@ impl<'a> Checkboxes<'a> {
     /// Sets a defaults for the menu
     pub fn defaults(&mut self, val: &[bool]) -> &mut Checkboxes<'a> {
         self.defaults = val
-            .to_vec()
             .iter()
-            .cloned()
-            .chain(repeat(false))
+            .map(|&checked| CheckState::from(checked))
+            .chain(repeat(CheckState::Unchecked))
             .take(self.items.len())
             .collect();
         self
@@ -288,8 +1383,16 @@ impl<'a> Checkboxes<'a> {
 
     /// Add a single item to the selector with a default checked state.
     pub fn item_checked(&mut self, item: &str, checked: bool) -> &mut Checkboxes<'a> {
+        self.item_state(item, checked.into())
+    }
+
+    /// Add a single item to the selector with a default state.
+    ///
+    /// A partial item is shown as partly checked, eg: for a group whose
+    /// children are only partly selected.  Pressing space checks it.
+    pub fn item_state(&mut self, item: &str, state: CheckState) -> &mut Checkboxes<'a> {
         self.items.push(item.to_string());
-        self.defaults.push(checked);
+        self.defaults.push(state);
         self
     }
 
@@ -297,7 +1400,7 @@ impl<'a> Checkboxes<'a> {
     pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Checkboxes<'a> {
         for item in items {
             self.items.push(item.to_string());
-            self.defaults.push(false);
+            self.defaults.push(CheckState::Unchecked);
         }
         self
     }
@@ -306,7 +1409,7 @@ impl<'a> Checkboxes<'a> {
     pub fn items_checked<T: ToString>(&mut self, items: &[(T, bool)]) -> &mut Checkboxes<'a> {
         for &(ref item, checked) in items {
             self.items.push(item.to_string());
-            self.defaults.push(checked);
+            self.defaults.push(checked.into());
         }
         self
     }
@@ -320,141 +1423,459 @@ impl<'a> Checkboxes<'a> {
         self
     }
 
-    /// Enables user interaction and returns the result.
+    /// Shows a help line below the menu, eg: which keys to press.
+    ///
+    /// The line is cleared along with the menu.
+    pub fn with_help(&mut self, text: &str) -> &mut Checkboxes<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The callback is only invoked if a prompt is set.  The checked
+    /// items are joined by commas.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut Checkboxes<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: &[usize]) -> &mut Checkboxes<'a> {
+        self.assumed = Some(value.to_vec());
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume_default(&mut self) -> &mut Checkboxes<'a> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Takes the answer from an environment variable if it is set.
+    ///
+    /// The variable has to hold the texts of the checked items separated
+    /// by commas.  The menu is not shown then but the answer is still
+    /// reported.
+    pub fn env_key(&mut self, key: &str) -> &mut Checkboxes<'a> {
+        self.env_key = Some(key.into());
+        self
+    }
+
+    /// Answers with the default without asking if stdin is not a
+    /// terminal, eg: when run in CI.
+    ///
+    /// The answer is still reported, the items checked by default
+    /// being returned.
+    pub fn accept_default_without_tty(&mut self, val: bool) -> &mut Checkboxes<'a> {
+        self.default_without_tty = val;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Checkboxes<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut Checkboxes<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut Checkboxes<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut Checkboxes<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut Checkboxes<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The user can select the items with the space bar and on enter
+    /// the selected items will be returned.
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but returns the state of every item.
+    ///
+    /// Partial items that were not toggled are returned as partial.
+    pub fn interact_states(&self) -> Result<Vec<CheckState>> {
+        self.interact_states_on(&self.term)
+    }
+
+    /// Like `interact` but returns the checked items along with their
+    /// indexes.
+    pub fn interact_items(&self) -> Result<Vec<(usize, String)>> {
+        self.interact_items_on(&self.term)
+    }
+
+    /// Like `interact_items` but allows a specific terminal to be set.
+    pub fn interact_items_on(&self, term: &Term) -> Result<Vec<(usize, String)>> {
+        Ok(self
+            .interact_on(term)?
+            .into_iter()
+            .map(|idx| (idx, self.item_text(idx).to_string()))
+            .collect())
+    }
+
+    /// Returns the text reported for an item.
+    pub(crate) fn item_text(&self, idx: usize) -> &str {
+        &self.items[idx]
+    }
+
+    /// Reports the prompt along with the checked items.
+    fn report_checked(&self, render: &mut TermThemeRenderer, checked: &[usize]) -> io::Result<()> {
+        if let Some(ref prompt) = self.prompt {
+            let selections: Vec<_> = checked
+                .iter()
+                .map(|&idx| self.items[idx].as_str())
+                .collect();
+            render.checked_prompt_selection(prompt, &selections[..], self.items.len())?;
+        }
+        Ok(())
+    }
+
+    /// Asks for the numbers of the checked items on terminals that
+    /// cannot move the cursor.
+    fn interact_lines(
+        &self,
+        render: &mut TermThemeRenderer,
+        allow_quit: bool,
+    ) -> io::Result<Option<Vec<CheckState>>> {
+        if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
+            render.prompt(prompt)?;
+        }
+        for (idx, item) in self.items.iter().enumerate() {
+            let mark = match self.defaults[idx] {
+                CheckState::Checked => 'x',
+                CheckState::Partial => '-',
+                CheckState::Unchecked => ' ',
+            };
+            render.numbered_line(&format!("[{}] {}", mark, item), idx + 1)?;
+        }
+        loop {
+            let prompt = localization::with(|l| {
+                Localization::fill(&l.enter_choices, &[("total", &self.items.len())])
+            });
+            render.input_prompt(&prompt, None)?;
+            let input = session::read_line(render.term(), None)?;
+            render.add_input(&input);
+            let states = if input.trim().is_empty() {
+                self.defaults.clone()
+            } else if allow_quit && input.trim() == "q" {
+                if let Some(ref prompt) = self.prompt {
+                    render.cancelled(prompt)?;
+                }
+                return Ok(None);
+            } else {
+                let max = self.max_selections.unwrap_or(usize::MAX);
+                match parse_choices(&input, self.items.len()) {
+                    Some(selected) if selected.len() > max => {
+                        render.error(&self.too_many())?;
+                        continue;
+                    }
+                    Some(selected) => checked_states(&selected, self.items.len()),
+                    None => {
+                        render.error(&localization::with(|l| l.invalid_choices.to_string()))?;
+                        continue;
+                    }
+                }
+            };
+            self.report_checked(render, &checked_indexes(&states))?;
+            return Ok(Some(states));
+        }
+    }
+
+    /// Returns the indexes of the items matching a filter, those with
+    /// the highest scores first.
+    fn filtered(&self, filter: &str) -> Vec<usize> {
+        let mut scored: Vec<_> = (0..self.items.len())
+            .filter_map(|idx| Some((self.matcher.find(filter, &self.items[idx])?.0, idx)))
+            .collect();
+        scored.sort_by_key(|&(score, _)| Reverse(score));
+        scored.into_iter().map(|(_, idx)| idx).collect()
+    }
+
+    /// The error for more checked items than `max_selections` allows.
+    fn too_many(&self) -> String {
+        let max = self.max_selections.unwrap_or_default();
+        localization::with(|l| Localization::fill(&l.too_many, &[("max", &max)]))
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<Vec<usize>> {
+        Ok(checked_indexes(&self.interact_states_on(term)?))
+    }
+
+    /// Like `interact` but lets the user back out with Esc, or by
+    /// entering `q` when the items are listed line by line.
+    ///
+    /// Returns `None` if the user backed out, in which case the prompt is
+    /// reported as cancelled.
+    pub fn interact_opt(&self) -> Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&self.term)
+    }
+
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<Vec<usize>>> {
+        Ok(self
+            .interact_filtered(term, true)?
+            .map(|(states, _)| checked_indexes(&states)))
+    }
+
+    /// Like `interact_states` but allows a specific terminal to be set.
+    pub fn interact_states_on(&self, term: &Term) -> Result<Vec<CheckState>> {
+        Ok(self.interact_confirmed(term)?.0)
+    }
+
+    /// Like `interact` but returns the checked items along with their
+    /// match scores for the filter active when the prompt was confirmed.
     ///
-    /// The user can select the items with the space bar and on enter
-    /// the selected items will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    /// The score is `None` without a filter or for a checked item the
+    /// filter no longer matches.
+    pub fn interact_scored(&self) -> Result<Vec<(usize, Option<i64>)>> {
+        self.interact_scored_on(&self.term)
     }
 
-    /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        let mut page = 0;
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
-            self.items.len()
-        };
-        let pages = (self.items.len() / capacity) + 1;
+    /// Like `interact_scored` but allows a specific terminal to be set.
+    pub fn interact_scored_on(&self, term: &Term) -> Result<Vec<(usize, Option<i64>)>> {
+        let (states, filter) = self.interact_confirmed(term)?;
+        Ok(checked_indexes(&states)
+            .into_iter()
+            .map(|idx| {
+                let found = self.matcher.find(&filter, &self.items[idx]);
+                (
+                    idx,
+                    found.filter(|_| !filter.is_empty()).map(|(score, _)| score),
+                )
+            })
+            .collect())
+    }
+
+    /// Runs the prompt without letting the user back out.
+    fn interact_confirmed(&self, term: &Term) -> Result<(Vec<CheckState>, String)> {
+        Ok(self
+            .interact_filtered(term, false)?
+            .ok_or_else(|| io::Error::other("Quit not allowed in this case"))?)
+    }
+
+    /// Runs the prompt and returns the state of every item along with
+    /// the final filter, `None` if the user backed out.
+    fn interact_filtered(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+    ) -> Result<Option<(Vec<CheckState>, String)>> {
+        let mut top = 0;
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+        render.set_truncate(self.truncate);
+        if let Some((key, value)) = env::answer(self.env_key.as_deref()) {
+            let mut selected = vec![];
+            for name in value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                match self.items.iter().position(|item| item == name) {
+                    Some(idx) if !selected.contains(&idx) => selected.push(idx),
+                    Some(_) => {}
+                    None => {
+                        return Err(env::invalid(key, &format!("no item named {:?}", name)).into())
+                    }
+                }
+            }
+            selected.sort_unstable();
+            if self.max_selections.is_some_and(|max| selected.len() > max) {
+                return Err(env::invalid(key, &self.too_many()).into());
+            }
+            self.report_checked(&mut render, &selected)?;
+            return Ok(Some((
+                checked_states(&selected, self.items.len()),
+                String::new(),
+            )));
+        }
+        let no_tty = self.default_without_tty && !session::stdin_is_term();
+        let assumed = match self.assumed {
+            Some(ref selected) => {
+                check_assumed(selected, self.items.len())?;
+                Some(checked_states(selected, self.items.len()))
+            }
+            None if self.assume_default || no_tty => Some(self.defaults.clone()),
+            None => None,
+        };
+        if let Some(states) = assumed {
+            let checked = checked_indexes(&states);
+            if self.max_selections.is_some_and(|max| checked.len() > max) {
+                return Err(Error::InvalidDefault(self.too_many()));
+            }
+            self.report_checked(&mut render, &checked)?;
+            return Ok(Some((states, String::new())));
+        }
+        if line_mode(term, self.accessible) {
+            let states = self.interact_lines(&mut render, allow_quit)?;
+            return Ok(states.map(|states| (states, String::new())));
+        }
+        if self.alternate_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.hide_cursor {
+            render.hide_cursor()?;
+        }
         let mut sel = 0;
         if let Some(ref prompt) = self.prompt {
-            render.prompt(prompt)?;
-        }
-        let mut size_vec = Vec::new();
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(size.clone());
+            session::question(prompt)?;
+            if self.max_selections.is_none() {
+                render.prompt(prompt)?;
+            }
         }
-        let mut checked: Vec<bool> = self.defaults.clone();
+        let mut checked = self.defaults.clone();
+        let mut filter = self.filter_text.clone();
+        let mut visible = self.filtered(&filter);
         loop {
-            for (idx, item) in self
-                .items
+            let count = checked
                 .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.selection(
-                    item,
-                    match (checked[idx], sel == idx) {
-                        (true, true) => SelectionStyle::CheckboxCheckedSelected,
-                        (true, false) => SelectionStyle::CheckboxCheckedUnselected,
-                        (false, true) => SelectionStyle::CheckboxUncheckedSelected,
-                        (false, false) => SelectionStyle::CheckboxUncheckedUnselected,
+                .filter(|&&state| state == CheckState::Checked)
+                .count();
+            let full = self.max_selections.is_some_and(|max| count >= max);
+            let mut reserved =
+                self.filterable as usize + self.paged as usize + self.help.is_some() as usize;
+            if let (Some(prompt), Some(max)) = (self.prompt.as_ref(), self.max_selections) {
+                render.limited_prompt(prompt, count, max)?;
+                reserved += 1;
+            }
+            let capacity = viewport(&render, self.items.len(), reserved);
+            if self.filterable {
+                render.filter(&filter)?;
+            }
+            top = scroll_top(top, sel, visible.len(), capacity, self.scroll_offset);
+            for (pos, &idx) in visible.iter().enumerate().skip(top).take(capacity) {
+                let found = self.matcher.find(&filter, &self.items[idx]);
+                let score = found
+                    .as_ref()
+                    .filter(|_| !filter.is_empty())
+                    .map(|&(score, _)| score);
+                let matches = found.map(|(_, matches)| matches).unwrap_or_default();
+                render.filtered_selection(
+                    &self.items[idx],
+                    &matches,
+                    checked[idx].style(sel == pos),
+                    ListItem {
+                        index: pos,
+                        count: visible.len(),
+                        disabled: full && checked[idx] != CheckState::Checked,
+                        score,
                     },
                 )?;
             }
-            match term.read_key()? {
+            if self.paged {
+                let capacity = capacity.max(1);
+                render.list_footer(
+                    sel / capacity + 1,
+                    visible.len().div_ceil(capacity).max(1),
+                    Some(checked_indexes(&checked).len()),
+                    self.items.len(),
+                )?;
+            }
+            if let Some(ref help) = self.help {
+                render.help(help)?;
+            }
+            let key = match session::read_key_input(term, None)? {
+                KeyInput::Pressed(key) => key,
+                _ => {
+                    render.clear_preserve_prompt()?;
+                    continue;
+                }
+            };
+            match key {
+                Key::Char(c) if self.filterable && c != ' ' && !c.is_control() => {
+                    filter.push(c);
+                    visible = self.filtered(&filter);
+                    sel = 0;
+                }
+                Key::Backspace if self.filterable => {
+                    filter.pop();
+                    visible = self.filtered(&filter);
+                    sel = 0;
+                }
+                Key::Escape if self.filterable && !filter.is_empty() => {
+                    filter.clear();
+                    visible = self.filtered(&filter);
+                    sel = 0;
+                }
+                Key::ArrowDown | Key::ArrowUp | Key::ArrowLeft | Key::ArrowRight | Key::Char(_)
+                    if visible.is_empty() => {}
                 Key::ArrowDown | Key::Char('j') => {
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
-                    }
+                    sel = next(sel, visible.len(), self.wrap);
                 }
                 Key::ArrowUp | Key::Char('k') => {
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
-                    }
+                    sel = prev(sel, visible.len(), self.wrap);
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-                        sel = page * capacity;
-                    }
+                Key::ArrowLeft | Key::Char('h') if self.paged => {
+                    sel = page_up(sel, visible.len(), capacity, self.wrap);
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
-                        sel = page * capacity;
-                    }
+                Key::ArrowRight | Key::Char('l') if self.paged => {
+                    sel = page_down(sel, visible.len(), capacity, self.wrap);
                 }
+                Key::Char(' ') if full && checked[visible[sel]] != CheckState::Checked => {}
                 Key::Char(' ') => {
-                    checked[sel] = !checked[sel];
+                    checked[visible[sel]] = checked[visible[sel]].toggled();
                 }
-                Key::Escape => {
+                Key::Escape if allow_quit => {
                     if self.clear {
                         render.clear()?;
                     }
                     if let Some(ref prompt) = self.prompt {
-                        render.multi_prompt_selection(prompt, &[][..])?;
+                        render.cancelled(prompt)?;
                     }
-                    return Ok(self
-                        .defaults
-                        .clone()
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                    return Ok(None);
                 }
-                Key::Enter => {
+                Key::Escape => {
                     if self.clear {
                         render.clear()?;
                     }
-                    if let Some(ref prompt) = self.prompt {
-                        let selections: Vec<_> = checked
-                            .iter()
-                            .enumerate()
-                            .filter_map(|(idx, &checked)| {
-                                if checked {
-                                    Some(self.items[idx].as_str())
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
-                        render.multi_prompt_selection(prompt, &selections[..])?;
+                    self.report_checked(&mut render, &checked_indexes(&self.defaults))?;
+                    return Ok(Some((self.defaults.clone(), String::new())));
+                }
+                Key::Enter => {
+                    if self.clear {
+                        render.clear()?;
                     }
-                    return Ok(checked
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                    self.report_checked(&mut render, &checked_indexes(&checked))?;
+                    return Ok(Some((checked, filter)));
                 }
                 _ => {}
             }
-            if sel < page * capacity || sel >= (page + 1) * capacity {
-                page = sel / capacity;
-            }
-            render.clear_preserve_prompt(&size_vec)?;
+            render.clear_preserve_prompt()?;
         }
     }
 }
@@ -477,15 +1898,92 @@ impl<'a> OrderList<'a> {
             items: vec![],
             clear: true,
             prompt: None,
+            help: None,
             theme,
+            term: Term::stderr(),
+            max_width: None,
+            localization: None,
+            report: true,
+            on_complete: None,
+            assumed: None,
+            assume_default: false,
             paged: false,
+            scroll_offset: !0,
+            alternate_screen: false,
+            hide_cursor: true,
+            wrap: true,
+            positions: false,
+            accessible: false,
+            truncate: false,
         }
     }
-    /// Enables or disables paging
+    /// Enables or disables paging.
+    ///
+    /// Lists taller than the terminal always scroll.  Paging also lets
+    /// left and right move the cursor by a page.
     pub fn paged(&mut self, val: bool) -> &mut OrderList<'a> {
         self.paged = val;
         self
     }
+    /// Sets the number of items kept visible above and below the
+    /// cursor when paging.
+    ///
+    /// By default the cursor is kept centered.
+    pub fn scroll_offset(&mut self, val: usize) -> &mut OrderList<'a> {
+        self.scroll_offset = val;
+        self
+    }
+    /// Shows the menu on the alternate screen of the terminal.
+    ///
+    /// This keeps long menus out of the scrollback.  The answer is
+    /// reported on the normal screen.
+    pub fn alternate_screen(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.alternate_screen = val;
+        self
+    }
+
+    /// Hides the cursor while the menu is navigated.
+    ///
+    /// The cursor is shown again once the menu is answered.  This is
+    /// enabled by default.
+    pub fn hide_cursor(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.hide_cursor = val;
+        self
+    }
+
+    /// Enables or disables wrapping around at the first and last item.
+    ///
+    /// When disabled moving past the last item stays on it.  This is
+    /// enabled by default.
+    pub fn wrap(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Shows the current position of each item in front of it, starting
+    /// at 1.
+    pub fn positions(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.positions = val;
+        self
+    }
+
+    /// Enables or disables the accessible mode.
+    ///
+    /// The menu is then not redrawn in place.  The items are printed
+    /// once with numbers and the answer is asked as a question, which
+    /// screen readers can follow.  Setting `DIALOGUER_ACCESSIBLE` enables
+    /// it for all menus.
+    pub fn accessible(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.accessible = val;
+        self
+    }
+
+    /// Cuts off items at the width of the terminal instead of wrapping
+    /// them, so that each item takes up a single line.
+    pub fn truncate(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.truncate = val;
+        self
+    }
     /// Sets the clear behavior of the checkbox menu.
     ///
     /// The default is to clear the checkbox menu.
@@ -517,58 +2015,216 @@ impl<'a> OrderList<'a> {
         self
     }
 
+    /// Shows a help line below the menu, eg: which keys to press.
+    ///
+    /// The line is cleared along with the menu.
+    pub fn with_help(&mut self, text: &str) -> &mut OrderList<'a> {
+        self.help = Some(text.into());
+        self
+    }
+
+    /// Indicates whether to report the answer after interaction.
+    pub fn report(&mut self, val: bool) -> &mut OrderList<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Sets a callback invoked with the prompt and the answer.
+    ///
+    /// The callback is only invoked if a prompt is set.  The ordered
+    /// items are joined by commas.
+    pub fn on_complete<F: Fn(&str, &str) + 'static>(&mut self, f: F) -> &mut OrderList<'a> {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// Answers with `value` without asking, eg: for a `--yes` flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.
+    pub fn assume(&mut self, value: &[usize]) -> &mut OrderList<'a> {
+        self.assumed = Some(value.to_vec());
+        self
+    }
+
+    /// Answers with the default without asking, eg: for a `--defaults`
+    /// flag.
+    ///
+    /// The prompt is not shown but the answer is still reported.  The
+    /// default is the order the items were added in.
+    pub fn assume_default(&mut self) -> &mut OrderList<'a> {
+        self.assume_default = true;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut OrderList<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Confines the prompt to `cols` columns.
+    pub fn max_width(&mut self, cols: usize) -> &mut OrderList<'a> {
+        self.max_width = Some(cols);
+        self
+    }
+
+    /// Translates the words built into the prompt and the theme.
+    pub fn localization(&mut self, val: &'a Localization) -> &mut OrderList<'a> {
+        self.localization = Some(val);
+        self
+    }
+
+    /// Renders the prompt on stdout.
+    pub fn stdout(&mut self) -> &mut OrderList<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the prompt on stderr.
+    pub fn stderr(&mut self) -> &mut OrderList<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can order the items with the space bar and the arrows.
-    /// On enter the ordered list will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    /// Pressing `v` marks the item under the cursor so that a block of
+    /// items up to the cursor is moved together.  On enter the ordered
+    /// list will be returned.
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        self.interact_on(&self.term)
+    }
+
+    /// Asks for the order of the items on terminals that cannot move the
+    /// cursor.
+    fn interact_lines(&self, render: &mut TermThemeRenderer) -> io::Result<Vec<usize>> {
+        if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
+            render.prompt(prompt)?;
+        }
+        for (idx, item) in self.items.iter().enumerate() {
+            render.numbered_line(item, idx + 1)?;
+        }
+        loop {
+            let prompt = localization::with(|l| {
+                Localization::fill(&l.enter_order, &[("total", &self.items.len())])
+            });
+            render.input_prompt(&prompt, None)?;
+            let input = session::read_line(render.term(), None)?;
+            render.add_input(&input);
+            let order = match parse_order(&input, self.items.len()) {
+                Some(order) => order,
+                None => {
+                    render.error(&localization::with(|l| l.invalid_order.to_string()))?;
+                    continue;
+                }
+            };
+            if let Some(ref prompt) = self.prompt {
+                let list: Vec<_> = order.iter().map(|&idx| self.items[idx].as_str()).collect();
+                render.multi_prompt_selection(prompt, &list[..])?;
+            }
+            return Ok(order);
+        }
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        let mut page = 0;
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
-            self.items.len()
-        };
-        let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
+    pub fn interact_on(&self, term: &Term) -> Result<Vec<usize>> {
+        let mut top = 0;
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_max_width(self.max_width);
+        render.set_localization(self.localization);
+        render.set_report(self.report);
+        render.set_on_complete(self.on_complete.as_deref());
+        render.set_truncate(self.truncate);
+        let assumed = match self.assumed {
+            Some(ref order) => {
+                check_assumed(order, self.items.len())?;
+                Some(order.clone())
+            }
+            None if self.assume_default => Some((0..self.items.len()).collect()),
+            None => None,
+        };
+        if let Some(order) = assumed {
+            if let Some(ref prompt) = self.prompt {
+                let list: Vec<_> = order.iter().map(|&idx| self.items[idx].as_str()).collect();
+                render.multi_prompt_selection(prompt, &list[..])?;
+            }
+            return Ok(order);
+        }
+        if line_mode(term, self.accessible) {
+            return Ok(self.interact_lines(&mut render)?);
+        }
+        if self.alternate_screen {
+            render.enter_alternate_screen()?;
+        }
+        if self.hide_cursor {
+            render.hide_cursor()?;
+        }
         let mut sel = 0;
         if let Some(ref prompt) = self.prompt {
+            session::question(prompt)?;
             render.prompt(prompt)?;
         }
-        let mut size_vec = Vec::new();
-        for items in self.items.iter().as_slice() {
-            let size = &items.len();
-            size_vec.push(size.clone());
-        }
         let mut order: Vec<_> = (0..self.items.len()).collect();
         let mut checked: bool = false;
+        let mut mark = None;
         loop {
-            for (idx, item) in order
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.selection(
-                    &self.items[*item],
-                    match (sel == idx, checked) {
-                        (true, true) => SelectionStyle::CheckboxCheckedSelected,
-                        (true, false) => SelectionStyle::CheckboxUncheckedSelected,
-                        (false, _) => SelectionStyle::CheckboxUncheckedUnselected,
-                    },
-                )?;
+            let (start, end) = marked_block(sel, mark);
+            let capacity = viewport(&render, self.items.len(), self.help.is_some() as usize);
+            top = scroll_top(top, sel, self.items.len(), capacity, self.scroll_offset);
+            for (idx, item) in order.iter().enumerate().skip(top).take(capacity) {
+                let style = match (start <= idx && idx <= end, checked) {
+                    (true, true) => SelectionStyle::CheckboxCheckedSelected,
+                    (true, false) => SelectionStyle::CheckboxUncheckedSelected,
+                    (false, _) => SelectionStyle::CheckboxUncheckedUnselected,
+                };
+                let pos = ListItem {
+                    index: idx,
+                    count: order.len(),
+                    disabled: false,
+                    score: None,
+                };
+                if self.positions {
+                    render.positioned_selection(&self.items[*item], style, pos)?;
+                } else {
+                    render.selection(&self.items[*item], style, pos)?;
+                }
             }
-            match term.read_key()? {
+            if let Some(ref help) = self.help {
+                render.help(help)?;
+            }
+            let key = match session::read_key_input(term, None)? {
+                KeyInput::Pressed(key) => key,
+                _ => {
+                    render.clear_preserve_prompt()?;
+                    continue;
+                }
+            };
+            match key {
+                Key::ArrowDown | Key::Char('j') if checked && mark.is_some() => {
+                    move_block(&mut order, &mut sel, &mut mark, start + 1);
+                }
+                Key::ArrowUp | Key::Char('k') if checked && mark.is_some() => {
+                    move_block(&mut order, &mut sel, &mut mark, start.saturating_sub(1));
+                }
+                Key::ArrowLeft | Key::Char('h') if self.paged && checked && mark.is_some() => {
+                    move_block(
+                        &mut order,
+                        &mut sel,
+                        &mut mark,
+                        start.saturating_sub(capacity),
+                    );
+                }
+                Key::ArrowRight | Key::Char('l') if self.paged && checked && mark.is_some() => {
+                    move_block(&mut order, &mut sel, &mut mark, start + capacity);
+                }
                 Key::ArrowDown | Key::Char('j') => {
                     let old_sel = sel;
                     if sel == !0 {
                         sel = 0;
                     } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+                        sel = next(sel, self.items.len(), self.wrap);
                     }
                     if checked && old_sel != sel {
                         order.swap(old_sel, sel);
@@ -579,63 +2235,40 @@ impl<'a> OrderList<'a> {
                     if sel == !0 {
                         sel = self.items.len() - 1;
                     } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
+                        sel = prev(sel, self.items.len(), self.wrap);
                     }
                     if checked && old_sel != sel {
                         order.swap(old_sel, sel);
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-                        sel = page * capacity;
-                        if checked {
-                            let indexes: Vec<_> = if old_page == 0 {
-                                let indexes1: Vec<_> = (0..=old_sel).rev().collect();
-                                let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (sel..=old_sel).rev().collect()
-                            };
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
-                        }
+                Key::ArrowLeft | Key::Char('h') if self.paged => {
+                    let old_sel = sel;
+                    sel = page_up(sel, self.items.len(), capacity, self.wrap);
+                    if checked {
+                        let item = order.remove(old_sel);
+                        order.insert(sel, item);
                     }
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
-                        sel = page * capacity;
-                        if checked {
-                            let indexes: Vec<_> = if old_page == pages - 1 {
-                                let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
-                                let indexes2: Vec<_> = vec![0];
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (old_sel..=sel).collect()
-                            };
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
-                        }
+                Key::ArrowRight | Key::Char('l') if self.paged => {
+                    let old_sel = sel;
+                    sel = page_down(sel, self.items.len(), capacity, self.wrap);
+                    if checked {
+                        let item = order.remove(old_sel);
+                        order.insert(sel, item);
                     }
                 }
+                Key::Char('v') if !checked => {
+                    mark = if mark.is_some() { None } else { Some(sel) };
+                }
                 Key::Char(' ') => {
                     checked = !checked;
+                    if !checked {
+                        mark = None;
+                    }
+                }
+                Key::Escape if mark.is_some() => {
+                    checked = false;
+                    mark = None;
                 }
                 Key::Enter => {
                     if self.clear {
@@ -644,8 +2277,7 @@ impl<'a> OrderList<'a> {
                     if let Some(ref prompt) = self.prompt {
                         let list: Vec<_> = order
                             .iter()
-                            .enumerate()
-                            .map(|(_, item)| self.items[*item].as_str())
+                            .map(|item| self.items[*item].as_str())
                             .collect();
                         render.multi_prompt_selection(prompt, &list[..])?;
                     }
@@ -653,10 +2285,7 @@ impl<'a> OrderList<'a> {
                 }
                 _ => {}
             }
-            if sel < page * capacity || sel >= (page + 1) * capacity {
-                page = sel / capacity;
-            }
-            render.clear_preserve_prompt(&size_vec)?;
+            render.clear_preserve_prompt()?;
         }
     }
 }
@@ -664,6 +2293,9 @@ impl<'a> OrderList<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(unix)]
+    use testing::Capture;
+    use testing::Script;
 
     #[test]
     fn test_str() {
@@ -702,4 +2334,539 @@ mod tests {
             selections
         );
     }
+
+    #[test]
+    fn test_rows() {
+        let mut select = Select::new();
+        select
+            .with_header(&["Name", "Version"])
+            .rows(&[vec!["serde", "1.0.104"], vec!["tempfile", "3.1"]])
+            .item("other");
+
+        assert_eq!(select.items, vec!["serde 1.0.104", "tempfile 3.1", "other"]);
+        assert_eq!(
+            select.aligned_rows(),
+            (
+                Some("Name      Version".to_string()),
+                vec![
+                    "serde     1.0.104".to_string(),
+                    "tempfile  3.1".to_string(),
+                    "other".to_string()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_env_key_rows() {
+        let term = Term::buffered_stderr();
+        std::env::set_var("DIALOGUER_TEST_ENV_ROWS", "tempfile 3.1");
+        let sel = Select::new()
+            .with_header(&["Name", "Version"])
+            .rows(&[vec!["serde", "1.0.104"], vec!["tempfile", "3.1"]])
+            .env_key("DIALOGUER_TEST_ENV_ROWS")
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 1);
+    }
+
+    #[test]
+    fn test_default_without_tty() {
+        let term = Term::buffered_stderr();
+        let session = Script::new().without_tty().start().unwrap();
+        let sel = Select::new()
+            .with_prompt("Region")
+            .items(&["eu", "us"])
+            .default(1)
+            .accept_default_without_tty(true)
+            .interact_on(&term)
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Features")
+            .items_checked(&[("a", true), ("b", false), ("c", true)])
+            .accept_default_without_tty(true)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(sel, 1);
+        assert_eq!(checked, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_idle_timeout() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Region")
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .idle()
+            .press(Key::Enter)
+            .expect_prompt("Region")
+            .press(Key::ArrowDown)
+            .idle()
+            .start()
+            .unwrap();
+        let mut select = Select::new();
+        select
+            .with_prompt("Region")
+            .items(&["eu", "us", "asia"])
+            .default(1);
+        let restarted = select
+            .idle_timeout(Duration::from_millis(20), IdleAction::Restart)
+            .interact_on(&term)
+            .unwrap();
+        let picked = select
+            .idle_timeout(Duration::from_millis(20), IdleAction::SelectDefault)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(restarted, 1);
+        assert_eq!(picked, 1);
+    }
+
+    #[test]
+    fn test_assume() {
+        let term = Term::buffered_stderr();
+        let session = Script::new().start().unwrap();
+        let sel = Select::new()
+            .with_prompt("Region")
+            .items(&["eu", "us"])
+            .assume(1)
+            .interact_on(&term)
+            .unwrap();
+        let err = Select::new()
+            .with_prompt("Region")
+            .items(&["eu", "us"])
+            .assume_default()
+            .interact_on(&term)
+            .unwrap_err();
+        let checked = Checkboxes::new()
+            .with_prompt("Features")
+            .items_checked(&[("a", true), ("b", false), ("c", true)])
+            .assume(&[1])
+            .interact_on(&term)
+            .unwrap();
+        let order = OrderList::new()
+            .with_prompt("Order")
+            .items(&["a", "b", "c"])
+            .assume_default()
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(sel, 1);
+        assert!(matches!(err, Error::InvalidDefault(_)));
+        assert_eq!(checked, vec![1]);
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_no_items() {
+        let term = Term::buffered_stderr();
+        let session = Script::new().start().unwrap();
+        let err = Select::new()
+            .with_prompt("Region")
+            .grid(true)
+            .interact_on(&term)
+            .unwrap_err();
+        let lazy = Select::new()
+            .with_prompt("Region")
+            .lazy_items(Vec::<String>::new(), None)
+            .interact_on(&term)
+            .unwrap_err();
+        session.finish().unwrap();
+        assert!(matches!(err, Error::NoItems));
+        assert!(matches!(lazy, Error::NoItems));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_viewport() {
+        let (capture, term) = Capture::new();
+        let items: Vec<_> = (0..40).map(|idx| format!("item {}", idx)).collect();
+        let session = Script::new()
+            .expect_prompt("Pick")
+            .press(Key::ArrowUp)
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let sel = Select::new()
+            .with_prompt("Pick")
+            .items(&items)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(sel, 39);
+        // 22 items fit below the prompt on the 24 rows of the terminal
+        let output = capture.output();
+        assert!(output.contains("  item 21\n\x1b[22A"));
+        assert!(output.contains("\x1b[22A  item 18\n"));
+        assert!(output.contains("> item 39\n\x1b[23A"));
+        assert!(output.ends_with("\x1b[23APick: item 39\n"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_wrapped_item_cleared() {
+        let (capture, term) = Capture::new();
+        let long = "lorem ipsum ".repeat(10);
+        let session = Script::new()
+            .expect_prompt("Pick")
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        Select::new()
+            .with_prompt("Pick")
+            .items(&[long.as_str(), "short"])
+            .default(1)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        // the long item is wrapped over two rows of 80 columns
+        let output = capture.output();
+        assert!(output.contains(" lorem\nipsum lorem"));
+        assert!(output.ends_with("\x1b[4APick: short\n"));
+    }
+
+    #[test]
+    fn test_assume_default_lazy() {
+        let term = Term::buffered_stderr();
+        let sel = Select::new()
+            .with_prompt("Number")
+            .lazy_items(0..100, None)
+            .default(50)
+            .assume_default()
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(sel, 50);
+    }
+
+    #[test]
+    fn test_scroll_top() {
+        assert_eq!(scroll_top(0, 0, 10, 5, !0), 0);
+        assert_eq!(scroll_top(0, 4, 10, 5, !0), 2);
+        assert_eq!(scroll_top(2, 9, 10, 5, !0), 5);
+        assert_eq!(scroll_top(0, 3, 10, 5, 1), 0);
+        assert_eq!(scroll_top(0, 4, 10, 5, 1), 1);
+        assert_eq!(scroll_top(5, 5, 10, 5, 1), 4);
+        assert_eq!(scroll_top(0, 3, 4, 5, !0), 0);
+    }
+
+    #[test]
+    fn test_page_keys() {
+        assert_eq!(page_down(0, 10, 4, true), 4);
+        assert_eq!(page_down(8, 10, 4, true), 9);
+        assert_eq!(page_down(9, 10, 4, true), 0);
+        assert_eq!(page_down(9, 10, 4, false), 9);
+        assert_eq!(page_up(5, 10, 4, true), 1);
+        assert_eq!(page_up(2, 10, 4, true), 0);
+        assert_eq!(page_up(0, 10, 4, true), 9);
+        assert_eq!(page_up(0, 10, 4, false), 0);
+        assert_eq!(next(9, 10, true), 0);
+        assert_eq!(next(9, 10, false), 9);
+        assert_eq!(prev(0, 10, true), 9);
+        assert_eq!(prev(0, 10, false), 0);
+    }
+
+    #[test]
+    fn test_grid_columns() {
+        // 10 items of width 4 fit three times into 16 columns
+        assert_eq!(grid_columns(10, 4, 16), 3);
+        // 3 rows are needed for 14 items in 6 columns, so 5 are enough
+        assert_eq!(grid_columns(14, 4, 34), 5);
+        assert_eq!(grid_columns(10, 4, 80), 10);
+        assert_eq!(grid_columns(10, 40, 20), 1);
+        assert_eq!(grid_columns(0, 4, 80), 1);
+    }
+
+    #[test]
+    fn test_move_block() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Priorities")
+            .press(Key::Char('v'))
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::ArrowUp)
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let order = OrderList::new()
+            .with_prompt("Priorities")
+            .items(&["a", "b", "c", "d", "e"])
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(order, vec![2, 3, 0, 1, 4]);
+    }
+
+    #[test]
+    fn test_parse_order() {
+        assert_eq!(parse_order("", 3), Some(vec![0, 1, 2]));
+        assert_eq!(parse_order("3", 3), Some(vec![2, 0, 1]));
+        assert_eq!(parse_order("3,1-2,3", 3), Some(vec![2, 0, 1]));
+        assert_eq!(parse_order("2 1", 3), Some(vec![1, 0, 2]));
+        assert_eq!(parse_order("4", 3), None);
+    }
+
+    #[test]
+    fn test_parse_choices() {
+        assert_eq!(parse_choices("2", 5), Some(vec![1]));
+        assert_eq!(parse_choices("3, 1 2-4", 5), Some(vec![0, 1, 2, 3]));
+        assert_eq!(parse_choices("", 5), Some(vec![]));
+        assert_eq!(parse_choices("6", 5), None);
+        assert_eq!(parse_choices("0", 5), None);
+        assert_eq!(parse_choices("a", 5), None);
+        assert_eq!(parse_choices("4-", 5), None);
+        assert_eq!(parse_choices("4-2", 5), None);
+    }
+
+    #[test]
+    fn test_check_states() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Packages")
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let states = Checkboxes::new()
+            .with_prompt("Packages")
+            .item_state("core", CheckState::Partial)
+            .item_state("docs", CheckState::Partial)
+            .item_checked("tests", true)
+            .interact_states_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(
+            states,
+            vec![
+                CheckState::Checked,
+                CheckState::Partial,
+                CheckState::Unchecked
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_selections() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Toppings")
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::ArrowUp)
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Toppings")
+            .items(&["ham", "egg", "corn"])
+            .max_selections(2)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_max_selections_assumed() {
+        let term = Term::buffered_stderr();
+        let err = Checkboxes::new()
+            .items(&["ham", "egg", "corn"])
+            .max_selections(2)
+            .assume(&[0, 1, 2])
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidDefault(_)));
+        let err = Checkboxes::new()
+            .items_checked(&[("ham", true), ("egg", true), ("corn", true)])
+            .max_selections(2)
+            .assume_default()
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidDefault(_)));
+        let checked = Checkboxes::new()
+            .items(&["ham", "egg", "corn"])
+            .max_selections(2)
+            .assume(&[0, 2])
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(checked, vec![0, 2]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_escape_reports_defaults() {
+        let (capture, term) = Capture::new();
+        let session = Script::new()
+            .expect_prompt("Toppings")
+            .press(Key::Char(' '))
+            .press(Key::Escape)
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Toppings")
+            .items_checked(&[("ham", false), ("egg", true), ("corn", true)])
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, vec![1, 2]);
+        assert!(capture.output().ends_with("\x1b[4AToppings: egg, corn\n"));
+    }
+
+    #[test]
+    fn test_quit_lines() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Toppings")
+            .type_line("q")
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Toppings")
+            .items(&["ham", "egg"])
+            .accessible(true)
+            .interact_on_opt(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, None);
+    }
+
+    #[test]
+    fn test_filter_text() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Hosts")
+            .press(Key::Char(' '))
+            .press(Key::Backspace)
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Hosts")
+            .items(&["dev-1", "prod-1", "prod-2"])
+            .with_filter_text("prod-2")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_matcher() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Hosts")
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Hosts")
+            .items(&["eu-prod", "prod-eu", "us-dev"])
+            .matcher(|query: &str, text: &str| text.find(query).map(|pos| (-(pos as i64), vec![])))
+            .with_filter_text("prod")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, vec![1]);
+    }
+
+    #[test]
+    fn test_scored() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Hosts")
+            .press(Key::Char(' '))
+            .press(Key::ArrowDown)
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let checked = Checkboxes::new()
+            .with_prompt("Hosts")
+            .items(&["eu-prod", "prod-eu", "us-dev"])
+            .matcher(|query: &str, text: &str| text.find(query).map(|pos| (-(pos as i64), vec![])))
+            .with_filter_text("prod")
+            .interact_scored_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(checked, vec![(0, Some(-3)), (1, Some(0))]);
+    }
+
+    #[cfg(feature = "state")]
+    #[test]
+    fn test_remember() {
+        use state::FileState;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state");
+        let term = Term::buffered_stderr();
+        let pick = |keys: &[Key]| {
+            let mut script = Script::new();
+            script.expect_prompt("Deploy to");
+            for key in keys {
+                script.press(key.clone());
+            }
+            let session = script.press(Key::Enter).start().unwrap();
+            let sel = Select::new()
+                .with_prompt("Deploy to")
+                .items(&["dev", "staging", "production"])
+                .default(0)
+                .remember("deploy", FileState::open(&path).unwrap())
+                .interact_on(&term)
+                .unwrap();
+            session.finish().unwrap();
+            sel
+        };
+        assert_eq!(pick(&[Key::ArrowDown, Key::ArrowDown]), 2);
+        assert_eq!(pick(&[]), 2);
+        assert_eq!(pick(&[Key::ArrowUp]), 1);
+    }
+
+    #[cfg(feature = "state")]
+    #[test]
+    fn test_remember_write_fails() {
+        struct ReadOnly;
+
+        impl StateStore for ReadOnly {
+            fn get(&self, _id: &str) -> Option<String> {
+                Some("staging".into())
+            }
+
+            fn set(&mut self, _id: &str, _value: &str) -> io::Result<()> {
+                Err(io::Error::from(io::ErrorKind::PermissionDenied))
+            }
+        }
+
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Deploy to")
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let sel = Select::new()
+            .with_prompt("Deploy to")
+            .items(&["dev", "staging", "production"])
+            .remember("deploy", ReadOnly)
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(sel, 2);
+    }
 }
@@ -0,0 +1,868 @@
+//! Recording and replaying prompt sessions.
+//!
+//! All input read by prompts goes through this module.  While a session
+//! is active on a thread the input is taken from its source instead of
+//! the terminal.
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use console::{Key, Term};
+
+use cancel;
+use localization;
+use wait;
+
+/// Supplies the input of prompts while a session is active.
+pub(crate) trait Source {
+    /// Called when a prompt starts asking.
+    fn question(&mut self, prompt: &str) -> io::Result<()>;
+    /// Called when a prompt was answered.
+    fn answer(&mut self, prompt: &str, answer: &str) -> io::Result<()>;
+    fn is_term(&self, term: &Term) -> bool;
+    fn is_dumb(&self, term: &Term) -> bool;
+    /// Returns `true` unless prompts should behave as if stdin was not
+    /// a terminal.
+    fn stdin_is_term(&self) -> bool {
+        true
+    }
+    fn key_ready(&mut self, timeout: Duration) -> io::Result<bool>;
+    fn read_key(&mut self, term: &Term) -> io::Result<Key>;
+    /// Reads a key typed into a password, which must not be kept in
+    /// plain text.
+    fn read_secure_key(&mut self, term: &Term) -> io::Result<Key> {
+        self.read_key(term)
+    }
+    fn read_char(&mut self, term: &Term) -> io::Result<char>;
+    fn read_line(&mut self, term: &Term, initial: Option<&str>, secure: bool)
+        -> io::Result<String>;
+    /// Called when the session is finished.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+thread_local! {
+    static SOURCE: RefCell<Option<Box<dyn Source>>> = RefCell::new(None);
+    static INTERRUPTED: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+fn with_source<R, F: FnOnce(&mut dyn Source) -> R>(f: F) -> Option<R> {
+    SOURCE.with(|source| source.borrow_mut().as_mut().map(|source| f(&mut **source)))
+}
+
+pub(crate) fn question(prompt: &str) -> io::Result<()> {
+    with_source(|source| source.question(prompt)).unwrap_or(Ok(()))
+}
+
+pub(crate) fn answer(prompt: &str, answer: &str) -> io::Result<()> {
+    with_source(|source| source.answer(prompt, answer)).unwrap_or(Ok(()))
+}
+
+/// Returns `true` if prompts should read keys rather than lines.
+pub(crate) fn is_term(term: &Term) -> bool {
+    with_source(|source| source.is_term(term)).unwrap_or_else(|| term.is_term())
+}
+
+/// Returns `true` if the cursor cannot be moved on the terminal, in
+/// which case menus fall back to reading numbers line by line.
+pub(crate) fn is_dumb(term: &Term) -> bool {
+    with_source(|source| source.is_dumb(term)).unwrap_or_else(|| term_is_dumb(term))
+}
+
+/// Returns `true` if stdin is a terminal the user can answer on.
+pub(crate) fn stdin_is_term() -> bool {
+    with_source(|source| source.stdin_is_term()).unwrap_or_else(|| io::stdin().is_terminal())
+}
+
+fn term_is_dumb(term: &Term) -> bool {
+    !term.is_term() || env::var("TERM").is_ok_and(|name| name == "dumb")
+}
+
+/// How often a cancellation is checked for while waiting for a key.
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+pub(crate) fn key_ready(timeout: Duration) -> io::Result<bool> {
+    if !cancel::is_cancellable() {
+        return wait_key(timeout);
+    }
+    let deadline = Instant::now() + timeout;
+    loop {
+        check_cancel()?;
+        let left = deadline.saturating_duration_since(Instant::now());
+        if wait_key(left.min(CANCEL_POLL))? {
+            return Ok(true);
+        }
+        if left <= CANCEL_POLL {
+            return Ok(false);
+        }
+    }
+}
+
+fn wait_key(timeout: Duration) -> io::Result<bool> {
+    with_source(|source| source.key_ready(timeout)).unwrap_or_else(|| wait::key_ready(timeout))
+}
+
+/// Fails if the prompts on this thread were cancelled, in which case the
+/// prompt is torn down like after Ctrl-C but without raising SIGINT.
+fn check_cancel() -> io::Result<()> {
+    cancel::check().inspect_err(|_| INTERRUPTED.with(|interrupted| interrupted.set(Some(false))))
+}
+
+/// Reads a key.  Ctrl-C interrupts the prompt with an error of the kind
+/// `Interrupted`.
+pub(crate) fn read_key(term: &Term) -> io::Result<Key> {
+    read_key_as(term, false)
+}
+
+/// Like `read_key` for a key typed into a password.
+pub(crate) fn read_secure_key(term: &Term) -> io::Result<Key> {
+    read_key_as(term, true)
+}
+
+fn read_key_as(term: &Term, secure: bool) -> io::Result<Key> {
+    if cancel::is_cancellable() && is_term(term) {
+        while !key_ready(CANCEL_POLL)? {}
+    }
+    let read = |source: &mut dyn Source| {
+        if secure {
+            source.read_secure_key(term)
+        } else {
+            source.read_key(term)
+        }
+    };
+    let (key, signal) = match with_source(read) {
+        Some(key) => (key?, false),
+        None => (term.read_key_raw()?, true),
+    };
+    if key == Key::CtrlC {
+        INTERRUPTED.with(|interrupted| interrupted.set(Some(signal)));
+        return Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            "interrupted by Ctrl-C",
+        ));
+    }
+    Ok(key)
+}
+
+/// A key read by `read_key_input` or what ended waiting for it.
+pub(crate) enum KeyInput {
+    Pressed(Key),
+    /// No key was pressed within the idle time
+    Idle,
+    /// The terminal was resized, so the prompt has to be redrawn
+    Resized,
+}
+
+/// How often a resize is checked for in case the signal came in just
+/// before waiting for a key.
+const RESIZE_POLL: Duration = Duration::from_millis(500);
+
+/// Reads a key unless no key was pressed within `idle` or the terminal
+/// was resized while waiting.
+///
+/// Prompts that are not on a terminal never go idle.
+pub(crate) fn read_key_input(term: &Term, idle: Option<Duration>) -> io::Result<KeyInput> {
+    let watch = with_source(|_| ()).is_none() && term.is_term() && wait::watch_resize();
+    if !watch {
+        return match idle {
+            Some(idle) if is_term(term) && !key_ready(idle)? => Ok(KeyInput::Idle),
+            _ => read_key(term).map(KeyInput::Pressed),
+        };
+    }
+    let deadline = idle.map(|idle| Instant::now() + idle);
+    loop {
+        if wait::take_resized() {
+            return Ok(KeyInput::Resized);
+        }
+        let wait = match deadline {
+            Some(deadline) => match deadline.saturating_duration_since(Instant::now()) {
+                left if left == Duration::from_secs(0) => return Ok(KeyInput::Idle),
+                left => left.min(RESIZE_POLL),
+            },
+            None => RESIZE_POLL,
+        };
+        if key_ready(wait)? {
+            return read_key(term).map(KeyInput::Pressed);
+        }
+    }
+}
+
+/// Returns whether the last key read was Ctrl-C and resets it.  The flag
+/// tells if SIGINT has to be raised once the prompt is torn down, which
+/// is the case when the key was read from the terminal.
+pub(crate) fn take_interrupt() -> Option<bool> {
+    INTERRUPTED.with(|interrupted| interrupted.take())
+}
+
+/// Raises SIGINT so the interruption is handled as if Ctrl-C was not
+/// caught.
+pub(crate) fn raise_interrupt() {
+    #[cfg(unix)]
+    unsafe {
+        libc::raise(libc::SIGINT);
+    }
+}
+
+pub(crate) fn read_char(term: &Term) -> io::Result<char> {
+    check_cancel()?;
+    with_source(|source| source.read_char(term)).unwrap_or_else(|| term.read_char())
+}
+
+pub(crate) fn read_line(term: &Term, initial: Option<&str>) -> io::Result<String> {
+    check_cancel()?;
+    with_source(|source| source.read_line(term, initial, false))
+        .unwrap_or_else(|| read_term_line(term, initial, false))
+}
+
+pub(crate) fn read_secure_line(term: &Term) -> io::Result<String> {
+    check_cancel()?;
+    with_source(|source| source.read_line(term, None, true))
+        .unwrap_or_else(|| term.read_secure_line())
+}
+
+/// Reads a line piped into stdin without its line ending.
+///
+/// Returns `None` if stdin is a terminal or a session is active.
+pub(crate) fn read_piped_line() -> io::Result<Option<String>> {
+    if with_source(|_| ()).is_some() || io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "nothing was piped into stdin",
+        ));
+    }
+    let len = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(len);
+    Ok(Some(line))
+}
+
+/// Edits a line typed key by key.  Returns `true` at the end of the line.
+pub(crate) fn type_key(line: &mut String, key: Key) -> bool {
+    match key {
+        Key::Enter => return true,
+        Key::Char(c) => line.push(c),
+        Key::Backspace => {
+            line.pop();
+        }
+        _ => {}
+    }
+    false
+}
+
+fn read_term_line(term: &Term, initial: Option<&str>, secure: bool) -> io::Result<String> {
+    match initial {
+        _ if secure => term.read_secure_line(),
+        Some(initial) => term.read_line_initial_text(initial),
+        None => term.read_line(),
+    }
+}
+
+/// Makes `source` the input of prompts on this thread.
+pub(crate) fn start(source: Box<dyn Source>) -> io::Result<Session> {
+    SOURCE.with(|current| {
+        let mut current = current.borrow_mut();
+        if current.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "a session is already active on this thread",
+            ));
+        }
+        *current = Some(source);
+        Ok(Session {
+            _marker: PhantomData,
+        })
+    })
+}
+
+/// An active recording or replay.
+///
+/// The session ends when this is dropped or `finish` is called.
+pub struct Session {
+    // sessions are bound to the thread that started them
+    _marker: PhantomData<*const ()>,
+}
+
+impl Session {
+    /// Ends the session.
+    ///
+    /// A replay fails if the recording has input that was not used.
+    pub fn finish(self) -> io::Result<()> {
+        match SOURCE.with(|source| source.borrow_mut().take()) {
+            Some(mut source) => source.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        SOURCE.with(|source| source.borrow_mut().take());
+    }
+}
+
+/// Records prompt sessions to a file.
+///
+/// The questions, the keys pressed with their timing and the answers
+/// are written as they happen.  Passwords are recorded as the `hidden`
+/// word of the localization, which is typed in their place when the
+/// session is replayed.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, Recording};
+///
+/// let session = Recording::new("session.txt").start()?;
+/// let name: String = Input::new().with_prompt("Your name").interact()?;
+/// session.finish()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Recording {
+    path: PathBuf,
+}
+
+impl Recording {
+    /// Creates a recording writing to a file.
+    pub fn new<P: AsRef<Path>>(path: P) -> Recording {
+        Recording {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Starts recording the prompts on this thread.
+    pub fn start(&self) -> io::Result<Session> {
+        let recorder = Recorder {
+            file: File::create(&self.path)?,
+            last: Instant::now(),
+        };
+        start(Box::new(recorder))
+    }
+}
+
+/// Replays a recorded session.
+///
+/// The recorded keys are fed to the prompts instead of the terminal.  A
+/// prompt that was not asked in the recording or an answer that differs
+/// from the recorded one fails with an error, which makes replays usable
+/// as regression tests.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, Replay};
+///
+/// let session = Replay::new("session.txt").start()?;
+/// let name: String = Input::new().with_prompt("Your name").interact()?;
+/// session.finish()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Replay {
+    path: PathBuf,
+    realtime: bool,
+}
+
+impl Replay {
+    /// Creates a replay of a recording file.
+    pub fn new<P: AsRef<Path>>(path: P) -> Replay {
+        Replay {
+            path: path.as_ref().to_path_buf(),
+            realtime: false,
+        }
+    }
+
+    /// Waits between keys as long as in the recording.
+    ///
+    /// This is useful for demos.  By default the keys are replayed
+    /// without delay.
+    pub fn realtime(&mut self, val: bool) -> &mut Replay {
+        self.realtime = val;
+        self
+    }
+
+    /// Starts replaying the prompts on this thread.
+    pub fn start(&self) -> io::Result<Session> {
+        let file = BufReader::new(File::open(&self.path)?);
+        let mut replayer = Replayer::parse(file)?;
+        replayer.realtime = self.realtime;
+        start(Box::new(replayer))
+    }
+}
+
+struct Recorder {
+    file: File,
+    last: Instant,
+}
+
+impl Recorder {
+    fn write(&mut self, fields: &[&str]) -> io::Result<()> {
+        let fields: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+        writeln!(self.file, "{}", fields.join("\t"))
+    }
+
+    fn write_input(&mut self, kind: &str, input: &str) -> io::Result<()> {
+        let delay = self.last.elapsed().as_millis().to_string();
+        self.last = Instant::now();
+        self.write(&[kind, &delay, input])
+    }
+}
+
+impl Source for Recorder {
+    fn question(&mut self, prompt: &str) -> io::Result<()> {
+        self.last = Instant::now();
+        self.write(&["question", prompt])
+    }
+
+    fn answer(&mut self, prompt: &str, answer: &str) -> io::Result<()> {
+        self.write(&["answer", prompt, answer])
+    }
+
+    fn is_term(&self, term: &Term) -> bool {
+        term.is_term()
+    }
+
+    fn is_dumb(&self, term: &Term) -> bool {
+        term_is_dumb(term)
+    }
+
+    fn key_ready(&mut self, timeout: Duration) -> io::Result<bool> {
+        wait::key_ready(timeout)
+    }
+
+    fn read_key(&mut self, term: &Term) -> io::Result<Key> {
+        let key = term.read_key_raw()?;
+        self.write_input("key", &key_name(&key))?;
+        Ok(key)
+    }
+
+    fn read_secure_key(&mut self, term: &Term) -> io::Result<Key> {
+        let key = term.read_key_raw()?;
+        if let Some((kind, input)) = secure_input(&key) {
+            self.write_input(kind, &input)?;
+        }
+        Ok(key)
+    }
+
+    fn read_char(&mut self, term: &Term) -> io::Result<char> {
+        let c = term.read_char()?;
+        self.write_input("key", &key_name(&Key::Char(c)))?;
+        Ok(c)
+    }
+
+    fn read_line(
+        &mut self,
+        term: &Term,
+        initial: Option<&str>,
+        secure: bool,
+    ) -> io::Result<String> {
+        let line = read_term_line(term, initial, secure)?;
+        if secure {
+            self.write_input("line", &hidden())?;
+        } else {
+            self.write_input("line", &line)?;
+        }
+        Ok(line)
+    }
+}
+
+/// Returns the input recorded for a key typed into a password, if any.
+///
+/// The typed characters are left out and the password is recorded as a
+/// placeholder line once it is entered.
+fn secure_input(key: &Key) -> Option<(&'static str, String)> {
+    match *key {
+        Key::Enter => Some(("line", hidden())),
+        Key::CtrlC => Some(("key", key_name(key))),
+        _ => None,
+    }
+}
+
+/// Returns the placeholder recorded in place of a password.
+fn hidden() -> String {
+    localization::with(|l| l.hidden.to_string())
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Question(String),
+    Answer(String, String),
+    Key(Duration, Key),
+    Line(Duration, String),
+}
+
+struct Replayer {
+    events: VecDeque<Event>,
+    realtime: bool,
+    waited: Duration,
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl Replayer {
+    fn parse<R: BufRead>(reader: R) -> io::Result<Replayer> {
+        let mut events = VecDeque::new();
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Option<Vec<String>> = line.split('\t').map(unescape).collect();
+            let event = fields.and_then(|fields| {
+                let delay = |field: &str| field.parse().ok().map(Duration::from_millis);
+                Some(match fields.as_slice() {
+                    [kind, prompt] if kind == "question" => Event::Question(prompt.clone()),
+                    [kind, prompt, answer] if kind == "answer" => {
+                        Event::Answer(prompt.clone(), answer.clone())
+                    }
+                    [kind, ms, key] if kind == "key" => Event::Key(delay(ms)?, parse_key(key)?),
+                    [kind, ms, line] if kind == "line" => Event::Line(delay(ms)?, line.clone()),
+                    _ => return None,
+                })
+            });
+            match event {
+                Some(event) => events.push_back(event),
+                None => {
+                    return Err(invalid_data(format!(
+                        "invalid recording on line {}",
+                        idx + 1
+                    )))
+                }
+            }
+        }
+        Ok(Replayer {
+            events,
+            realtime: false,
+            waited: Duration::from_secs(0),
+        })
+    }
+
+    /// Returns the delay before the next input if it is one.
+    fn next_delay(&self) -> Option<Duration> {
+        match self.events.front() {
+            Some(&Event::Key(delay, _)) | Some(&Event::Line(delay, _)) => Some(delay),
+            _ => None,
+        }
+    }
+
+    fn wait_for_input(&mut self) {
+        if let Some(delay) = self.next_delay() {
+            if self.realtime {
+                thread::sleep(delay.checked_sub(self.waited).unwrap_or_default());
+            }
+        }
+        self.waited = Duration::from_secs(0);
+    }
+
+    fn unexpected(&self, wanted: &str) -> io::Error {
+        let found = match self.events.front() {
+            Some(Event::Question(prompt)) => format!("the prompt {:?}", prompt),
+            Some(Event::Answer(prompt, _)) => format!("the answer to {:?}", prompt),
+            Some(_) => "more input".into(),
+            None => "nothing".into(),
+        };
+        invalid_data(format!(
+            "expected {} but the recording has {}",
+            wanted, found
+        ))
+    }
+}
+
+impl Source for Replayer {
+    fn question(&mut self, prompt: &str) -> io::Result<()> {
+        match self.events.front() {
+            Some(Event::Question(recorded)) if recorded == prompt => {}
+            _ => return Err(self.unexpected(&format!("the prompt {:?}", prompt))),
+        }
+        self.events.pop_front();
+        Ok(())
+    }
+
+    fn answer(&mut self, prompt: &str, answer: &str) -> io::Result<()> {
+        match self.events.front() {
+            Some(Event::Answer(recorded, expected)) if recorded == prompt => {
+                if expected != answer {
+                    return Err(invalid_data(format!(
+                        "answered {:?} to {:?} but the recording has {:?}",
+                        answer, prompt, expected
+                    )));
+                }
+            }
+            _ => return Err(self.unexpected(&format!("the answer to {:?}", prompt))),
+        }
+        self.events.pop_front();
+        Ok(())
+    }
+
+    fn is_term(&self, _term: &Term) -> bool {
+        true
+    }
+
+    fn is_dumb(&self, _term: &Term) -> bool {
+        false
+    }
+
+    fn key_ready(&mut self, timeout: Duration) -> io::Result<bool> {
+        let delay = match self.next_delay() {
+            Some(delay) if self.realtime => delay,
+            _ => return Ok(true),
+        };
+        let left = delay.checked_sub(self.waited).unwrap_or_default();
+        let wait = left.min(timeout);
+        thread::sleep(wait);
+        self.waited += wait;
+        Ok(left <= timeout)
+    }
+
+    fn read_key(&mut self, _term: &Term) -> io::Result<Key> {
+        if let Some(&Event::Line(..)) = self.events.front() {
+            // lines are typed out key by key
+            if let Some(Event::Line(delay, line)) = self.events.pop_front() {
+                let keys: Vec<Key> = line
+                    .chars()
+                    .map(Key::Char)
+                    .chain(Some(Key::Enter))
+                    .collect();
+                for (idx, key) in keys.into_iter().enumerate().rev() {
+                    let delay = if idx == 0 {
+                        delay
+                    } else {
+                        Duration::from_secs(0)
+                    };
+                    self.events.push_front(Event::Key(delay, key));
+                }
+            }
+        }
+        self.wait_for_input();
+        match self.events.front() {
+            Some(Event::Key(_, key)) => {
+                let key = key.clone();
+                self.events.pop_front();
+                Ok(key)
+            }
+            _ => Err(self.unexpected("a key")),
+        }
+    }
+
+    fn read_char(&mut self, term: &Term) -> io::Result<char> {
+        match self.read_key(term)? {
+            Key::Char(c) => Ok(c),
+            Key::Enter => Ok('\n'),
+            key => Err(invalid_data(format!(
+                "expected a character but the recording has {}",
+                key_name(&key)
+            ))),
+        }
+    }
+
+    fn read_line(
+        &mut self,
+        _term: &Term,
+        _initial: Option<&str>,
+        _secure: bool,
+    ) -> io::Result<String> {
+        self.wait_for_input();
+        match self.events.front() {
+            Some(&Event::Line(..)) => match self.events.pop_front() {
+                Some(Event::Line(_, line)) => Ok(line),
+                _ => unreachable!(),
+            },
+            Some(&Event::Key(..)) => {
+                // keys are collected up to the end of the line
+                let mut line = String::new();
+                while let Some(&Event::Key(..)) = self.events.front() {
+                    if let Some(Event::Key(_, key)) = self.events.pop_front() {
+                        if type_key(&mut line, key) {
+                            break;
+                        }
+                    }
+                }
+                Ok(line)
+            }
+            _ => Err(self.unexpected("a line")),
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.events.len() {
+            0 => Ok(()),
+            n => Err(invalid_data(format!(
+                "{} recorded events were not replayed",
+                n
+            ))),
+        }
+    }
+}
+
+const KEY_NAMES: &[(&str, Key)] = &[
+    ("Unknown", Key::Unknown),
+    ("ArrowLeft", Key::ArrowLeft),
+    ("ArrowRight", Key::ArrowRight),
+    ("ArrowUp", Key::ArrowUp),
+    ("ArrowDown", Key::ArrowDown),
+    ("Enter", Key::Enter),
+    ("Escape", Key::Escape),
+    ("Backspace", Key::Backspace),
+    ("Home", Key::Home),
+    ("End", Key::End),
+    ("Tab", Key::Tab),
+    ("BackTab", Key::BackTab),
+    ("Alt", Key::Alt),
+    ("Del", Key::Del),
+    ("Shift", Key::Shift),
+    ("Insert", Key::Insert),
+    ("PageUp", Key::PageUp),
+    ("PageDown", Key::PageDown),
+    ("CtrlC", Key::CtrlC),
+];
+
+fn key_name(key: &Key) -> String {
+    match *key {
+        Key::Char(c) => format!("Char({})", c),
+        Key::UnknownEscSeq(ref chars) => {
+            format!("UnknownEscSeq({})", chars.iter().collect::<String>())
+        }
+        ref key => KEY_NAMES
+            .iter()
+            .find(|(_, named)| named == key)
+            .map_or("Unknown", |&(name, _)| name)
+            .to_string(),
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let arg = |prefix: &str| {
+        if name.starts_with(prefix) && name.ends_with(')') {
+            Some(&name[prefix.len()..name.len() - 1])
+        } else {
+            None
+        }
+    };
+    if let Some(c) = arg("Char(") {
+        let mut chars = c.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(Key::Char(c)),
+            _ => None,
+        };
+    }
+    if let Some(chars) = arg("UnknownEscSeq(") {
+        return Some(Key::UnknownEscSeq(chars.chars().collect()));
+    }
+    KEY_NAMES
+        .iter()
+        .find(|&&(named, _)| named == name)
+        .map(|(_, key)| key.clone())
+}
+
+/// Escapes backslashes and control characters.
+fn escape(s: &str) -> String {
+    let mut rv = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => rv.push_str("\\\\"),
+            '\t' => rv.push_str("\\t"),
+            '\n' => rv.push_str("\\n"),
+            '\r' => rv.push_str("\\r"),
+            c if c.is_control() => rv.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => rv.push(c),
+        }
+    }
+    rv
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut rv = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            rv.push(c);
+            continue;
+        }
+        rv.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let code: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                std::char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+            }
+            _ => return None,
+        });
+    }
+    Some(rv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_names() {
+        let keys = vec![
+            Key::Char('a'),
+            Key::Char(')'),
+            Key::Char('\u{17}'),
+            Key::Char('\t'),
+            Key::Enter,
+            Key::PageDown,
+            Key::UnknownEscSeq(vec!['[', 'b']),
+        ];
+        for key in keys {
+            let name = escape(&key_name(&key));
+            assert_eq!(parse_key(&unescape(&name).unwrap()), Some(key));
+        }
+        assert_eq!(parse_key("Char(ab)"), None);
+        assert_eq!(parse_key("Nope"), None);
+        assert_eq!(unescape("a\\u{17}\\\\\\t"), Some("a\u{17}\\\t".into()));
+        assert_eq!(unescape("\\x"), None);
+    }
+
+    #[test]
+    fn test_secure_input() {
+        assert_eq!(secure_input(&Key::Char('s')), None);
+        assert_eq!(secure_input(&Key::Backspace), None);
+        assert_eq!(secure_input(&Key::Enter), Some(("line", "[hidden]".into())));
+        assert_eq!(secure_input(&Key::CtrlC), Some(("key", "CtrlC".into())));
+    }
+
+    #[test]
+    fn test_replay() {
+        let recording = "question\tYour name\n\
+                         key\t10\tChar(A)\n\
+                         key\t10\tChar(n)\n\
+                         key\t10\tBackspace\n\
+                         line\t10\tnn\n\
+                         answer\tYour name\tAnn\n";
+        let term = Term::stderr();
+        let mut replay = Replayer::parse(recording.as_bytes()).unwrap();
+        assert!(replay.question("Your age").is_err());
+        replay.question("Your name").unwrap();
+        assert_eq!(replay.read_line(&term, None, false).unwrap(), "A");
+        assert_eq!(replay.read_key(&term).unwrap(), Key::Char('n'));
+        assert_eq!(replay.read_key(&term).unwrap(), Key::Char('n'));
+        assert!(replay.question("Your name").is_err());
+        assert_eq!(replay.read_key(&term).unwrap(), Key::Enter);
+        assert!(replay.read_key(&term).is_err());
+        assert!(replay.answer("Your name", "Bob").is_err());
+        replay.answer("Your name", "Ann").unwrap();
+        replay.finish().unwrap();
+
+        assert!(Replayer::parse("key\tsoon\tEnter".as_bytes()).is_err());
+    }
+}
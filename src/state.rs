@@ -0,0 +1,105 @@
+//! Answers remembered across invocations.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Values remembered for prompts, keyed by the id of the prompt.
+pub trait StateStore {
+    /// Returns the value remembered for a prompt.
+    fn get(&self, id: &str) -> Option<String>;
+
+    /// Remembers a value for a prompt.
+    fn set(&mut self, id: &str, value: &str) -> io::Result<()>;
+}
+
+/// A state store kept in a file so that it is recalled across
+/// invocations.
+///
+/// The file holds one prompt per line, its id and value separated by a
+/// tab.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FileState, Select};
+///
+/// let env = Select::new()
+///     .with_prompt("Deploy to")
+///     .items(&["staging", "production"])
+///     .remember("deploy-env", FileState::open(".prompt_state")?)
+///     .interact()?;
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+#[derive(Debug)]
+pub struct FileState {
+    path: PathBuf,
+    values: BTreeMap<String, String>,
+}
+
+impl FileState {
+    /// Loads the state from a file.
+    ///
+    /// A missing file is an empty state and is created once a value is
+    /// remembered.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileState> {
+        let path = path.as_ref().to_path_buf();
+        let values = match fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .filter_map(|line| line.split_once('\t'))
+                .map(|(id, value)| (id.to_string(), value.to_string()))
+                .collect(),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(FileState { path, values })
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut text = String::new();
+        for (id, value) in &self.values {
+            text.push_str(id);
+            text.push('\t');
+            text.push_str(value);
+            text.push('\n');
+        }
+        fs::write(&self.path, text)
+    }
+}
+
+impl StateStore for FileState {
+    fn get(&self, id: &str) -> Option<String> {
+        self.values.get(&line_safe(id)).cloned()
+    }
+
+    fn set(&mut self, id: &str, value: &str) -> io::Result<()> {
+        self.values.insert(line_safe(id), value.replace('\n', " "));
+        self.save()
+    }
+}
+
+/// Makes an id fit in the id column of a line.
+fn line_safe(id: &str) -> String {
+    id.replace(['\t', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state");
+        let mut state = FileState::open(&path).unwrap();
+        assert_eq!(state.get("env"), None);
+        state.set("env", "production").unwrap();
+        state.set("region\tid", "eu\nwest").unwrap();
+
+        let state = FileState::open(&path).unwrap();
+        assert_eq!(state.get("env").as_deref(), Some("production"));
+        assert_eq!(state.get("region\tid").as_deref(), Some("eu west"));
+    }
+}
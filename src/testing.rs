@@ -0,0 +1,458 @@
+//! Scripted input for testing interactive flows.
+//!
+//! A `Script` declares the prompts a test expects in order and the
+//! input typed at each of them.  While the script runs, prompts read
+//! from it instead of the terminal and a prompt asked out of order
+//! fails the test.
+//!
+//! ## Example usage
+//!
+//! ```rust
+//! # extern crate console;
+//! # extern crate dialoguer;
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use console::{Key, Term};
+//! use dialoguer::testing::Script;
+//! use dialoguer::{Confirmation, Input};
+//!
+//! let session = Script::new()
+//!     .expect_prompt("Your name")
+//!     .type_line("Ann")
+//!     .expect_prompt("Continue?")
+//!     .press(Key::Char('y'))
+//!     .start()?;
+//!
+//! // output is kept in the buffer of the terminal
+//! let term = Term::buffered_stderr();
+//! let name: String = Input::new().with_prompt("Your name").interact_on(&term)?;
+//! let ok = Confirmation::new().with_text("Continue?").interact_on(&term)?;
+//! session.finish()?;
+//!
+//! assert_eq!(name, "Ann");
+//! assert!(ok);
+//! # Ok(()) } fn main() { test().unwrap(); }
+//! ```
+//!
+//! A `Snapshot` renders prompts in a given state to a string without a
+//! terminal, eg: to test the formatting of a theme.
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use console::{Key, Term};
+
+use localization::Scope;
+use session::{self, Session, Source};
+use theme::{isolate, question_line, ConfirmationLabels, ListItem, SelectionStyle, Theme};
+
+/// The prompts expected by a test with the input typed at them.
+#[derive(Default)]
+pub struct Script {
+    // `None` leaves the prompt idle
+    steps: Vec<(String, Vec<Option<Key>>)>,
+    without_tty: bool,
+}
+
+impl Script {
+    /// Creates an empty script.
+    pub fn new() -> Script {
+        Script::default()
+    }
+
+    /// Expects a prompt with the given text to be asked next.
+    pub fn expect_prompt(&mut self, prompt: &str) -> &mut Script {
+        self.steps.push((prompt.into(), vec![]));
+        self
+    }
+
+    /// Presses a key at the expected prompt.
+    pub fn press(&mut self, key: Key) -> &mut Script {
+        self.input(Some(key))
+    }
+
+    /// Leaves the expected prompt idle until its timeout runs out before
+    /// the next key is pressed.
+    pub fn idle(&mut self) -> &mut Script {
+        self.input(None)
+    }
+
+    fn input(&mut self, key: Option<Key>) -> &mut Script {
+        match self.steps.last_mut() {
+            Some(&mut (_, ref mut keys)) => keys.push(key),
+            None => panic!("expect_prompt has to be called before input is typed"),
+        }
+        self
+    }
+
+    /// Types a line of text followed by enter at the expected prompt.
+    pub fn type_line(&mut self, text: &str) -> &mut Script {
+        for c in text.chars() {
+            self.press(Key::Char(c));
+        }
+        self.press(Key::Enter)
+    }
+
+    /// Runs the prompts as if stdin was not a terminal, eg: to test
+    /// what they answer in CI.
+    pub fn without_tty(&mut self) -> &mut Script {
+        self.without_tty = true;
+        self
+    }
+
+    /// Runs the script on this thread until the session is finished.
+    ///
+    /// Finishing the session fails if an expected prompt was not asked
+    /// or typed input was not read.
+    pub fn start(&self) -> io::Result<Session> {
+        session::start(Box::new(Scripted {
+            steps: self.steps.iter().cloned().collect(),
+            current: None,
+            keys: VecDeque::new(),
+            without_tty: self.without_tty,
+        }))
+    }
+}
+
+/// Renders prompts in a given state to a string, eg: for snapshot tests
+/// of a theme.
+///
+/// Each method adds the lines a prompt shows in that state.  Styles are
+/// kept as escape codes unless the theme turns them off, such as the
+/// `ColoredTheme` with `colors(false)`.  A theme failing to format
+/// panics.
+///
+/// ## Example usage
+///
+/// ```rust
+/// use dialoguer::testing::Snapshot;
+/// use dialoguer::theme::SimpleTheme;
+///
+/// let text = Snapshot::new(&SimpleTheme)
+///     .select("Color", &["red", "green"], 1)
+///     .answer("Color", "green")
+///     .to_string();
+/// assert_eq!(text, "Color:\n  red\n> green\nColor: green\n");
+/// ```
+pub struct Snapshot<'a> {
+    theme: &'a dyn Theme,
+    text: String,
+    _localization: Option<Scope>,
+}
+
+impl<'a> Snapshot<'a> {
+    /// Creates an empty snapshot rendered with a theme.
+    pub fn new(theme: &'a dyn Theme) -> Snapshot<'a> {
+        Snapshot {
+            theme,
+            text: String::new(),
+            _localization: theme.localization().map(Scope::enter),
+        }
+    }
+
+    /// Adds an input prompt with the text typed so far.
+    pub fn input(&mut self, prompt: &str, default: Option<&str>, text: &str) -> &mut Snapshot<'a> {
+        let prompt = self.prompt_text(prompt);
+        self.line(|theme, f| {
+            theme.format_singleline_prompt(f, &prompt, default)?;
+            f.write_str(text)
+        })
+    }
+
+    /// Adds a confirmation prompt.
+    pub fn confirmation(&mut self, prompt: &str, default: Option<bool>) -> &mut Snapshot<'a> {
+        let prompt = self.prompt_text(prompt);
+        let labels = ConfirmationLabels::default().localized();
+        self.line(|theme, f| theme.format_confirmation_prompt(f, &prompt, default, &labels))
+    }
+
+    /// Adds a menu with the item at `active` under the cursor.
+    pub fn select(&mut self, prompt: &str, items: &[&str], active: usize) -> &mut Snapshot<'a> {
+        let prompt = self.prompt_text(prompt);
+        self.line(|theme, f| theme.format_prompt(f, &prompt));
+        for (index, item) in items.iter().enumerate() {
+            let style = if index == active {
+                SelectionStyle::MenuSelected
+            } else {
+                SelectionStyle::MenuUnselected
+            };
+            self.item(item, style, index, items.len());
+        }
+        self
+    }
+
+    /// Adds checkboxes with the item at `active` under the cursor.
+    pub fn checkboxes(
+        &mut self,
+        prompt: &str,
+        items: &[&str],
+        checked: &[bool],
+        active: usize,
+    ) -> &mut Snapshot<'a> {
+        let prompt = self.prompt_text(prompt);
+        self.line(|theme, f| theme.format_prompt(f, &prompt));
+        for (index, item) in items.iter().enumerate() {
+            let style = match (checked.get(index) == Some(&true), index == active) {
+                (true, true) => SelectionStyle::CheckboxCheckedSelected,
+                (true, false) => SelectionStyle::CheckboxCheckedUnselected,
+                (false, true) => SelectionStyle::CheckboxUncheckedSelected,
+                (false, false) => SelectionStyle::CheckboxUncheckedUnselected,
+            };
+            self.item(item, style, index, items.len());
+        }
+        self
+    }
+
+    /// Adds an error shown below a prompt.
+    pub fn error(&mut self, err: &str) -> &mut Snapshot<'a> {
+        self.line(|theme, f| theme.format_error(f, err))
+    }
+
+    /// Adds the line reporting the answer of a prompt.
+    pub fn answer(&mut self, prompt: &str, answer: &str) -> &mut Snapshot<'a> {
+        let prompt = question_line(prompt);
+        self.line(|theme, f| theme.format_single_prompt_selection(f, &prompt, &isolate(answer)))
+    }
+
+    /// Adds the leading lines of a multi-line prompt and returns the line
+    /// the prompt is asked on.
+    fn prompt_text(&mut self, prompt: &str) -> String {
+        if let Some(idx) = prompt.rfind('\n') {
+            self.line(|theme, f| theme.format_prompt_text(f, &prompt[..idx]));
+        }
+        question_line(prompt).into_owned()
+    }
+
+    fn item(&mut self, text: &str, style: SelectionStyle, index: usize, count: usize) {
+        let text = isolate(text);
+        let item = ListItem {
+            index,
+            count,
+            disabled: false,
+            score: None,
+        };
+        self.line(|theme, f| theme.format_list_item(f, &text, &[], style, item));
+    }
+
+    fn line<F>(&mut self, f: F) -> &mut Snapshot<'a>
+    where
+        F: FnOnce(&dyn Theme, &mut dyn Write) -> fmt::Result,
+    {
+        f(self.theme, &mut self.text).expect("the theme failed to format a prompt");
+        self.text.push('\n');
+        self
+    }
+}
+
+impl<'a> fmt::Display for Snapshot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A terminal keeping what prompts render in a file, for tests that
+/// check the output.
+#[cfg(all(test, unix))]
+pub(crate) struct Capture {
+    file: std::fs::File,
+}
+
+#[cfg(all(test, unix))]
+impl Capture {
+    pub(crate) fn new() -> (Capture, Term) {
+        let file = tempfile::tempfile().unwrap();
+        let term = Term::read_write_pair(file.try_clone().unwrap(), file.try_clone().unwrap());
+        (Capture { file }, term)
+    }
+
+    /// Returns everything rendered so far.
+    pub(crate) fn output(&self) -> String {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut file = &self.file;
+        let mut text = String::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_string(&mut text).unwrap();
+        text
+    }
+}
+
+struct Scripted {
+    steps: VecDeque<(String, Vec<Option<Key>>)>,
+    current: Option<String>,
+    keys: VecDeque<Option<Key>>,
+    without_tty: bool,
+}
+
+impl Scripted {
+    fn next_key(&mut self) -> Key {
+        // prompts without a timeout wait through idle times
+        while let Some(&None) = self.keys.front() {
+            self.keys.pop_front();
+        }
+        match (self.keys.pop_front().flatten(), self.current.as_ref()) {
+            (Some(key), _) => key,
+            (None, Some(prompt)) => panic!("the prompt {:?} read more input than scripted", prompt),
+            (None, None) => panic!("input was read before any prompt was asked"),
+        }
+    }
+}
+
+impl Source for Scripted {
+    fn question(&mut self, prompt: &str) -> io::Result<()> {
+        if let Some(ref current) = self.current {
+            if !self.keys.is_empty() {
+                panic!(
+                    "the prompt {:?} was asked before the input of {:?} was read",
+                    prompt, current
+                );
+            }
+        }
+        match self.steps.pop_front() {
+            Some((ref expected, ref keys)) if expected == prompt => {
+                self.keys = keys.iter().cloned().collect();
+            }
+            Some((expected, _)) => {
+                panic!(
+                    "expected the prompt {:?} but {:?} was asked",
+                    expected, prompt
+                )
+            }
+            None => panic!(
+                "the prompt {:?} was asked after the end of the script",
+                prompt
+            ),
+        }
+        self.current = Some(prompt.into());
+        Ok(())
+    }
+
+    fn answer(&mut self, _prompt: &str, _answer: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn is_term(&self, _term: &Term) -> bool {
+        true
+    }
+
+    fn is_dumb(&self, _term: &Term) -> bool {
+        false
+    }
+
+    fn stdin_is_term(&self) -> bool {
+        !self.without_tty
+    }
+
+    fn key_ready(&mut self, timeout: Duration) -> io::Result<bool> {
+        // without scripted keys a timeout runs out
+        match self.keys.front() {
+            Some(&Some(_)) => Ok(true),
+            Some(&None) => {
+                self.keys.pop_front();
+                thread::sleep(timeout);
+                Ok(false)
+            }
+            None => {
+                thread::sleep(timeout);
+                Ok(false)
+            }
+        }
+    }
+
+    fn read_key(&mut self, _term: &Term) -> io::Result<Key> {
+        Ok(self.next_key())
+    }
+
+    fn read_char(&mut self, _term: &Term) -> io::Result<char> {
+        match self.next_key() {
+            Key::Char(c) => Ok(c),
+            Key::Enter => Ok('\n'),
+            key => Err(io::Error::other(format!("{:?} is not a character", key))),
+        }
+    }
+
+    fn read_line(
+        &mut self,
+        _term: &Term,
+        _initial: Option<&str>,
+        _secure: bool,
+    ) -> io::Result<String> {
+        let mut line = String::new();
+        while !session::type_key(&mut line, self.next_key()) {}
+        Ok(line)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let error = |msg: String| Err(io::Error::other(msg));
+        if let Some((prompt, _)) = self.steps.front() {
+            return error(format!("the prompt {:?} was not asked", prompt));
+        }
+        match self.current {
+            Some(ref prompt) if !self.keys.is_empty() => {
+                error(format!("not all input typed at {:?} was read", prompt))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use theme::SimpleTheme;
+    use {Input, Select};
+
+    #[test]
+    fn test_script() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("Ann")
+            .expect_prompt("Color")
+            .press(Key::ArrowDown)
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let name: String = Input::new().with_prompt("Name").interact_on(&term).unwrap();
+        let color = Select::new()
+            .with_prompt("Color")
+            .items(&["red", "green"])
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(name, "Ann");
+        assert_eq!(color, 1);
+
+        let session = Script::new().expect_prompt("Name").start().unwrap();
+        assert!(session.finish().is_err());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let text = Snapshot::new(&SimpleTheme)
+            .checkboxes("Intro\nToppings", &["ham", "egg"], &[true], 1)
+            .input("Name", Some("Ann"), "Bo")
+            .confirmation("Continue?", Some(true))
+            .error("oops")
+            .to_string();
+        assert_eq!(
+            text,
+            "Intro\nToppings:\n  [x] ham\n> [ ] egg\n\
+             Name [Ann]: Bo\nContinue? [Y/n] \nerror: oops\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected the prompt \"Name\" but \"Age\" was asked")]
+    fn test_script_order() {
+        let term = Term::buffered_stderr();
+        let _session = Script::new()
+            .expect_prompt("Name")
+            .type_line("Ann")
+            .start()
+            .unwrap();
+        let _: String = Input::new().with_prompt("Age").interact_on(&term).unwrap();
+    }
+}
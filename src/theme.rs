@@ -1,8 +1,19 @@
 //! Customizes the rendering of the elements.
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::env;
 use std::fmt;
 use std::io;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
 
-use console::{Style, StyledObject, Term};
+use console::{measure_text_width, strip_ansi_codes, truncate_str, Key, Style, StyledObject, Term};
+use guard::PanicGuard;
+use localization::{self, Localization};
+use select::GRID_GAP;
+use session;
+use unicode_width::UnicodeWidthChar;
 
 /// Rendering style for a selected item
 #[derive(Debug, Clone, Copy)]
@@ -15,19 +26,171 @@ pub enum SelectionStyle {
     CheckboxCheckedSelected,
     /// Renders a checked and unselected checkbox
     CheckboxCheckedUnselected,
+    /// Renders a partly checked but selected checkbox
+    CheckboxPartialSelected,
+    /// Renders a partly checked and unselected checkbox
+    CheckboxPartialUnselected,
     /// Renders a selected menu item
     MenuSelected,
     /// Renders un unselected menu item
     MenuUnselected,
 }
 
+/// The place of an item in the list it is shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListItem {
+    /// The index of the item, starting at 0
+    pub index: usize,
+    /// The number of items in the list
+    pub count: usize,
+    /// Whether the item cannot be checked, eg: because the most items
+    /// allowed are checked
+    pub disabled: bool,
+    /// The match score of the item while the list is filtered, higher
+    /// scores being more relevant
+    pub score: Option<i64>,
+}
+
+impl ListItem {
+    /// Returns whether this is the last item of the list.
+    pub fn is_last(&self) -> bool {
+        self.index + 1 == self.count
+    }
+}
+
+/// The severity of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// A notice
+    Info,
+    /// A warning, eg: before a destructive action
+    Warning,
+    /// An error
+    Error,
+}
+
+impl Severity {
+    /// Returns the lowercase name of the severity in the active
+    /// localization.
+    pub fn label(self) -> String {
+        localization::with(|l| match self {
+            Severity::Info => l.info.to_string(),
+            Severity::Warning => l.warning.to_string(),
+            Severity::Error => l.error.to_string(),
+        })
+    }
+}
+
+/// How a prompt reacts to a key it ignores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidKeyFeedback {
+    /// Does nothing
+    #[default]
+    Silent,
+    /// Rings the terminal bell
+    Bell,
+    /// Briefly inverts the colors of the screen
+    Flash,
+}
+
+/// The words and keys used to answer a confirmation prompt.
+///
+/// If no words are set the theme picks its own (usually "yes" and "no").
+#[derive(Debug, Clone)]
+pub struct ConfirmationLabels {
+    /// The word reported for an affirmative answer
+    pub yes: Option<String>,
+    /// The word reported for a negative answer
+    pub no: Option<String>,
+    /// The key confirming the prompt
+    pub yes_key: char,
+    /// The key declining the prompt
+    pub no_key: char,
+}
+
+impl Default for ConfirmationLabels {
+    fn default() -> ConfirmationLabels {
+        ConfirmationLabels {
+            yes: None,
+            no: None,
+            yes_key: 'y',
+            no_key: 'n',
+        }
+    }
+}
+
+impl ConfirmationLabels {
+    /// Returns the word for an answer or the given fallback.
+    pub fn word<'b>(&'b self, val: bool, fallback: &'b str) -> &'b str {
+        if val {
+            self.yes.as_deref()
+        } else {
+            self.no.as_deref()
+        }
+        .unwrap_or(fallback)
+    }
+
+    /// Takes the keys of the active localization unless other words or
+    /// keys were set.
+    pub(crate) fn localized(&self) -> ConfirmationLabels {
+        let mut rv = self.clone();
+        if rv.yes.is_none() && rv.no.is_none() && (rv.yes_key, rv.no_key) == ('y', 'n') {
+            localization::with(|l| {
+                rv.yes_key = l.yes_key;
+                rv.no_key = l.no_key;
+            });
+        }
+        rv
+    }
+
+    /// Returns the key hint (eg: `Y/n`) with the default in uppercase.
+    pub fn hint(&self, default: bool) -> String {
+        let (yes, no) = if default {
+            (
+                self.yes_key.to_uppercase().collect(),
+                self.no_key.to_string(),
+            )
+        } else {
+            (
+                self.yes_key.to_string(),
+                self.no_key.to_uppercase().collect(),
+            )
+        };
+        format!("{}/{}", yes, no)
+    }
+}
+
 /// Implements a theme for dialoguer.
 pub trait Theme {
+    /// Returns the localization of the built-in words used while this
+    /// theme renders a prompt, unless the prompt sets its own.
+    fn localization(&self) -> Option<&Localization> {
+        None
+    }
+
     /// Given a prompt this formats out what the prompt should look like (multiline).
     fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
         write!(f, "{}:", prompt)
     }
 
+    /// Formats the prompt of a checkbox list that allows at most `max`
+    /// checked items, of which `count` are checked.
+    fn format_limited_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        count: usize,
+        max: usize,
+    ) -> fmt::Result {
+        self.format_prompt(f, prompt)?;
+        write!(f, " ({})", chosen(count, max))
+    }
+
+    /// Formats the leading lines of a multi-line prompt.
+    fn format_prompt_text(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", text)
+    }
+
     /// Given a prompt this formats out what the prompt should look like (singleline).
     fn format_singleline_prompt(
         &self,
@@ -41,9 +204,65 @@ pub trait Theme {
         }
     }
 
+    /// Formats the placeholder shown in an empty input field.
+    fn format_input_placeholder(&self, f: &mut dyn fmt::Write, placeholder: &str) -> fmt::Result {
+        write!(f, "{}", placeholder)
+    }
+
+    /// Formats the line below an input that is updated while typing.
+    ///
+    /// `error` is why the typed text would be rejected, it is `None` for
+    /// a valid or empty text.
+    fn format_input_feedback(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        error: Option<&str>,
+    ) -> fmt::Result {
+        let count = localization::with(|l| {
+            Localization::fill(&l.characters, &[("count", &text.chars().count())])
+        });
+        f.write_str(&count)?;
+        match error {
+            Some(err) => write!(f, " - {}", err),
+            None => Ok(()),
+        }
+    }
+
+    /// Formats the counter shown after the text of an input limited to
+    /// `max` characters.
+    fn format_length_counter(&self, f: &mut dyn fmt::Write, len: usize, max: usize) -> fmt::Result {
+        write!(f, "{}/{}", len, max)
+    }
+
+    /// Formats the warning shown below an input while the typed text
+    /// fails a validator run on every key.
+    fn format_input_warning(&self, f: &mut dyn fmt::Write, warning: &str) -> fmt::Result {
+        localization::with(|l| write!(f, "{}: {}", l.warning, warning))
+    }
+
     /// Formats out an error.
     fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
-        write!(f, "error: {}", err)
+        localization::with(|l| write!(f, "{}: {}", l.error, err))
+    }
+
+    /// Formats the error shown for a value outside the allowed range of
+    /// an input.  The error is then formatted with `format_error`.
+    fn format_range_error(
+        &self,
+        f: &mut dyn fmt::Write,
+        min: Option<&str>,
+        max: Option<&str>,
+    ) -> fmt::Result {
+        let text = localization::with(|l| match (min, max) {
+            (Some(min), Some(max)) => {
+                Localization::fill(&l.range_between, &[("min", &min), ("max", &max)])
+            }
+            (Some(min), None) => Localization::fill(&l.range_min, &[("min", &min)]),
+            (None, Some(max)) => Localization::fill(&l.range_max, &[("max", &max)]),
+            (None, None) => String::new(),
+        });
+        f.write_str(&text)
     }
 
     /// Formats a confirmation prompt.
@@ -52,12 +271,11 @@ pub trait Theme {
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<bool>,
+        labels: &ConfirmationLabels,
     ) -> fmt::Result {
         write!(f, "{}", &prompt)?;
-        match default {
-            None => {}
-            Some(true) => write!(f, " [Y/n] ")?,
-            Some(false) => write!(f, " [y/N] ")?,
+        if let Some(default) = default {
+            write!(f, " [{}] ", labels.hint(default))?;
         }
         Ok(())
     }
@@ -68,7 +286,7 @@ pub trait Theme {
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<usize>,
-        choices: &[char],
+        choices: &[Key],
     ) -> fmt::Result {
         write!(f, "{}", &prompt)?;
         let strs = self._format_key_prompt(default, choices);
@@ -76,15 +294,57 @@ pub trait Theme {
         Ok(())
     }
 
-    fn _format_key_prompt(&self, default: Option<usize>, choices: &[char]) -> String {
+    /// Formats the seconds left until a prompt picks its default.
+    fn format_countdown(&self, f: &mut dyn fmt::Write, default: &str, seconds: u64) -> fmt::Result {
+        write!(f, "({})", countdown_text(default, seconds))
+    }
+
+    /// Formats the help line listing the described keys of a key prompt.
+    fn format_key_prompt_help(&self, f: &mut dyn fmt::Write, keys: &[(Key, &str)]) -> fmt::Result {
+        for (idx, &(ref key, desc)) in keys.iter().enumerate() {
+            write!(f, "{}", if idx == 0 { "" } else { ", " })?;
+            self.format_key(f, key)?;
+            write!(f, ": {}", desc)?;
+        }
+        Ok(())
+    }
+
+    /// Formats the name of a key.
+    fn format_key(&self, f: &mut dyn fmt::Write, key: &Key) -> fmt::Result {
+        match *key {
+            Key::Char(c) => write!(f, "{}", c),
+            Key::ArrowLeft => write!(f, "left"),
+            Key::ArrowRight => write!(f, "right"),
+            Key::ArrowUp => write!(f, "up"),
+            Key::ArrowDown => write!(f, "down"),
+            Key::Enter => write!(f, "enter"),
+            Key::Escape => write!(f, "esc"),
+            Key::Backspace => write!(f, "backspace"),
+            Key::Home => write!(f, "home"),
+            Key::End => write!(f, "end"),
+            Key::Tab => write!(f, "tab"),
+            Key::BackTab => write!(f, "shift-tab"),
+            Key::Del => write!(f, "del"),
+            Key::Insert => write!(f, "ins"),
+            Key::PageUp => write!(f, "pgup"),
+            Key::PageDown => write!(f, "pgdn"),
+            Key::UnknownEscSeq(ref seq) => match seq.as_slice() {
+                ['O', c @ 'P'..='S'] => write!(f, "F{}", *c as u8 - b'O'),
+                _ => write!(f, "esc-{}", seq.iter().collect::<String>()),
+            },
+            _ => write!(f, "?"),
+        }
+    }
+
+    fn _format_key_prompt(&self, default: Option<usize>, choices: &[Key]) -> String {
         let num = default.unwrap_or(100);
-        let choices = choices.to_owned();
         let mut strs = "".to_string();
         for (pos, choice) in choices.iter().enumerate() {
-            if pos == num {
-                strs.push(choice.to_ascii_uppercase());
-            } else {
-                strs.push(*choice);
+            match *choice {
+                Key::Char(c) if pos == num => strs.push(c.to_ascii_uppercase()),
+                _ => {
+                    self.format_key(&mut strs, choice).ok();
+                }
             }
             if pos != choices.len() - 1 {
                 strs.push('/');
@@ -99,8 +359,51 @@ pub trait Theme {
         f: &mut dyn fmt::Write,
         prompt: &str,
         selection: bool,
+        labels: &ConfirmationLabels,
+    ) -> fmt::Result {
+        localization::with(|l| {
+            let word = labels.word(selection, if selection { &l.yes } else { &l.no });
+            write!(f, "{} {}", &prompt, word)
+        })
+    }
+
+    /// Formats a prompt the user backed out of.
+    fn format_cancelled_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        localization::with(|l| write!(f, "{} {}", prompt, l.cancelled))
+    }
+
+    /// Formats a pause prompt.
+    fn format_pause_prompt(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", text)
+    }
+
+    /// Renders a pause prompt after the key was pressed.
+    fn format_pause_prompt_selection(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", text)
+    }
+
+    /// Formats the text of a message.
+    ///
+    /// If `boxed` is set the text is already wrapped to fit into a box
+    /// drawn around it.
+    fn format_message(
+        &self,
+        f: &mut dyn fmt::Write,
+        severity: Severity,
+        text: &str,
+        boxed: bool,
     ) -> fmt::Result {
-        write!(f, "{} {}", &prompt, if selection { "yes" } else { "no" })
+        if boxed {
+            write_box(
+                f,
+                &severity.label(),
+                text,
+                &Style::new(),
+                !unicode_supported(),
+            )
+        } else {
+            write!(f, "{}: {}", severity.label(), text)
+        }
     }
 
     /// Renders a prompt and a single selection made.
@@ -127,13 +430,157 @@ pub trait Theme {
         Ok(())
     }
 
-    /// Renders a prompt and multiple selections,
+    /// Renders a checkbox prompt and the checked items out of `total`
+    /// items.
+    fn format_checked_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
+        total: usize,
+    ) -> fmt::Result {
+        let _ = total;
+        self.format_multi_prompt_selection(f, prompt, selections)
+    }
+
+    /// Formats the prompt of a password input.
+    fn format_password_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        self.format_singleline_prompt(f, prompt, None)
+    }
+
+    /// Renders a password prompt after the password was entered.
+    ///
+    /// `mask` is the mask character the prompt was configured with.
     fn format_password_prompt_selection(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
+        mask: Option<char>,
+    ) -> fmt::Result {
+        let _ = mask;
+        let hidden = localization::with(|l| l.hidden.to_string());
+        self.format_single_prompt_selection(f, prompt, &hidden)
+    }
+
+    /// Formats the masked password while it is typed.
+    fn format_password_mask(&self, f: &mut dyn fmt::Write, mask: char, len: usize) -> fmt::Result {
+        for _ in 0..len {
+            f.write_char(mask)?;
+        }
+        Ok(())
+    }
+
+    /// Formats the header shown above each step of a wizard.
+    fn format_step_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        title: Option<&str>,
+        step: usize,
+        total: usize,
+    ) -> fmt::Result {
+        match title {
+            Some(title) => write!(f, "{} [{}/{}]", title, step, total),
+            None => write!(f, "[{}/{}]", step, total),
+        }
+    }
+
+    /// Formats the header row of a table selection.
+    fn format_table_header(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "  {}", text)
+    }
+
+    /// Formats the number shortcut put in front of a menu item.
+    fn format_shortcut(&self, f: &mut dyn fmt::Write, num: usize) -> fmt::Result {
+        write!(f, "{}) ", num)
+    }
+
+    /// Formats the position of an item in a list to order.  `width` is
+    /// the number of digits of the last position.
+    fn format_position(&self, f: &mut dyn fmt::Write, pos: usize, width: usize) -> fmt::Result {
+        write!(f, "{:>width$}. ", pos, width = width)
+    }
+
+    /// Formats the filter line of a filterable menu.
+    fn format_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        localization::with(|l| write!(f, "{}: {}", l.filter, filter))
+    }
+
+    /// Formats the line shown while the items of a search are fetched.
+    fn format_loading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        localization::with(|l| write!(f, "  {}", l.loading))
+    }
+
+    /// Formats a line of the preview shown below a menu.
+    fn format_preview_line(&self, f: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        write!(f, "  | {}", line)
+    }
+
+    /// Formats the help line of a prompt.
+    fn format_help(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", text)
+    }
+
+    /// Formats the marker put at the end of cut off text.
+    fn format_ellipsis(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(if unicode_supported() { "…" } else { "..." })
+    }
+
+    /// Formats the footer shown below a paged menu.
+    ///
+    /// `selected_count` is the number of checked items of a menu with
+    /// checkboxes and `total` the number of items.
+    fn format_list_footer(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize,
+    ) -> fmt::Result {
+        write!(f, "  {}", list_footer(page, pages, selected_count, total))
+    }
+
+    /// Formats an item of a menu, checkbox list or list to order.
+    ///
+    /// Unlike `format_filtered_selection` this knows the place of the item
+    /// in the list, eg: to number the items or to draw tree connectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::{ExtendedTheme, SimpleTheme, Theme};
+    ///
+    /// let theme = ExtendedTheme::extending(SimpleTheme).with_format_list_item(
+    ///     |base, f, text, matches, style, item| {
+    ///         f.write_str(if item.is_last() { "└ " } else { "├ " })?;
+    ///         base.format_filtered_selection(f, text, matches, style)
+    ///     },
+    /// );
+    /// ```
+    fn format_list_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> fmt::Result {
+        let _ = item;
+        self.format_filtered_selection(f, text, matches, style)
+    }
+
+    /// Formats a selection of a filtered menu.
+    ///
+    /// `matches` are the char indexes of `text` that matched the filter.
+    fn format_filtered_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
     ) -> fmt::Result {
-        self.format_single_prompt_selection(f, prompt, "[hidden]")
+        let _ = matches;
+        self.format_selection(f, text, style)
     }
 
     /// Formats a selection.
@@ -151,6 +598,8 @@ pub trait Theme {
                 SelectionStyle::CheckboxUncheckedUnselected => "  [ ] ",
                 SelectionStyle::CheckboxCheckedSelected => "> [x] ",
                 SelectionStyle::CheckboxCheckedUnselected => "  [x] ",
+                SelectionStyle::CheckboxPartialSelected => "> [-] ",
+                SelectionStyle::CheckboxPartialUnselected => "  [-] ",
                 SelectionStyle::MenuSelected => "> ",
                 SelectionStyle::MenuUnselected => "  ",
             },
@@ -224,44 +673,90 @@ impl Theme for CustomPromptCharacterTheme {
         Ok(())
     }
 }
-/// A colorful theme
-pub struct ColorfulTheme {
-    /// The style for default values in prompts and similar
-    pub defaults_style: Style,
-    /// The style for errors indicators
-    pub error_style: Style,
-    /// The style for user interface indicators
-    pub indicator_style: Style,
-    /// The style for inactive elements
-    pub inactive_style: Style,
-    /// The style for active elements
-    pub active_style: Style,
-    /// The style for values indicating "yes"
-    pub yes_style: Style,
-    /// The style for values indicating "no"
-    pub no_style: Style,
-    /// The style for values embedded in prompts
-    pub values_style: Style,
+/// Wraps a theme and puts custom symbols in front of its prompts,
+/// answers and errors.
+///
+/// The symbols are written before what the wrapped theme renders, so
+/// this is meant for themes without symbols of their own such as the
+/// `SimpleTheme`.  The symbols of the `ColoredTheme` are set with its
+/// builder methods instead.
+///
+/// # Examples
+///
+/// ```
+/// use dialoguer::theme::{DecoratedTheme, SimpleTheme};
+///
+/// let theme = DecoratedTheme::new(&SimpleTheme)
+///     .prompt_prefix("?")
+///     .success_symbol("+");
+/// ```
+pub struct DecoratedTheme<'a> {
+    inner: &'a dyn Theme,
+    prompt_prefix: Option<String>,
+    success_symbol: Option<String>,
+    error_symbol: Option<String>,
 }
 
-impl Default for ColorfulTheme {
-    fn default() -> ColorfulTheme {
-        ColorfulTheme {
-            defaults_style: Style::new().dim(),
-            error_style: Style::new().red(),
-            indicator_style: Style::new().cyan().bold(),
-            inactive_style: Style::new().dim(),
-            active_style: Style::new(),
-            yes_style: Style::new().green(),
-            no_style: Style::new().green(),
-            values_style: Style::new().cyan(),
+impl<'a> DecoratedTheme<'a> {
+    /// Wraps a theme without changing any symbols.
+    pub fn new(inner: &'a dyn Theme) -> DecoratedTheme<'a> {
+        DecoratedTheme {
+            inner,
+            prompt_prefix: None,
+            success_symbol: None,
+            error_symbol: None,
         }
     }
+
+    /// Sets the symbol in front of prompts.
+    pub fn prompt_prefix(mut self, val: &str) -> Self {
+        self.prompt_prefix = Some(val.into());
+        self
+    }
+
+    /// Sets the symbol in front of answered prompts.
+    pub fn success_symbol(mut self, val: &str) -> Self {
+        self.success_symbol = Some(val.into());
+        self
+    }
+
+    /// Sets the symbol in front of errors.
+    pub fn error_symbol(mut self, val: &str) -> Self {
+        self.error_symbol = Some(val.into());
+        self
+    }
 }
 
-impl Theme for ColorfulTheme {
+fn write_symbol(f: &mut dyn fmt::Write, symbol: &Option<String>) -> fmt::Result {
+    match *symbol {
+        Some(ref symbol) => write!(f, "{} ", symbol),
+        None => Ok(()),
+    }
+}
+
+impl<'a> Theme for DecoratedTheme<'a> {
+    fn localization(&self) -> Option<&Localization> {
+        self.inner.localization()
+    }
+
     fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
-        write!(f, "{}:", prompt)
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner.format_prompt(f, prompt)
+    }
+
+    fn format_limited_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        count: usize,
+        max: usize,
+    ) -> fmt::Result {
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner.format_limited_prompt(f, prompt, count, max)
+    }
+
+    fn format_prompt_text(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        self.inner.format_prompt_text(f, text)
     }
 
     fn format_singleline_prompt(
@@ -270,68 +765,617 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         default: Option<&str>,
     ) -> fmt::Result {
-        match default {
-            Some(default) => write!(
-                f,
-                "{} [{}]: ",
-                prompt,
-                self.defaults_style.apply_to(default)
-            ),
-            None => write!(f, "{}: ", prompt),
-        }
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner.format_singleline_prompt(f, prompt, default)
     }
 
-    fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
-        write!(f, "{}: {}", self.error_style.apply_to("error"), err)
+    fn format_input_placeholder(&self, f: &mut dyn fmt::Write, placeholder: &str) -> fmt::Result {
+        self.inner.format_input_placeholder(f, placeholder)
     }
 
-    fn format_confirmation_prompt(
+    fn format_input_feedback(
         &self,
         f: &mut dyn fmt::Write,
-        prompt: &str,
-        default: Option<bool>,
+        text: &str,
+        error: Option<&str>,
     ) -> fmt::Result {
-        write!(f, "{}", &prompt)?;
-        match default {
-            None => {}
-            Some(true) => write!(f, " {} ", self.defaults_style.apply_to("[Y/n]"))?,
-            Some(false) => write!(f, " {} ", self.defaults_style.apply_to("[y/N]"))?,
-        }
-        Ok(())
+        self.inner.format_input_feedback(f, text, error)
     }
 
-    fn format_confirmation_prompt_selection(
+    fn format_length_counter(&self, f: &mut dyn fmt::Write, len: usize, max: usize) -> fmt::Result {
+        self.inner.format_length_counter(f, len, max)
+    }
+
+    fn format_input_warning(&self, f: &mut dyn fmt::Write, warning: &str) -> fmt::Result {
+        self.inner.format_input_warning(f, warning)
+    }
+
+    fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        write_symbol(f, &self.error_symbol)?;
+        self.inner.format_error(f, err)
+    }
+
+    fn format_range_error(
         &self,
         f: &mut dyn fmt::Write,
-        prompt: &str,
-        selection: bool,
+        min: Option<&str>,
+        max: Option<&str>,
     ) -> fmt::Result {
-        write!(
-            f,
-            "{} {}",
-            &prompt,
-            if selection {
-                self.yes_style.apply_to("yes")
-            } else {
-                self.no_style.apply_to("no")
-            }
-        )
+        self.inner.format_range_error(f, min, max)
     }
 
-    fn format_single_prompt_selection(
+    fn format_confirmation_prompt(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
-        sel: &str,
+        default: Option<bool>,
+        labels: &ConfirmationLabels,
     ) -> fmt::Result {
-        write!(f, "{}: {}", prompt, self.values_style.apply_to(sel))
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner
+            .format_confirmation_prompt(f, prompt, default, labels)
     }
 
-    fn format_multi_prompt_selection(
+    fn format_key_prompt(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
-        selections: &[&str],
+        default: Option<usize>,
+        choices: &[Key],
+    ) -> fmt::Result {
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner.format_key_prompt(f, prompt, default, choices)
+    }
+
+    fn format_countdown(&self, f: &mut dyn fmt::Write, default: &str, seconds: u64) -> fmt::Result {
+        self.inner.format_countdown(f, default, seconds)
+    }
+
+    fn format_key_prompt_help(&self, f: &mut dyn fmt::Write, keys: &[(Key, &str)]) -> fmt::Result {
+        self.inner.format_key_prompt_help(f, keys)
+    }
+
+    fn format_key(&self, f: &mut dyn fmt::Write, key: &Key) -> fmt::Result {
+        self.inner.format_key(f, key)
+    }
+
+    fn _format_key_prompt(&self, default: Option<usize>, choices: &[Key]) -> String {
+        self.inner._format_key_prompt(default, choices)
+    }
+
+    fn format_confirmation_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selection: bool,
+        labels: &ConfirmationLabels,
+    ) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner
+            .format_confirmation_prompt_selection(f, prompt, selection, labels)
+    }
+
+    fn format_cancelled_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        self.inner.format_cancelled_prompt(f, prompt)
+    }
+
+    fn format_pause_prompt(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        self.inner.format_pause_prompt(f, text)
+    }
+
+    fn format_pause_prompt_selection(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner.format_pause_prompt_selection(f, text)
+    }
+
+    fn format_message(
+        &self,
+        f: &mut dyn fmt::Write,
+        severity: Severity,
+        text: &str,
+        boxed: bool,
+    ) -> fmt::Result {
+        self.inner.format_message(f, severity, text, boxed)
+    }
+
+    fn format_single_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner.format_single_prompt_selection(f, prompt, sel)
+    }
+
+    fn format_multi_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
+    ) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner
+            .format_multi_prompt_selection(f, prompt, selections)
+    }
+
+    fn format_checked_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
+        total: usize,
+    ) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner
+            .format_checked_prompt_selection(f, prompt, selections, total)
+    }
+
+    fn format_password_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        mask: Option<char>,
+    ) -> fmt::Result {
+        write_symbol(f, &self.success_symbol)?;
+        self.inner.format_password_prompt_selection(f, prompt, mask)
+    }
+
+    fn format_password_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        write_symbol(f, &self.prompt_prefix)?;
+        self.inner.format_password_prompt(f, prompt)
+    }
+
+    fn format_password_mask(&self, f: &mut dyn fmt::Write, mask: char, len: usize) -> fmt::Result {
+        self.inner.format_password_mask(f, mask, len)
+    }
+
+    fn format_step_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        title: Option<&str>,
+        step: usize,
+        total: usize,
+    ) -> fmt::Result {
+        self.inner.format_step_header(f, title, step, total)
+    }
+
+    fn format_table_header(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        self.inner.format_table_header(f, text)
+    }
+
+    fn format_shortcut(&self, f: &mut dyn fmt::Write, num: usize) -> fmt::Result {
+        self.inner.format_shortcut(f, num)
+    }
+
+    fn format_position(&self, f: &mut dyn fmt::Write, pos: usize, width: usize) -> fmt::Result {
+        self.inner.format_position(f, pos, width)
+    }
+
+    fn format_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        self.inner.format_filter(f, filter)
+    }
+
+    fn format_loading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.inner.format_loading(f)
+    }
+
+    fn format_preview_line(&self, f: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        self.inner.format_preview_line(f, line)
+    }
+
+    fn format_help(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        self.inner.format_help(f, text)
+    }
+
+    fn format_ellipsis(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.inner.format_ellipsis(f)
+    }
+
+    fn format_list_footer(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize,
+    ) -> fmt::Result {
+        self.inner
+            .format_list_footer(f, page, pages, selected_count, total)
+    }
+
+    fn format_list_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> fmt::Result {
+        self.inner.format_list_item(f, text, matches, style, item)
+    }
+
+    fn format_filtered_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+    ) -> fmt::Result {
+        self.inner
+            .format_filtered_selection(f, text, matches, style)
+    }
+
+    fn format_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        style: SelectionStyle,
+    ) -> fmt::Result {
+        self.inner.format_selection(f, text, style)
+    }
+}
+
+/// Generates `ExtendedTheme` with a field and a builder method for
+/// each hook of `Theme`.
+macro_rules! extended_theme {
+    ($($with:ident => $hook:ident($($arg:ident: $ty:ty),*);)*) => {
+        /// Wraps a theme and replaces some of its hooks with closures.
+        ///
+        /// This avoids implementing `Theme` for a small tweak of an
+        /// existing theme.  Each closure is called with the wrapped theme
+        /// so that it can fall back to it.  Hooks of the wrapped theme
+        /// that call other hooks do not see the replaced ones.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use dialoguer::theme::{ColorfulTheme, ExtendedTheme, Theme};
+        ///
+        /// let theme = ExtendedTheme::extending(ColorfulTheme::default())
+        ///     .with_format_error(|base, f, err| base.format_error(f, &err.to_uppercase()));
+        /// ```
+        pub struct ExtendedTheme<T> {
+            base: T,
+            localization: Option<Localization>,
+            $($hook: Option<Box<dyn Fn(&T, &mut dyn fmt::Write, $($ty),*) -> fmt::Result + Send + Sync>>,)*
+        }
+
+        impl<T: Theme> ExtendedTheme<T> {
+            /// Wraps a theme without replacing any hooks.
+            pub fn extending(base: T) -> ExtendedTheme<T> {
+                ExtendedTheme {
+                    base,
+                    localization: None,
+                    $($hook: None,)*
+                }
+            }
+
+            /// Sets the localization of the built-in words.
+            pub fn localization(mut self, val: Localization) -> Self {
+                self.localization = Some(val);
+                self
+            }
+
+            $(
+                #[doc = concat!("Replaces `", stringify!($hook), "` of the wrapped theme.")]
+                pub fn $with<F>(mut self, hook: F) -> Self
+                where
+                    F: Fn(&T, &mut dyn fmt::Write, $($ty),*) -> fmt::Result + Send + Sync + 'static,
+                {
+                    self.$hook = Some(Box::new(hook));
+                    self
+                }
+            )*
+        }
+
+        impl<T: Theme> Theme for ExtendedTheme<T> {
+            fn localization(&self) -> Option<&Localization> {
+                self.localization.as_ref().or_else(|| self.base.localization())
+            }
+
+            $(
+                fn $hook(&self, f: &mut dyn fmt::Write, $($arg: $ty),*) -> fmt::Result {
+                    match self.$hook {
+                        Some(ref hook) => hook(&self.base, f, $($arg),*),
+                        None => self.base.$hook(f, $($arg),*),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+extended_theme! {
+    with_format_prompt => format_prompt(prompt: &str);
+    with_format_limited_prompt => format_limited_prompt(prompt: &str, count: usize, max: usize);
+    with_format_prompt_text => format_prompt_text(text: &str);
+    with_format_singleline_prompt => format_singleline_prompt(
+        prompt: &str,
+        default: Option<&str>
+    );
+    with_format_input_placeholder => format_input_placeholder(placeholder: &str);
+    with_format_input_feedback => format_input_feedback(text: &str, error: Option<&str>);
+    with_format_length_counter => format_length_counter(len: usize, max: usize);
+    with_format_input_warning => format_input_warning(warning: &str);
+    with_format_error => format_error(err: &str);
+    with_format_range_error => format_range_error(min: Option<&str>, max: Option<&str>);
+    with_format_confirmation_prompt => format_confirmation_prompt(
+        prompt: &str,
+        default: Option<bool>,
+        labels: &ConfirmationLabels
+    );
+    with_format_key_prompt => format_key_prompt(
+        prompt: &str,
+        default: Option<usize>,
+        choices: &[Key]
+    );
+    with_format_countdown => format_countdown(default: &str, seconds: u64);
+    with_format_key_prompt_help => format_key_prompt_help(keys: &[(Key, &str)]);
+    with_format_key => format_key(key: &Key);
+    with_format_confirmation_prompt_selection => format_confirmation_prompt_selection(
+        prompt: &str,
+        selection: bool,
+        labels: &ConfirmationLabels
+    );
+    with_format_cancelled_prompt => format_cancelled_prompt(prompt: &str);
+    with_format_pause_prompt => format_pause_prompt(text: &str);
+    with_format_pause_prompt_selection => format_pause_prompt_selection(text: &str);
+    with_format_message => format_message(severity: Severity, text: &str, boxed: bool);
+    with_format_single_prompt_selection => format_single_prompt_selection(
+        prompt: &str,
+        sel: &str
+    );
+    with_format_multi_prompt_selection => format_multi_prompt_selection(
+        prompt: &str,
+        selections: &[&str]
+    );
+    with_format_checked_prompt_selection => format_checked_prompt_selection(
+        prompt: &str,
+        selections: &[&str],
+        total: usize
+    );
+    with_format_password_prompt => format_password_prompt(prompt: &str);
+    with_format_password_prompt_selection => format_password_prompt_selection(
+        prompt: &str,
+        mask: Option<char>
+    );
+    with_format_password_mask => format_password_mask(mask: char, len: usize);
+    with_format_step_header => format_step_header(
+        title: Option<&str>,
+        step: usize,
+        total: usize
+    );
+    with_format_table_header => format_table_header(text: &str);
+    with_format_shortcut => format_shortcut(num: usize);
+    with_format_position => format_position(pos: usize, width: usize);
+    with_format_filter => format_filter(filter: &str);
+    with_format_loading => format_loading();
+    with_format_preview_line => format_preview_line(line: &str);
+    with_format_help => format_help(text: &str);
+    with_format_ellipsis => format_ellipsis();
+    with_format_list_footer => format_list_footer(
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize
+    );
+    with_format_list_item => format_list_item(
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem
+    );
+    with_format_filtered_selection => format_filtered_selection(
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle
+    );
+    with_format_selection => format_selection(text: &str, style: SelectionStyle);
+}
+
+/// A colorful theme
+pub struct ColorfulTheme {
+    /// The style for default values in prompts and similar
+    pub defaults_style: Style,
+    /// The style for errors indicators
+    pub error_style: Style,
+    /// The style for warning indicators
+    pub warning_style: Style,
+    /// The style for user interface indicators
+    pub indicator_style: Style,
+    /// The style for inactive elements
+    pub inactive_style: Style,
+    /// The style for active elements
+    pub active_style: Style,
+    /// The style for values indicating "yes"
+    pub yes_style: Style,
+    /// The style for values indicating "no"
+    pub no_style: Style,
+    /// The style for values embedded in prompts
+    pub values_style: Style,
+    /// The style for placeholders in empty input fields
+    pub placeholder_style: Style,
+    /// The style for the characters of items matching a filter
+    pub matched_style: Style,
+}
+
+impl Default for ColorfulTheme {
+    fn default() -> ColorfulTheme {
+        let theme = ColorfulTheme {
+            defaults_style: Style::new().dim(),
+            error_style: Style::new().red(),
+            warning_style: Style::new().yellow(),
+            indicator_style: Style::new().cyan().bold(),
+            inactive_style: Style::new().dim(),
+            active_style: Style::new(),
+            yes_style: Style::new().green(),
+            no_style: Style::new().green(),
+            values_style: Style::new().cyan(),
+            placeholder_style: Style::new().dim(),
+            matched_style: Style::new().bold().underlined(),
+        };
+        match colors_from_env() {
+            Some(val) => theme.colors(val),
+            None => theme,
+        }
+    }
+}
+
+impl ColorfulTheme {
+    /// Forces colors on or off for all styles of the theme.
+    ///
+    /// By default colors are disabled if `NO_COLOR` is set and forced
+    /// if `CLICOLOR_FORCE` or `FORCE_COLOR` is set.  Otherwise the
+    /// terminal decides.
+    pub fn colors(mut self, val: bool) -> Self {
+        for style in [
+            &mut self.defaults_style,
+            &mut self.error_style,
+            &mut self.warning_style,
+            &mut self.indicator_style,
+            &mut self.inactive_style,
+            &mut self.active_style,
+            &mut self.yes_style,
+            &mut self.no_style,
+            &mut self.values_style,
+            &mut self.placeholder_style,
+            &mut self.matched_style,
+        ] {
+            *style = style.clone().force_styling(val);
+        }
+        self
+    }
+}
+
+impl Theme for ColorfulTheme {
+    fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        write!(f, "{}:", prompt)
+    }
+
+    fn format_singleline_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<&str>,
+    ) -> fmt::Result {
+        match default {
+            Some(default) => write!(
+                f,
+                "{} [{}]: ",
+                prompt,
+                self.defaults_style.apply_to(default)
+            ),
+            None => write!(f, "{}: ", prompt),
+        }
+    }
+
+    fn format_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        localization::with(|l| write!(f, "{}: {}", self.error_style.apply_to(&l.error), err))
+    }
+
+    fn format_input_placeholder(&self, f: &mut dyn fmt::Write, placeholder: &str) -> fmt::Result {
+        write!(f, "{}", self.placeholder_style.apply_to(placeholder))
+    }
+
+    fn format_input_warning(&self, f: &mut dyn fmt::Write, warning: &str) -> fmt::Result {
+        let style = self.warning_style.clone().dim();
+        localization::with(|l| write!(f, "{}: {}", style.apply_to(&l.warning), warning))
+    }
+
+    fn format_confirmation_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<bool>,
+        labels: &ConfirmationLabels,
+    ) -> fmt::Result {
+        write!(f, "{}", &prompt)?;
+        if let Some(default) = default {
+            write!(
+                f,
+                " {} ",
+                self.defaults_style
+                    .apply_to(format!("[{}]", labels.hint(default)))
+            )?;
+        }
+        Ok(())
+    }
+
+    fn format_confirmation_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selection: bool,
+        labels: &ConfirmationLabels,
+    ) -> fmt::Result {
+        localization::with(|l| {
+            write!(
+                f,
+                "{} {}",
+                &prompt,
+                if selection {
+                    self.yes_style.apply_to(labels.word(true, &l.yes))
+                } else {
+                    self.no_style.apply_to(labels.word(false, &l.no))
+                }
+            )
+        })
+    }
+
+    fn format_cancelled_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        localization::with(|l| {
+            write!(
+                f,
+                "{} {}",
+                prompt,
+                self.defaults_style.apply_to(&l.cancelled)
+            )
+        })
+    }
+
+    fn format_countdown(&self, f: &mut dyn fmt::Write, default: &str, seconds: u64) -> fmt::Result {
+        let text = format!("({})", countdown_text(default, seconds));
+        write!(f, "{}", self.defaults_style.apply_to(text))
+    }
+
+    fn format_pause_prompt(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", self.defaults_style.apply_to(text))
+    }
+
+    fn format_message(
+        &self,
+        f: &mut dyn fmt::Write,
+        severity: Severity,
+        text: &str,
+        boxed: bool,
+    ) -> fmt::Result {
+        let style = match severity {
+            Severity::Info => &self.indicator_style,
+            Severity::Warning => &self.warning_style,
+            Severity::Error => &self.error_style,
+        };
+        if boxed {
+            write_box(f, &severity.label(), text, style, !unicode_supported())
+        } else {
+            write!(f, "{}: {}", style.apply_to(severity.label()), text)
+        }
+    }
+
+    fn format_single_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        write!(f, "{}: {}", prompt, self.values_style.apply_to(sel))
+    }
+
+    fn format_multi_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
     ) -> fmt::Result {
         write!(f, "{}: ", prompt)?;
         for (idx, sel) in selections.iter().enumerate() {
@@ -345,232 +1389,1149 @@ impl Theme for ColorfulTheme {
         Ok(())
     }
 
+    fn format_step_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        title: Option<&str>,
+        step: usize,
+        total: usize,
+    ) -> fmt::Result {
+        if let Some(title) = title {
+            write!(f, "{} ", title)?;
+        }
+        write!(
+            f,
+            "{}",
+            self.defaults_style
+                .apply_to(format!("[{}/{}]", step, total))
+        )
+    }
+
+    fn format_table_header(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "  {}", self.defaults_style.apply_to(text))
+    }
+
+    fn format_preview_line(&self, f: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        let bar = if unicode_supported() { "│" } else { "|" };
+        write!(f, "  {} {}", self.defaults_style.apply_to(bar), line)
+    }
+
+    fn format_help(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", self.defaults_style.apply_to(text))
+    }
+
+    fn format_list_footer(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize,
+    ) -> fmt::Result {
+        let text = list_footer(page, pages, selected_count, total);
+        write!(f, "  {}", self.defaults_style.apply_to(text))
+    }
+
+    fn format_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.indicator_style.apply_to("/"),
+            self.values_style.apply_to(filter)
+        )
+    }
+
+    fn format_limited_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        count: usize,
+        max: usize,
+    ) -> fmt::Result {
+        self.format_prompt(f, prompt)?;
+        write!(f, " {}", self.defaults_style.apply_to(chosen(count, max)))
+    }
+
+    fn format_list_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> fmt::Result {
+        // the active item is not dimmed so that the cursor stays visible
+        if !item.disabled || matches!(style, SelectionStyle::CheckboxUncheckedSelected) {
+            return self.format_filtered_selection(f, text, matches, style);
+        }
+        let mut buf = String::new();
+        self.format_filtered_selection(&mut buf, text, matches, style)?;
+        write!(
+            f,
+            "{}",
+            self.placeholder_style.apply_to(strip_ansi_codes(&buf))
+        )
+    }
+
     fn format_selection(
         &self,
         f: &mut dyn fmt::Write,
         text: &str,
         st: SelectionStyle,
     ) -> fmt::Result {
+        self.format_filtered_selection(f, text, &[], st)
+    }
+
+    fn format_filtered_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        st: SelectionStyle,
+    ) -> fmt::Result {
+        let active = highlight(text, matches, &self.active_style, &self.matched_style);
+        let inactive = highlight(text, matches, &self.inactive_style, &self.matched_style);
         match st {
-            SelectionStyle::CheckboxUncheckedSelected => write!(
-                f,
-                "{} [ ] {}",
-                self.indicator_style.apply_to(">"),
-                self.active_style.apply_to(text)
-            ),
+            SelectionStyle::CheckboxUncheckedSelected => {
+                write!(f, "{} [ ] {}", self.indicator_style.apply_to(">"), active)
+            }
             SelectionStyle::CheckboxUncheckedUnselected => {
-                write!(f, "  [ ] {}", self.inactive_style.apply_to(text))
+                write!(f, "  [ ] {}", inactive)
             }
             SelectionStyle::CheckboxCheckedSelected => write!(
                 f,
                 "{} [{}] {}",
                 self.indicator_style.apply_to(">"),
                 self.indicator_style.apply_to("x"),
-                self.active_style.apply_to(text),
-            ),
-            SelectionStyle::CheckboxCheckedUnselected => write!(
-                f,
-                "  [{}] {}",
-                self.indicator_style.apply_to("x"),
-                self.inactive_style.apply_to(text)
+                active,
             ),
-            SelectionStyle::MenuSelected => write!(
+            SelectionStyle::CheckboxCheckedUnselected => {
+                write!(f, "  [{}] {}", self.indicator_style.apply_to("x"), inactive)
+            }
+            SelectionStyle::CheckboxPartialSelected => write!(
                 f,
-                "{} {}",
+                "{} [{}] {}",
                 self.indicator_style.apply_to(">"),
-                self.active_style.apply_to(text)
+                self.indicator_style.apply_to("-"),
+                active,
             ),
-            SelectionStyle::MenuUnselected => write!(f, "  {}", self.inactive_style.apply_to(text)),
+            SelectionStyle::CheckboxPartialUnselected => {
+                write!(f, "  [{}] {}", self.indicator_style.apply_to("-"), inactive)
+            }
+            SelectionStyle::MenuSelected => {
+                write!(f, "{} {}", self.indicator_style.apply_to(">"), active)
+            }
+            SelectionStyle::MenuUnselected => write!(f, "  {}", inactive),
+        }
+    }
+}
+
+/// Callback invoked with the prompt and the answer once a prompt is done.
+pub(crate) type CompleteFn = dyn Fn(&str, &str);
+pub(crate) type CompleteCallback = Box<CompleteFn>;
+
+thread_local! {
+    static REPORTED: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Returns the lines reported on this thread since the last call, eg: to
+/// clear the answers of a wizard step again.
+pub(crate) fn take_reported() -> String {
+    REPORTED.with(|reported| std::mem::take(&mut *reported.borrow_mut()))
+}
+
+/// Helper struct to conveniently render a theme to a term.
+pub(crate) struct TermThemeRenderer<'a> {
+    term: &'a Term,
+    theme: &'a dyn Theme,
+    frame: String,
+    prompt_frame: String,
+    // the rows written below the cursor, eg: the help of a key prompt
+    below: usize,
+    prompts_reset_height: bool,
+    alternate_screen: bool,
+    cursor_hidden: bool,
+    report: bool,
+    report_cancelled: bool,
+    truncate: bool,
+    max_width: Option<usize>,
+    on_complete: Option<&'a CompleteFn>,
+    _localization: Option<localization::Scope>,
+    _guard: PanicGuard,
+}
+
+impl<'a> TermThemeRenderer<'a> {
+    pub fn new(term: &'a Term, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
+        TermThemeRenderer {
+            term,
+            theme,
+            frame: String::new(),
+            prompt_frame: String::new(),
+            below: 0,
+            prompts_reset_height: true,
+            alternate_screen: false,
+            cursor_hidden: false,
+            report: true,
+            report_cancelled: true,
+            truncate: false,
+            max_width: None,
+            on_complete: None,
+            _localization: theme.localization().map(localization::Scope::enter),
+            _guard: PanicGuard::new(term),
+        }
+    }
+
+    pub fn set_prompts_reset_height(&mut self, val: bool) {
+        self.prompts_reset_height = val;
+    }
+
+    /// Enables or disables writing the final answer of a prompt.
+    pub fn set_report(&mut self, val: bool) {
+        self.report = val;
+    }
+
+    /// Enables or disables writing the line reporting a cancelled prompt.
+    pub fn set_report_cancelled(&mut self, val: bool) {
+        self.report_cancelled = val;
+    }
+
+    /// Enables or disables cutting off selections at the terminal width.
+    pub fn set_truncate(&mut self, val: bool) {
+        self.truncate = val;
+    }
+
+    /// Confines the output to fewer columns than the terminal has.
+    /// Selections are cut off at this width.
+    pub fn set_max_width(&mut self, val: Option<usize>) {
+        self.max_width = val;
+    }
+
+    /// Uses a localization other than the one of the theme.
+    pub fn set_localization(&mut self, val: Option<&Localization>) {
+        if let Some(val) = val {
+            // leaves the scope of the theme before entering the new one
+            self._localization = None;
+            self._localization = Some(localization::Scope::enter(val));
+        }
+    }
+
+    /// Sets the callback invoked with the prompt and the answer.
+    pub fn set_on_complete(&mut self, f: Option<&'a CompleteFn>) {
+        self.on_complete = f;
+    }
+
+    fn complete(&self, prompt: &str, answer: &str) -> io::Result<()> {
+        if let Some(f) = self.on_complete {
+            f(prompt, answer);
+        }
+        session::answer(prompt, answer)
+    }
+
+    pub fn term(&self) -> &Term {
+        self.term
+    }
+
+    /// The width that output is wrapped to.
+    pub fn width(&self) -> usize {
+        match self.max_width {
+            Some(max_width) => self.term_width().min(max_width.max(1)),
+            None => self.term_width(),
+        }
+    }
+
+    /// The width of the terminal.  Typed input may be wider than `width`,
+    /// so the rows to clear are counted with this one.
+    fn term_width(&self) -> usize {
+        self.term.size().1 as usize
+    }
+
+    /// Switches to the alternate screen of the terminal until the answer
+    /// is reported or the renderer is dropped.
+    pub fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        if self.term.is_term() && !self.alternate_screen {
+            self.term.write_str("\x1b[?1049h")?;
+            self.alternate_screen = true;
+        }
+        Ok(())
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        if self.alternate_screen {
+            self.term.write_str("\x1b[?1049l")?;
+            self.alternate_screen = false;
+            self.frame.clear();
+            self.prompt_frame.clear();
+        }
+        Ok(())
+    }
+
+    /// Hides the cursor until the answer is reported or the renderer is
+    /// dropped.
+    pub fn hide_cursor(&mut self) -> io::Result<()> {
+        if self.term.is_term() && !self.cursor_hidden {
+            self.term.hide_cursor()?;
+            self.cursor_hidden = true;
+        }
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        if self.cursor_hidden {
+            self.term.show_cursor()?;
+            self.cursor_hidden = false;
+        }
+        Ok(())
+    }
+
+    pub fn add_line(&mut self) {
+        self.frame.push('\n');
+    }
+
+    /// Records the input typed after a prompt so it is cleared with it.
+    pub fn add_input(&mut self, input: &str) {
+        self.frame.push_str(input);
+        self.frame.push('\n');
+    }
+
+    /// Returns the column the cursor was left at by the last write.
+    pub fn column(&self) -> usize {
+        measure_text_width(self.frame.rsplit('\n').next().unwrap_or(""))
+    }
+
+    fn write_formatted_str<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(io::Error::other)?;
+        let buf = wrap_text(&buf, self.width());
+        self.frame.push_str(&buf);
+        self.term.write_str(&buf)
+    }
+
+    fn write_formatted_line<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(io::Error::other)?;
+        let buf = wrap_text(&buf, self.width());
+        self.frame.push_str(&buf);
+        self.frame.push('\n');
+        self.term.write_line(&buf)
+    }
+
+    fn write_formatted_prompt<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        self.write_formatted_line(f)?;
+        if self.prompts_reset_height {
+            self.prompt_frame = std::mem::take(&mut self.frame);
+        }
+        Ok(())
+    }
+
+    fn write_report<F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result>(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        self.leave_alternate_screen()?;
+        self.show_cursor()?;
+        if !self.report {
+            return Ok(());
+        }
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(io::Error::other)?;
+        let buf = wrap_text(&buf, self.width());
+        REPORTED.with(|reported| {
+            let mut reported = reported.borrow_mut();
+            reported.push_str(&buf);
+            reported.push('\n');
+        });
+        self.write_formatted_prompt(|_, out| out.write_str(&buf))
+    }
+
+    /// Writes the leading lines of a multi-line prompt and returns the
+    /// line the prompt is asked on.
+    fn prompt_text<'p>(&mut self, prompt: &'p str) -> io::Result<Cow<'p, str>> {
+        if let Some(idx) = prompt.rfind('\n') {
+            let text = &prompt[..idx];
+            self.write_formatted_line(|this, buf| this.theme.format_prompt_text(buf, text))?;
+        }
+        Ok(question_line(prompt))
+    }
+
+    pub fn error(&mut self, err: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_error(buf, err))
+    }
+
+    pub fn prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        self.write_formatted_prompt(|this, buf| this.theme.format_prompt(buf, &prompt))
+    }
+
+    /// Renders the prompt of a checkbox list with a limit as part of the
+    /// frame, so that it is redrawn along with the items.
+    pub fn limited_prompt(&mut self, prompt: &str, count: usize, max: usize) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_limited_prompt(buf, &prompt, count, max)
+        })
+    }
+
+    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            this.theme.format_singleline_prompt(buf, &prompt, default)
+        })
+    }
+
+    pub fn input_placeholder(&mut self, placeholder: &str) -> io::Result<()> {
+        let mut buf = String::new();
+        self.theme
+            .format_input_placeholder(&mut buf, placeholder)
+            .map_err(io::Error::other)?;
+        let width = measure_text_width(&buf);
+        self.term.write_str(&buf)?;
+        if width > 0 {
+            self.term.move_cursor_left(width)?;
+        }
+        Ok(())
+    }
+
+    pub fn clear_input_placeholder(&mut self, placeholder: &str) -> io::Result<()> {
+        let mut buf = String::new();
+        self.theme
+            .format_input_placeholder(&mut buf, placeholder)
+            .map_err(io::Error::other)?;
+        let width = measure_text_width(&buf);
+        if width > 0 {
+            self.term.write_str(&" ".repeat(width))?;
+            self.term.move_cursor_left(width)?;
+        }
+        Ok(())
+    }
+
+    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            write!(buf, "\r")?;
+            this.theme.format_password_prompt(buf, &prompt)
+        })
+    }
+
+    pub fn confirmation_prompt(
+        &mut self,
+        prompt: &str,
+        default: Option<bool>,
+        labels: &ConfirmationLabels,
+    ) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            this.theme
+                .format_confirmation_prompt(buf, &prompt, default, labels)
+        })
+    }
+
+    pub fn key_prompt(
+        &mut self,
+        prompt: &str,
+        default: Option<usize>,
+        choices: &[Key],
+        help: &[(Key, &str)],
+    ) -> io::Result<()> {
+        let prompt = self.prompt_text(prompt)?;
+        let mut buf = String::new();
+        self.theme
+            .format_key_prompt(&mut buf, &prompt, default, choices)
+            .map_err(io::Error::other)?;
+        let buf = wrap_text(&buf, self.width());
+        self.write_formatted_str(|_, out| out.write_str(&buf))?;
+        if !help.is_empty() {
+            // render the help below and return to the end of the prompt
+            let mut help_buf = String::new();
+            self.theme
+                .format_key_prompt_help(&mut help_buf, help)
+                .map_err(io::Error::other)?;
+            let help_buf = wrap_text(&help_buf, self.width());
+            self.term.write_str(&format!("\n{}", help_buf))?;
+            self.below = text_rows(&help_buf, self.term_width()) + 1;
+            self.term.move_cursor_up(self.below)?;
+            self.term.write_str("\r")?;
+            self.term.move_cursor_right(self.column())?;
+        }
+        Ok(())
+    }
+
+    /// Shows a countdown to picking `default` after the prompt, leaving
+    /// the cursor in place.
+    pub fn countdown(&mut self, default: &str, seconds: u64) -> io::Result<()> {
+        let mut buf = String::new();
+        self.theme
+            .format_countdown(&mut buf, default, seconds)
+            .map_err(io::Error::other)?;
+        self.term.write_str(&format!("{}\x1b[0K", buf))?;
+        self.term.move_cursor_left(measure_text_width(&buf))
+    }
+
+    /// Removes the countdown after the prompt.
+    pub fn clear_countdown(&mut self) -> io::Result<()> {
+        self.term.write_str("\x1b[0K")
+    }
+
+    /// Signals that a key was ignored.
+    pub fn invalid_key(&self, feedback: InvalidKeyFeedback) -> io::Result<()> {
+        if !self.term.is_term() {
+            return Ok(());
+        }
+        match feedback {
+            InvalidKeyFeedback::Silent => Ok(()),
+            InvalidKeyFeedback::Bell => self.term.write_str("\x07"),
+            InvalidKeyFeedback::Flash => {
+                self.term.write_str("\x1b[?5h")?;
+                self.term.flush()?;
+                thread::sleep(Duration::from_millis(100));
+                self.term.write_str("\x1b[?5l")
+            }
         }
     }
-}
 
-/// Helper struct to conveniently render a theme to a term.
-pub(crate) struct TermThemeRenderer<'a> {
-    term: &'a Term,
-    theme: &'a dyn Theme,
-    height: usize,
-    prompt_height: usize,
-    prompts_reset_height: bool,
-}
+    /// Clears the rows written below the cursor and returns to it.
+    pub fn clear_below(&mut self) -> io::Result<()> {
+        for _ in 0..self.below {
+            self.term.move_cursor_down(1)?;
+            self.term.clear_line()?;
+        }
+        self.term.move_cursor_up(self.below)?;
+        self.below = 0;
+        Ok(())
+    }
 
-impl<'a> TermThemeRenderer<'a> {
-    pub fn new(term: &'a Term, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
-        TermThemeRenderer {
-            term,
-            theme,
-            height: 0,
-            prompt_height: 0,
-            prompts_reset_height: true,
+    pub fn confirmation_prompt_selection(
+        &mut self,
+        prompt: &str,
+        sel: bool,
+        labels: &ConfirmationLabels,
+    ) -> io::Result<()> {
+        self.write_report(|this, buf| {
+            this.theme.format_confirmation_prompt_selection(
+                buf,
+                &question_line(prompt),
+                sel,
+                labels,
+            )
+        })?;
+        let word = localization::with(|l| {
+            labels
+                .word(sel, if sel { &l.yes } else { &l.no })
+                .to_string()
+        });
+        self.complete(prompt, &word)
+    }
+
+    pub fn cancelled(&mut self, prompt: &str) -> io::Result<()> {
+        if !self.report_cancelled {
+            self.leave_alternate_screen()?;
+            return self.show_cursor();
         }
+        self.write_report(|this, buf| {
+            this.theme
+                .format_cancelled_prompt(buf, &question_line(prompt))
+        })
     }
 
-    pub fn set_prompts_reset_height(&mut self, val: bool) {
-        self.prompts_reset_height = val;
+    pub fn pause_prompt(&mut self, text: &str) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| this.theme.format_pause_prompt(buf, text))
     }
 
-    pub fn term(&self) -> &Term {
-        self.term
+    /// Renders a message.  A boxed message is wrapped to fit its box.
+    pub fn message(&mut self, severity: Severity, text: &str, boxed: bool) -> io::Result<()> {
+        let text = if boxed {
+            wrap_text(text, self.width().saturating_sub(4))
+        } else {
+            text.to_string()
+        };
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_message(buf, severity, &text, boxed)
+        })
     }
 
-    pub fn add_line(&mut self) {
-        self.height += 1;
+    /// Clears the message unless it is reported once acknowledged.
+    pub fn message_acknowledged(&mut self, text: &str) -> io::Result<()> {
+        self.leave_alternate_screen()?;
+        if !self.report {
+            self.clear()?;
+        }
+        self.complete(text, "")
     }
 
-    fn write_formatted_str<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
+    pub fn pause_prompt_selection(&mut self, text: &str) -> io::Result<()> {
+        self.write_report(|this, buf| this.theme.format_pause_prompt_selection(buf, text))?;
+        self.complete(text, "")
+    }
+
+    pub fn key_prompt_selection(&mut self, prompt: &str, sel: &Key) -> io::Result<()> {
+        let mut name = String::new();
+        self.theme
+            .format_key(&mut name, sel)
+            .map_err(io::Error::other)?;
+        self.single_prompt_selection(prompt, &name)
+    }
+
+    pub fn single_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        self.write_report(|this, buf| {
+            this.theme
+                .format_single_prompt_selection(buf, &question_line(prompt), &isolate(sel))
+        })?;
+        self.complete(prompt, sel)
+    }
+
+    pub fn multi_prompt_selection(&mut self, prompt: &str, selections: &[&str]) -> io::Result<()> {
+        let isolated: Vec<_> = selections.iter().map(|sel| isolate(sel)).collect();
+        let isolated: Vec<&str> = isolated.iter().map(|sel| &**sel).collect();
+        self.write_report(|this, buf| {
+            this.theme
+                .format_multi_prompt_selection(buf, &question_line(prompt), &isolated)
+        })?;
+        self.complete(prompt, &selections.join(", "))
+    }
+
+    pub fn checked_prompt_selection(
         &mut self,
-        f: F,
+        prompt: &str,
+        selections: &[&str],
+        total: usize,
     ) -> io::Result<()> {
-        let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count();
-        self.term.write_str(&buf)
+        let isolated: Vec<_> = selections.iter().map(|sel| isolate(sel)).collect();
+        let isolated: Vec<&str> = isolated.iter().map(|sel| &**sel).collect();
+        self.write_report(|this, buf| {
+            this.theme.format_checked_prompt_selection(
+                buf,
+                &question_line(prompt),
+                &isolated,
+                total,
+            )
+        })?;
+        self.complete(prompt, &selections.join(", "))
     }
 
-    fn write_formatted_line<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
+    pub fn password_prompt_selection(
         &mut self,
-        f: F,
+        prompt: &str,
+        mask: Option<char>,
     ) -> io::Result<()> {
+        self.write_report(|this, buf| {
+            this.theme
+                .format_password_prompt_selection(buf, &question_line(prompt), mask)
+        })?;
+        self.complete(prompt, "")
+    }
+
+    pub fn password_mask(&self, mask: Option<char>, len: usize) -> io::Result<String> {
         let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count() + 1;
-        self.term.write_line(&buf)
+        if let Some(mask) = mask {
+            self.theme
+                .format_password_mask(&mut buf, mask, len)
+                .map_err(io::Error::other)?;
+        }
+        Ok(buf)
     }
 
-    fn write_formatted_prompt<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
+    pub fn step_header(
         &mut self,
-        f: F,
+        title: Option<&str>,
+        step: usize,
+        total: usize,
     ) -> io::Result<()> {
-        self.write_formatted_line(f)?;
-        if self.prompts_reset_height {
-            self.prompt_height = self.height;
-            self.height = 0;
-        }
-        Ok(())
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_step_header(buf, title, step, total)
+        })
     }
 
-    pub fn error(&mut self, err: &str) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| this.theme.format_error(buf, err))
+    pub fn table_header(&mut self, text: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_table_header(buf, text))
     }
 
-    pub fn prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_prompt(buf, prompt))
+    pub fn help(&mut self, text: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_help(buf, text))
     }
 
-    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            this.theme.format_singleline_prompt(buf, prompt, default)
+    pub fn list_footer(
+        &mut self,
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme
+                .format_list_footer(buf, page, pages, selected_count, total)
         })
     }
 
-    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            write!(buf, "\r")?;
-            this.theme.format_singleline_prompt(buf, prompt, None)
-        })
+    pub fn filter(&mut self, filter: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_filter(buf, filter))
     }
 
-    pub fn confirmation_prompt(&mut self, prompt: &str, default: Option<bool>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            this.theme.format_confirmation_prompt(buf, prompt, default)
-        })
+    pub fn loading(&mut self) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_loading(buf))
     }
 
-    pub fn key_prompt(
+    pub fn selection(
         &mut self,
-        prompt: &str,
-        default: Option<usize>,
-        choices: &[char],
+        text: &str,
+        style: SelectionStyle,
+        item: ListItem,
     ) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            this.theme.format_key_prompt(buf, prompt, default, &choices)
-        })
+        self.filtered_selection(text, &[], style, item)
     }
 
-    pub fn confirmation_prompt_selection(&mut self, prompt: &str, sel: bool) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme
-                .format_confirmation_prompt_selection(buf, prompt, sel)
-        })
+    /// Renders a selection with the chars at `matches` highlighted.
+    pub fn filtered_selection(
+        &mut self,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> io::Result<()> {
+        match isolate(text) {
+            Cow::Owned(text) => {
+                // the isolate mark in front shifts the matched chars
+                let matches: Vec<_> = matches.iter().map(|idx| idx + 1).collect();
+                self.write_selection(&text, &matches, style, item)
+            }
+            Cow::Borrowed(text) => self.write_selection(text, matches, style, item),
+        }
     }
 
-    pub fn key_prompt_selection(&mut self, prompt: &str, sel: char) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme
-                .format_single_prompt_selection(buf, prompt, &sel.to_string())
-        })
+    fn write_selection(
+        &mut self,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> io::Result<()> {
+        if !self.truncate && self.max_width.is_none() {
+            return self.write_formatted_line(|this, buf| {
+                this.theme.format_list_item(buf, text, matches, style, item)
+            });
+        }
+        let mut buf = String::new();
+        self.theme
+            .format_list_item(&mut buf, text, matches, style, item)
+            .map_err(io::Error::other)?;
+        let line = self.cut_off(&buf)?;
+        self.write_formatted_line(|_, out| out.write_str(&line))
     }
 
-    pub fn single_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_single_prompt_selection(buf, prompt, sel)
-        })
+    /// Cuts off a line at the width of the terminal.
+    fn cut_off(&self, line: &str) -> io::Result<String> {
+        let mut tail = String::new();
+        self.theme
+            .format_ellipsis(&mut tail)
+            .map_err(io::Error::other)?;
+        Ok(truncate_str(line, self.width(), &tail).into_owned())
     }
 
-    pub fn multi_prompt_selection(&mut self, prompt: &str, selections: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme
-                .format_multi_prompt_selection(buf, prompt, selections)
+    /// Renders `height` lines of a preview.  Lines are cut off at the
+    /// width of the terminal.
+    pub fn preview(&mut self, text: &str, height: usize) -> io::Result<()> {
+        let mut lines = text.lines();
+        for _ in 0..height {
+            let mut buf = String::new();
+            self.theme
+                .format_preview_line(&mut buf, lines.next().unwrap_or(""))
+                .map_err(io::Error::other)?;
+            let line = self.cut_off(&buf)?;
+            self.write_formatted_line(|_, out| out.write_str(&line))?;
+        }
+        Ok(())
+    }
+
+    /// Renders selections next to each other, padded to `width`.
+    pub fn selection_row(
+        &mut self,
+        cells: &[(&str, SelectionStyle)],
+        width: usize,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            for (idx, &(text, style)) in cells.iter().enumerate() {
+                let mut cell = String::new();
+                this.theme
+                    .format_selection(&mut cell, &isolate(text), style)?;
+                if idx + 1 < cells.len() {
+                    let pad = (width + GRID_GAP).saturating_sub(measure_text_width(&cell));
+                    cell.push_str(&" ".repeat(pad));
+                }
+                buf.write_str(&cell)?;
+            }
+            Ok(())
         })
     }
 
-    pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_password_prompt_selection(buf, prompt)
+    /// The width of the widest item when rendered as a selection.
+    pub fn selection_width(&self, items: &[String]) -> io::Result<usize> {
+        let mut width = 0;
+        for item in items {
+            for &style in &[SelectionStyle::MenuSelected, SelectionStyle::MenuUnselected] {
+                let mut buf = String::new();
+                self.theme
+                    .format_selection(&mut buf, item, style)
+                    .map_err(io::Error::other)?;
+                width = width.max(measure_text_width(&buf));
+            }
+        }
+        Ok(width)
+    }
+
+    /// Renders an item of a menu read line by line on dumb terminals.
+    pub fn numbered_line(&mut self, text: &str, num: usize) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_shortcut(buf, num)?;
+            buf.write_str(&isolate(text))
         })
     }
 
-    pub fn selection(&mut self, text: &str, style: SelectionStyle) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| this.theme.format_selection(buf, text, style))
+    /// Renders a selection prefixed by its number shortcut.  Items past
+    /// the ninth are indented instead.
+    pub fn numbered_selection(
+        &mut self,
+        text: &str,
+        num: usize,
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> io::Result<()> {
+        let mut prefix = String::new();
+        if num > 9 {
+            prefix = " ".repeat(self.shortcut_width()?);
+        } else {
+            self.theme
+                .format_shortcut(&mut prefix, num)
+                .map_err(io::Error::other)?;
+        }
+        self.write_selection(&format!("{}{}", prefix, isolate(text)), &[], style, item)
+    }
+
+    /// Renders a selection prefixed by its position in the list.
+    pub fn positioned_selection(
+        &mut self,
+        text: &str,
+        style: SelectionStyle,
+        item: ListItem,
+    ) -> io::Result<()> {
+        let mut prefix = String::new();
+        let width = item.count.to_string().len();
+        self.theme
+            .format_position(&mut prefix, item.index + 1, width)
+            .map_err(io::Error::other)?;
+        self.write_selection(&format!("{}{}", prefix, isolate(text)), &[], style, item)
+    }
+
+    /// The width of the number shortcuts.
+    pub fn shortcut_width(&self) -> io::Result<usize> {
+        let mut buf = String::new();
+        self.theme
+            .format_shortcut(&mut buf, 9)
+            .map_err(io::Error::other)?;
+        Ok(measure_text_width(&buf))
     }
 
     pub fn clear(&mut self) -> io::Result<()> {
+        let width = self.term_width();
+        self.term.clear_last_lines(
+            text_rows(&self.frame, width) + text_rows(&self.prompt_frame, width),
+        )?;
+        self.frame.clear();
+        Ok(())
+    }
+
+    /// Clears everything rendered so far after the prompt was interrupted.
+    fn tear_down(&mut self) -> io::Result<()> {
+        if self.term.is_term() {
+            self.term.clear_line()?;
+            self.clear()?;
+            self.term.clear_to_end_of_screen()?;
+            self.term.show_cursor()?;
+        }
+        self.term.flush()
+    }
+
+    /// Returns the number of rows taken up by the last prompt.
+    pub fn prompt_rows(&self) -> usize {
+        text_rows(&self.prompt_frame, self.term_width())
+    }
+
+    /// Returns the text written since the last prompt and forgets it, eg:
+    /// to clear it later with `clear_text`.
+    pub fn take_frame(&mut self) -> String {
+        std::mem::take(&mut self.frame)
+    }
+
+    /// Clears the rows taken up by `text` written earlier.
+    pub fn clear_text(&mut self, text: &str) -> io::Result<()> {
+        self.term
+            .clear_last_lines(text_rows(text, self.term_width()))
+    }
+
+    pub fn clear_preserve_prompt(&mut self) -> io::Result<()> {
         self.term
-            .clear_last_lines(self.height + self.prompt_height)?;
-        self.height = 0;
+            .clear_last_lines(text_rows(&self.frame, self.term_width()))?;
+        self.frame.clear();
         Ok(())
     }
+}
 
-    pub fn clear_preserve_prompt(&mut self, size_vec: &[usize]) -> io::Result<()> {
-        let mut new_height = self.height;
-        //Check each item size, increment on finding an overflow
-        for size in size_vec {
-            if *size > self.term.size().1 as usize {
-                new_height += 1;
+impl<'a> Drop for TermThemeRenderer<'a> {
+    fn drop(&mut self) {
+        self.leave_alternate_screen().ok();
+        self.show_cursor().ok();
+        if let Some(signal) = session::take_interrupt() {
+            self.tear_down().ok();
+            if signal {
+                session::raise_interrupt();
             }
         }
-        self.term.clear_last_lines(new_height)?;
-        self.height = 0;
-        Ok(())
     }
 }
 
+/// Returns the localized text of a countdown, eg: `auto-selecting 'yes'
+/// in 7s`.
+fn countdown_text(default: &str, seconds: u64) -> String {
+    localization::with(|l| {
+        Localization::fill(
+            &l.countdown,
+            &[("default", &default), ("seconds", &seconds)],
+        )
+    })
+}
+
+/// Returns the line of a multi-line prompt that the prompt is asked on.
+pub(crate) fn question_line(prompt: &str) -> Cow<'_, str> {
+    isolate(prompt.rsplit('\n').next().unwrap_or(prompt))
+}
+
+/// Returns whether a char is written right-to-left, eg: Hebrew or Arabic.
+fn is_rtl(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08ff | 0xfb1d..=0xfdff | 0xfe70..=0xfeff | 0x10800..=0x10fff | 0x1e800..=0x1efff
+    )
+}
+
+/// Wraps text with right-to-left chars in directional isolate marks.
+///
+/// Terminals that reorder right-to-left text otherwise mix it up with
+/// the indicators and separators around it, eg: the arrow of the active
+/// item ends up in the middle of the item.  The marks take no space.
+pub(crate) fn isolate(text: &str) -> Cow<'_, str> {
+    if text.chars().any(is_rtl) {
+        Cow::Owned(format!("\u{2068}{}\u{2069}", text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns the number of rows above the cursor taken up by `text` once
+/// written to a terminal `width` columns wide.
+///
+/// The text is measured at the current width so that output the
+/// terminal reflowed after a resize is cleared completely.
+fn text_rows(text: &str, width: usize) -> usize {
+    let line_rows = |line: &str| {
+        let len = measure_text_width(line);
+        if width == 0 || len == 0 {
+            1
+        } else {
+            len.div_ceil(width)
+        }
+    };
+    let mut lines = text.split('\n');
+    let last = lines.next_back().map_or(1, line_rows);
+    lines.map(line_rows).sum::<usize>() + last - 1
+}
+
+/// Wraps the lines of a text at spaces so that no line is wider than
+/// `width` columns.  Words longer than a line are broken.  ANSI escape
+/// sequences are kept and do not take up any space.
+fn wrap_text(text: &str, width: usize) -> String {
+    if width == 0 {
+        return text.to_string();
+    }
+    let mut rv = String::with_capacity(text.len());
+    for (idx, line) in text.split('\n').enumerate() {
+        if idx > 0 {
+            rv.push('\n');
+        }
+        wrap_line(line, width, &mut rv);
+    }
+    rv
+}
+
+fn wrap_line(line: &str, width: usize, out: &mut String) {
+    let mut col = 0;
+    // position of the last space in `out` and the column after it
+    let mut last_space: Option<(usize, usize)> = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            out.push(c);
+            if chars.peek() == Some(&'[') {
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c != '[' && ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        let w = char_width(c);
+        if c == ' ' && col + w > width {
+            out.push('\n');
+            col = 0;
+            last_space = None;
+            continue;
+        }
+        while col > 0 && col + w > width {
+            if let Some((pos, col_after)) = last_space.take() {
+                out.replace_range(pos..pos + 1, "\n");
+                col -= col_after;
+            } else {
+                out.push('\n');
+                col = 0;
+            }
+        }
+        if c == ' ' {
+            last_space = Some((out.len(), col + w));
+        }
+        out.push(c);
+        col += w;
+    }
+}
+
+/// Applies `style` to `text` and `matched` on top of it to the chars at
+/// the `matches` indexes.
+fn highlight(text: &str, matches: &[usize], style: &Style, matched: &Style) -> String {
+    if matches.is_empty() {
+        return style.apply_to(text).to_string();
+    }
+    let mut rv = String::new();
+    let mut chars = text.chars().enumerate().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let is_match = matches.contains(&idx);
+        let mut run = c.to_string();
+        while let Some(&(idx, c)) = chars.peek() {
+            if matches.contains(&idx) != is_match {
+                break;
+            }
+            run.push(c);
+            chars.next();
+        }
+        if is_match {
+            rv.push_str(&style.apply_to(matched.apply_to(run)).to_string());
+        } else {
+            rv.push_str(&style.apply_to(run).to_string());
+        }
+    }
+    rv
+}
+
+/// Returns the text of a list footer, eg: `page 2/7, 3 of 20 selected`.
+fn list_footer(page: usize, pages: usize, selected_count: Option<usize>, total: usize) -> String {
+    localization::with(|l| {
+        let page = Localization::fill(&l.page, &[("page", &page), ("pages", &pages)]);
+        let items = match selected_count {
+            Some(count) => Localization::fill(&l.selected, &[("count", &count), ("total", &total)]),
+            None => Localization::fill(&l.items, &[("total", &total)]),
+        };
+        format!("{}, {}", page, items)
+    })
+}
+
+/// Returns the number of checked items out of those allowed, eg: `2/3 chosen`.
+fn chosen(count: usize, max: usize) -> String {
+    localization::with(|l| Localization::fill(&l.chosen, &[("count", &count), ("max", &max)]))
+}
+
+/// Draws a box with a title around the lines of a text, optionally
+/// with ASCII characters only.
+fn write_box(
+    f: &mut dyn fmt::Write,
+    title: &str,
+    text: &str,
+    border: &Style,
+    ascii: bool,
+) -> fmt::Result {
+    let (h, v, corners) = if ascii {
+        ("-", "|", ['+', '+', '+', '+'])
+    } else {
+        ("─", "│", ['┌', '┐', '└', '┘'])
+    };
+    let title_width = measure_text_width(title);
+    let width = text
+        .lines()
+        .map(measure_text_width)
+        .max()
+        .unwrap_or(0)
+        .max(title_width + 2);
+    let top = format!(
+        "{}{} {} {}{}",
+        corners[0],
+        h,
+        title,
+        h.repeat(width - title_width - 1),
+        corners[1]
+    );
+    writeln!(f, "{}", border.apply_to(top))?;
+    for line in text.lines() {
+        let pad = " ".repeat(width - measure_text_width(line));
+        writeln!(
+            f,
+            "{} {}{} {}",
+            border.apply_to(v),
+            line,
+            pad,
+            border.apply_to(v)
+        )?;
+    }
+    let bottom = format!("{}{}{}", corners[2], h.repeat(width + 2), corners[3]);
+    write!(f, "{}", border.apply_to(bottom))
+}
+
+/// Returns the number of columns a character takes up.  Wide characters
+/// such as CJK ideographs and most emoji take up two.
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
 //=== START CUSTOM COLORED THEME ===
 #[allow(clippy::needless_doctest_main)]
 /// Provides a colored theme for dialoguer
 ///
 /// # Examples
 ///
-/// ```
+/// ```rust,no_run
 /// use dialoguer::Confirmation;
-/// use enquirer::ColoredTheme;
+/// use dialoguer::theme::ColoredTheme;
 ///
-/// fn main() {
-///     let prompt = Confirmation::with_theme(&ColoredTheme::default())
-///         .with_text("Do you want to continue?")
-///         .with_default(true);
+/// fn main() -> std::io::Result<()> {
+///     let theme = ColoredTheme::default();
+///     let mut prompt = Confirmation::with_theme(&theme);
+///     prompt.with_text("Do you want to continue?").default(true);
 ///
 ///     if prompt.interact()? {
 ///         println!("Looks like you want to continue");
 ///     } else {
 ///         println!("nevermind then :(");
 ///     }
+///     Ok(())
 /// }
 /// ```
 pub struct ColoredTheme {
@@ -579,60 +2540,244 @@ pub struct ColoredTheme {
     pub prefixes_style: Style,
     pub values_style: Style,
     pub errors_style: Style,
+    pub warnings_style: Style,
     pub selected_style: Style,
     pub unselected_style: Style,
+    pub placeholder_style: Style,
+    pub matched_style: Style,
+    /// Defaults to `?`
+    pub prompt_prefix: String,
+    /// Defaults to `?`
+    pub password_prefix: String,
+    /// Defaults to `›`
+    pub prompt_suffix: String,
+    /// Defaults to `✔`
+    pub success_prefix: String,
+    /// Defaults to `✘`
+    pub error_prefix: String,
+    /// Defaults to `·`
+    pub separator: String,
+    /// Defaults to `❯`
+    pub active_item_prefix: String,
+    /// Defaults to `✔`
+    pub checked_item_prefix: String,
+    /// Defaults to `◐`
+    pub partial_item_prefix: String,
     /// Defaults to `true`
     pub inline_selections: bool,
     /// Defaults to `false`
+    pub selection_count: bool,
+    /// Defaults to `false`
     pub is_sort: bool,
+    /// Defaults to `false` unless the console cannot show Unicode symbols
+    pub ascii: bool,
 }
 
 impl Default for ColoredTheme {
     fn default() -> Self {
-        ColoredTheme {
+        let theme = ColoredTheme {
             defaults_style: Style::new().yellow().bold(),
             prompts_style: Style::new().bold(),
             prefixes_style: Style::new().cyan(),
             values_style: Style::new().green(),
             errors_style: Style::new().red(),
+            warnings_style: Style::new().yellow(),
             selected_style: Style::new().cyan().bold(),
             unselected_style: Style::new(),
+            placeholder_style: Style::new().dim(),
+            matched_style: Style::new().bold().underlined(),
+            prompt_prefix: "?".into(),
+            password_prefix: "?".into(),
+            prompt_suffix: "›".into(),
+            success_prefix: "✔".into(),
+            error_prefix: "✘".into(),
+            separator: "·".into(),
+            active_item_prefix: "❯".into(),
+            checked_item_prefix: "✔".into(),
+            partial_item_prefix: "◐".into(),
             inline_selections: true,
+            selection_count: false,
             is_sort: true,
+            ascii: false,
+        };
+        let theme = if unicode_supported() {
+            theme
+        } else {
+            theme.ascii()
+        };
+        match colors_from_env() {
+            Some(val) => theme.colors(val),
+            None => theme,
         }
     }
-}
+}
+
+impl ColoredTheme {
+    /// Checkboxes print the selected values on the prompt line.
+    /// This option allows the user to customize whether
+    /// those will be printed on the prompts line or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::ColoredTheme;
+    ///
+    /// let theme = ColoredTheme::default().inline_selections(false);
+    /// ```
+    pub fn inline_selections(mut self, val: bool) -> Self {
+        self.inline_selections = val;
+        self
+    }
+
+    /// Shows how many items were checked, eg: "3 of 12 selected", in
+    /// place of the checked items on the prompt line of Checkboxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::ColoredTheme;
+    ///
+    /// let theme = ColoredTheme::default().selection_count(true);
+    /// ```
+    pub fn selection_count(mut self, val: bool) -> Self {
+        self.selection_count = val;
+        self
+    }
+
+    /// OrderList by default prints like Checkboxes. This function
+    /// allows the user to specify that the theme needs to use
+    /// a different style for sort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::ColoredTheme;
+    ///
+    /// let theme = ColoredTheme::default().set_sort(true);
+    /// ```
+    pub fn set_sort(mut self, val: bool) -> Self {
+        self.is_sort = val;
+        self
+    }
+
+    /// Replaces the Unicode symbols of the theme with ASCII characters
+    /// (`+ x > -` for `✔ ✘ ❯ ›`) and draws boxes with `+-|`.
+    ///
+    /// This is done by default on consoles that cannot show the Unicode
+    /// symbols, such as cmd.exe with a legacy code page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::ColoredTheme;
+    ///
+    /// let theme = ColoredTheme::default().ascii();
+    /// ```
+    pub fn ascii(mut self) -> Self {
+        self.prompt_suffix = "-".into();
+        self.success_prefix = "+".into();
+        self.error_prefix = "x".into();
+        self.separator = "-".into();
+        self.active_item_prefix = ">".into();
+        self.checked_item_prefix = "+".into();
+        self.partial_item_prefix = "~".into();
+        self.ascii = true;
+        self
+    }
 
-impl ColoredTheme {
-    /// Checkboxes print the selected values on the prompt line.
-    /// This option allows the user to customize whether
-    /// those will be printed on the prompts line or not.
+    /// Forces colors on or off for all styles of the theme.
+    ///
+    /// By default colors are disabled if `NO_COLOR` is set and forced
+    /// if `CLICOLOR_FORCE` or `FORCE_COLOR` is set.  Otherwise the
+    /// terminal decides.
     ///
     /// # Examples
     ///
     /// ```
-    /// use enquirer::ColoredTheme;
+    /// use dialoguer::theme::ColoredTheme;
     ///
-    /// let theme = ColoredTheme::default().inline_selections(false);
+    /// let theme = ColoredTheme::default().colors(false);
     /// ```
-    pub fn inline_selections(mut self, val: bool) -> Self {
-        self.inline_selections = val;
+    pub fn colors(mut self, val: bool) -> Self {
+        for style in [
+            &mut self.defaults_style,
+            &mut self.prompts_style,
+            &mut self.prefixes_style,
+            &mut self.values_style,
+            &mut self.errors_style,
+            &mut self.warnings_style,
+            &mut self.selected_style,
+            &mut self.unselected_style,
+            &mut self.placeholder_style,
+            &mut self.matched_style,
+        ] {
+            *style = style.clone().force_styling(val);
+        }
         self
     }
 
-    /// OrderList by default prints like Checkboxes. This function
-    /// allows the user to specify that the theme needs to use
-    /// a different style for sort.
+    /// Sets the symbol in front of prompts (`?` by default).
     ///
     /// # Examples
     ///
     /// ```
-    /// use enquirer::ColoredTheme;
+    /// use dialoguer::theme::ColoredTheme;
     ///
-    /// let theme = ColoredTheme::default().set_sort(true);
+    /// let theme = ColoredTheme::default()
+    ///     .prompt_prefix(">")
+    ///     .success_prefix("+")
+    ///     .error_prefix("x");
     /// ```
-    pub fn set_sort(mut self, val: bool) -> Self {
-        self.is_sort = val;
+    pub fn prompt_prefix(mut self, val: &str) -> Self {
+        self.prompt_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of password prompts (`?` by default).
+    pub fn password_prefix(mut self, val: &str) -> Self {
+        self.password_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol between a prompt and the input (`›` by default).
+    pub fn prompt_suffix(mut self, val: &str) -> Self {
+        self.prompt_suffix = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of answered prompts (`✔` by default).
+    pub fn success_prefix(mut self, val: &str) -> Self {
+        self.success_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of errors (`✘` by default).
+    pub fn error_prefix(mut self, val: &str) -> Self {
+        self.error_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol between a prompt and its answer (`·` by default).
+    pub fn separator(mut self, val: &str) -> Self {
+        self.separator = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of the active item (`❯` by default).
+    pub fn active_item_prefix(mut self, val: &str) -> Self {
+        self.active_item_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of checkbox items (`✔` by default).
+    pub fn checked_item_prefix(mut self, val: &str) -> Self {
+        self.checked_item_prefix = val.into();
+        self
+    }
+
+    /// Sets the symbol in front of partly checked items (`◐` by default).
+    pub fn partial_item_prefix(mut self, val: &str) -> Self {
+        self.partial_item_prefix = val.into();
         self
     }
 
@@ -650,7 +2795,7 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {}",
-            self.errors_style.apply_to("✘"),
+            self.errors_style.apply_to(self.error_prefix.as_str()),
             self.errors_style.apply_to(err)
         )?;
 
@@ -662,9 +2807,9 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {} {}",
-            self.prefixes_style.apply_to("?"),
+            self.prefixes_style.apply_to(self.prompt_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
-            self.defaults_style.apply_to("›")
+            self.defaults_style.apply_to(self.prompt_suffix.as_str())
         )?;
 
         Ok(())
@@ -685,15 +2830,77 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {}{} {} ",
-            self.prefixes_style.apply_to("?"),
+            self.prefixes_style.apply_to(self.prompt_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
             self.defaults_style.apply_to(details),
-            self.defaults_style.apply_to("›"),
+            self.defaults_style.apply_to(self.prompt_suffix.as_str()),
         )?;
 
         Ok(())
     }
 
+    // Input Placeholder
+    fn format_input_placeholder(&self, f: &mut dyn fmt::Write, placeholder: &str) -> fmt::Result {
+        write!(f, "{}", self.placeholder_style.apply_to(placeholder))
+    }
+
+    fn format_input_feedback(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        error: Option<&str>,
+    ) -> fmt::Result {
+        let count = localization::with(|l| {
+            Localization::fill(&l.characters, &[("count", &text.chars().count())])
+        });
+        write!(f, "  {}", self.placeholder_style.apply_to(count))?;
+        match error {
+            Some(err) => write!(
+                f,
+                " {} {}",
+                self.errors_style.apply_to(self.error_prefix.as_str()),
+                self.errors_style.apply_to(err)
+            ),
+            None if !text.is_empty() => write!(
+                f,
+                " {}",
+                self.values_style.apply_to(self.success_prefix.as_str())
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn format_length_counter(&self, f: &mut dyn fmt::Write, len: usize, max: usize) -> fmt::Result {
+        let style = if len >= max {
+            &self.warnings_style
+        } else {
+            &self.placeholder_style
+        };
+        write!(f, "{}", style.apply_to(format!("{}/{}", len, max)))
+    }
+
+    fn format_input_warning(&self, f: &mut dyn fmt::Write, warning: &str) -> fmt::Result {
+        let style = self.warnings_style.clone().dim();
+        let prefix = if self.ascii { "!" } else { "⚠" };
+        write!(
+            f,
+            "  {} {}",
+            style.apply_to(prefix),
+            style.apply_to(warning)
+        )
+    }
+
+    // Password
+    fn format_password_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.prefixes_style.apply_to(self.password_prefix.as_str()),
+            self.prompts_style.apply_to(prompt),
+            self.defaults_style.apply_to(self.prompt_suffix.as_str()),
+        )
+    }
+
     // Input Selection
     fn format_single_prompt_selection(
         &self,
@@ -704,9 +2911,9 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {} {} {}",
-            self.values_style.apply_to("✔"),
+            self.values_style.apply_to(self.success_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
-            self.defaults_style.apply_to("·"),
+            self.defaults_style.apply_to(self.separator.as_str()),
             self.values_style.apply_to(selection),
         )?;
 
@@ -719,26 +2926,25 @@ impl Theme for ColoredTheme {
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<bool>,
+        labels: &ConfirmationLabels,
     ) -> fmt::Result {
+        let hint = default.map(|default| format!("({})", labels.hint(default)));
+        let value = default.map(|default| bool_value(labels, default));
         let details = match default {
             None => self.empty(),
-            Some(true) => (
-                self.defaults_style.apply_to("(Y/n)"),
-                self.prefixes_style.apply_to("true"),
-            ),
-            Some(false) => (
-                self.defaults_style.apply_to("(y/N)"),
-                self.prefixes_style.apply_to("false"),
+            Some(_) => (
+                self.defaults_style.apply_to(hint.as_deref().unwrap_or("")),
+                self.prefixes_style.apply_to(value.as_deref().unwrap_or("")),
             ),
         };
 
         write!(
             f,
             "{} {} {} {} {} ",
-            self.prefixes_style.apply_to("?"),
+            self.prefixes_style.apply_to(self.prompt_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
             details.0,
-            self.defaults_style.apply_to("›"),
+            self.defaults_style.apply_to(self.prompt_suffix.as_str()),
             details.1,
         )?;
 
@@ -751,9 +2957,9 @@ impl Theme for ColoredTheme {
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<usize>,
-        choices: &[char],
+        choices: &[Key],
     ) -> fmt::Result {
-        let mut strs = self._format_key_prompt(default, &choices);
+        let mut strs = self._format_key_prompt(default, choices);
         strs.insert(0, '(');
         strs.push(')');
         let keys = self.defaults_style.apply_to(strs);
@@ -761,41 +2967,244 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {} {} {} ",
-            self.prefixes_style.apply_to("?"),
+            self.prefixes_style.apply_to(self.prompt_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
             keys,
-            self.defaults_style.apply_to("›"),
+            self.defaults_style.apply_to(self.prompt_suffix.as_str()),
         )?;
         Ok(())
     }
 
+    // Countdown
+    fn format_countdown(&self, f: &mut dyn fmt::Write, default: &str, seconds: u64) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.placeholder_style
+                .apply_to(countdown_text(default, seconds))
+        )
+    }
+
+    // Pause
+    fn format_pause_prompt(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.prefixes_style.apply_to(self.prompt_prefix.as_str()),
+            self.prompts_style.apply_to(text),
+            self.defaults_style.apply_to(self.prompt_suffix.as_str()),
+        )
+    }
+
+    // Message
+    fn format_message(
+        &self,
+        f: &mut dyn fmt::Write,
+        severity: Severity,
+        text: &str,
+        boxed: bool,
+    ) -> fmt::Result {
+        let (style, prefix) = match severity {
+            Severity::Info => (&self.prefixes_style, if self.ascii { "i" } else { "ℹ" }),
+            Severity::Warning => (&self.warnings_style, if self.ascii { "!" } else { "⚠" }),
+            Severity::Error => (&self.errors_style, self.error_prefix.as_str()),
+        };
+        if boxed {
+            write_box(f, &severity.label(), text, style, self.ascii)
+        } else {
+            write!(
+                f,
+                "{} {}",
+                style.apply_to(prefix),
+                self.prompts_style.apply_to(text)
+            )
+        }
+    }
+
+    // Pause Selection
+    fn format_pause_prompt_selection(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.values_style.apply_to(self.success_prefix.as_str()),
+            self.prompts_style.apply_to(text),
+        )
+    }
+
+    // Key Prompt Help
+    fn format_key_prompt_help(&self, f: &mut dyn fmt::Write, keys: &[(Key, &str)]) -> fmt::Result {
+        write!(f, "  ")?;
+        for (idx, &(ref key, desc)) in keys.iter().enumerate() {
+            let mut name = String::new();
+            self.format_key(&mut name, key)?;
+            write!(
+                f,
+                "{}{} {}",
+                if idx == 0 { "" } else { "  " },
+                self.prefixes_style.apply_to(name),
+                self.defaults_style.apply_to(desc)
+            )?;
+        }
+        Ok(())
+    }
+
     // Confirm Selection
     fn format_confirmation_prompt_selection(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
         selection: bool,
+        labels: &ConfirmationLabels,
     ) -> fmt::Result {
         write!(
             f,
             "{} {} {} {}",
-            self.values_style.apply_to("✔"),
+            self.values_style.apply_to(self.success_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
-            self.defaults_style.apply_to("·"),
-            self.values_style
-                .apply_to(if selection { "true" } else { "false" }),
+            self.defaults_style.apply_to(self.separator.as_str()),
+            self.values_style.apply_to(bool_value(labels, selection)),
         )?;
 
         Ok(())
     }
 
+    // Cancelled Prompt
+    fn format_cancelled_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.errors_style.apply_to(self.error_prefix.as_str()),
+            self.prompts_style.apply_to(prompt),
+            self.defaults_style.apply_to(self.separator.as_str()),
+            self.errors_style
+                .apply_to(localization::with(|l| l.cancelled.to_string())),
+        )
+    }
+
     // Password Selection
     fn format_password_prompt_selection(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
+        mask: Option<char>,
+    ) -> fmt::Result {
+        let mask = mask.unwrap_or('*').to_string().repeat(8);
+        self.format_single_prompt_selection(f, prompt, &mask)
+    }
+
+    // Password Mask
+    fn format_password_mask(&self, f: &mut dyn fmt::Write, mask: char, len: usize) -> fmt::Result {
+        let mask = mask.to_string().repeat(len);
+        write!(f, "{}", self.defaults_style.apply_to(mask))
+    }
+
+    // Wizard Step Header
+    fn format_step_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        title: Option<&str>,
+        step: usize,
+        total: usize,
+    ) -> fmt::Result {
+        if let Some(title) = title {
+            write!(f, "{} ", self.prompts_style.apply_to(title))?;
+        }
+        write!(
+            f,
+            "{}",
+            self.prefixes_style
+                .apply_to(format!("({}/{})", step, total))
+        )
+    }
+
+    // Table Header
+    fn format_table_header(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "  {}", self.prompts_style.apply_to(text))
+    }
+
+    // Filter
+    fn format_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.prefixes_style.apply_to(self.prompt_suffix.as_str()),
+            self.values_style.apply_to(filter)
+        )
+    }
+
+    fn format_loading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let text = localization::with(|l| l.loading.to_string());
+        write!(f, "  {}", self.placeholder_style.apply_to(text))
+    }
+
+    // Help
+    fn format_help(&self, f: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+        write!(f, "{}", self.placeholder_style.apply_to(text))
+    }
+
+    // Ellipsis
+    fn format_ellipsis(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(if self.ascii { "..." } else { "…" })
+    }
+
+    // List Footer
+    fn format_list_footer(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+        selected_count: Option<usize>,
+        total: usize,
+    ) -> fmt::Result {
+        let text = list_footer(page, pages, selected_count, total);
+        write!(f, "  {}", self.placeholder_style.apply_to(text))
+    }
+
+    // Preview
+    fn format_preview_line(&self, f: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        write!(
+            f,
+            "  {} {}",
+            self.defaults_style.apply_to(self.separator.as_str()),
+            self.placeholder_style.apply_to(line)
+        )
+    }
+
+    fn format_limited_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        count: usize,
+        max: usize,
+    ) -> fmt::Result {
+        self.format_prompt(f, prompt)?;
+        let style = if count >= max {
+            &self.warnings_style
+        } else {
+            &self.defaults_style
+        };
+        write!(f, " {}", style.apply_to(chosen(count, max)))
+    }
+
+    fn format_list_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+        item: ListItem,
     ) -> fmt::Result {
-        self.format_single_prompt_selection(f, prompt, "********")
+        // the active item is not dimmed so that the cursor stays visible
+        if !item.disabled || matches!(style, SelectionStyle::CheckboxUncheckedSelected) {
+            return self.format_filtered_selection(f, text, matches, style);
+        }
+        let mut buf = String::new();
+        self.format_filtered_selection(&mut buf, text, matches, style)?;
+        write!(
+            f,
+            "{}",
+            self.placeholder_style.apply_to(strip_ansi_codes(&buf))
+        )
     }
 
     // Selection
@@ -805,40 +3214,69 @@ impl Theme for ColoredTheme {
         text: &str,
         style: SelectionStyle,
     ) -> fmt::Result {
+        self.format_filtered_selection(f, text, &[], style)
+    }
+
+    fn format_filtered_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        matches: &[usize],
+        style: SelectionStyle,
+    ) -> fmt::Result {
+        let selected = highlight(text, matches, &self.selected_style, &self.matched_style);
+        let unselected = highlight(text, matches, &self.unselected_style, &self.matched_style);
+        // Keeps the items aligned with the active one for wide prefixes.
+        let pad = " ".repeat(measure_text_width(&self.active_item_prefix).max(1));
         let strings = match style {
             SelectionStyle::CheckboxCheckedSelected => (
-                self.values_style
-                    .apply_to(if self.is_sort { "❯" } else { "✔" }),
-                self.selected_style.apply_to(text),
+                self.values_style.apply_to(if self.is_sort {
+                    self.active_item_prefix.as_str()
+                } else {
+                    self.checked_item_prefix.as_str()
+                }),
+                selected,
             ),
             SelectionStyle::CheckboxCheckedUnselected => (
-                self.values_style.apply_to("✔"),
-                self.unselected_style.apply_to(text),
+                self.values_style
+                    .apply_to(self.checked_item_prefix.as_str()),
+                unselected,
+            ),
+            SelectionStyle::CheckboxPartialSelected => (
+                self.values_style
+                    .apply_to(self.partial_item_prefix.as_str()),
+                selected,
+            ),
+            SelectionStyle::CheckboxPartialUnselected => (
+                self.values_style
+                    .apply_to(self.partial_item_prefix.as_str()),
+                unselected,
             ),
             SelectionStyle::CheckboxUncheckedSelected => (
                 if self.is_sort {
-                    self.defaults_style.apply_to(" ")
+                    self.defaults_style.apply_to(pad.as_str())
                 } else {
-                    self.defaults_style.apply_to("✔")
+                    self.defaults_style
+                        .apply_to(self.checked_item_prefix.as_str())
                 },
-                self.selected_style.apply_to(text),
+                selected,
             ),
             SelectionStyle::CheckboxUncheckedUnselected => (
                 if self.is_sort {
-                    self.defaults_style.apply_to(" ")
+                    self.defaults_style.apply_to(pad.as_str())
                 } else {
-                    self.defaults_style.apply_to("✔")
+                    self.defaults_style
+                        .apply_to(self.checked_item_prefix.as_str())
                 },
-                self.unselected_style.apply_to(text),
+                unselected,
             ),
             SelectionStyle::MenuSelected => (
-                self.values_style.apply_to("❯"),
-                self.selected_style.apply_to(text),
-            ),
-            SelectionStyle::MenuUnselected => (
-                self.defaults_style.apply_to(" "),
-                self.unselected_style.apply_to(text),
+                self.values_style.apply_to(self.active_item_prefix.as_str()),
+                selected,
             ),
+            SelectionStyle::MenuUnselected => {
+                (self.defaults_style.apply_to(pad.as_str()), unselected)
+            }
         };
 
         write!(f, "{} {}", strings.0, strings.1)?;
@@ -856,16 +3294,14 @@ impl Theme for ColoredTheme {
         write!(
             f,
             "{} {} {}",
-            self.values_style.apply_to("✔"),
+            self.values_style.apply_to(self.success_prefix.as_str()),
             self.prompts_style.apply_to(prompt),
-            self.defaults_style.apply_to("·"),
+            self.defaults_style.apply_to(self.separator.as_str()),
         )?;
 
         if self.inline_selections {
-            let selections_last_index = selections.len() - 1;
-
             for (i, v) in selections.iter().enumerate() {
-                if i == selections_last_index {
+                if i + 1 == selections.len() {
                     write!(f, " {}", self.values_style.apply_to(v))?;
                 } else {
                     write!(f, " {},", self.values_style.apply_to(v))?;
@@ -875,12 +3311,278 @@ impl Theme for ColoredTheme {
 
         Ok(())
     }
+
+    fn format_checked_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
+        total: usize,
+    ) -> fmt::Result {
+        if !self.selection_count {
+            return self.format_multi_prompt_selection(f, prompt, selections);
+        }
+        let count = localization::with(|l| {
+            Localization::fill(
+                &l.selected,
+                &[("count", &selections.len()), ("total", &total)],
+            )
+        });
+        write!(
+            f,
+            "{} {} {} {}",
+            self.values_style.apply_to(self.success_prefix.as_str()),
+            self.prompts_style.apply_to(prompt),
+            self.defaults_style.apply_to(self.separator.as_str()),
+            self.values_style.apply_to(count),
+        )
+    }
+}
+
+/// Returns the word of the `ColoredTheme` for a confirmation answer.
+fn bool_value(labels: &ConfirmationLabels, val: bool) -> String {
+    localization::with(|l| {
+        let fallback = if val { &l.true_value } else { &l.false_value };
+        labels.word(val, fallback).to_string()
+    })
 }
 //=== END CUSTOM COLORED THEME ===
 
+pub use theme_colors::{color256, rgb, ColorDepth};
+#[cfg(feature = "serde")]
+pub use theme_config::{StylesConfig, SymbolsConfig, ThemeConfig};
+#[cfg(feature = "presets")]
+pub use theme_presets::PRESETS;
+
+/// Returns whether the environment forces colors on or off.
+///
+/// `CLICOLOR_FORCE` and `FORCE_COLOR` take precedence over `NO_COLOR`.
+pub(crate) fn colors_from_env() -> Option<bool> {
+    let is_set = |name| env::var_os(name).is_some_and(|val| !val.is_empty() && val != "0");
+    if is_set("CLICOLOR_FORCE") || is_set("FORCE_COLOR") {
+        Some(true)
+    } else if env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Returns `false` on consoles that show Unicode symbols as `?`.
+///
+/// The legacy Windows console (cmd.exe with an OEM code page) is the
+/// only known case.  Windows Terminal, ConEmu, VS Code and mintty are
+/// detected by their environment variables.
+pub(crate) fn unicode_supported() -> bool {
+    if !cfg!(windows) {
+        return true;
+    }
+    let is_set = |name| env::var_os(name).is_some_and(|val| !val.is_empty());
+    is_set("WT_SESSION")
+        || is_set("TERM_PROGRAM")
+        || is_set("TERM")
+        || env::var_os("ConEmuANSI").is_some_and(|val| val == "ON")
+}
+
+static DEFAULT_THEME: OnceLock<Box<dyn Theme + Send + Sync>> = OnceLock::new();
+
+/// Sets the theme used by prompts created without a theme.
+///
+/// This affects prompts created afterwards on any thread.  The default
+/// theme can only be set once, later calls hand their theme back as an
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use dialoguer::theme::ColoredTheme;
+///
+/// assert!(dialoguer::set_default_theme(ColoredTheme::default()).is_ok());
+/// ```
+pub fn set_default_theme<T: Theme + Send + Sync + 'static>(theme: T) -> Result<(), T> {
+    let mut theme = Some(theme);
+    DEFAULT_THEME.get_or_init(|| Box::new(theme.take().unwrap()));
+    match theme {
+        Some(theme) => Err(theme),
+        None => Ok(()),
+    }
+}
+
 /// Returns the default theme.
 ///
-/// (This returns the simple theme)
-pub(crate) fn get_default_theme() -> &'static dyn Theme {
-    &SimpleTheme
+/// This is the theme set with `set_default_theme` or the simple theme.
+pub fn get_default_theme() -> &'static dyn Theme {
+    match DEFAULT_THEME.get() {
+        Some(theme) => &**theme,
+        None => &SimpleTheme,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("short", 10), "short");
+        assert_eq!(
+            wrap_text("Do you want to continue?", 10),
+            "Do you\nwant to\ncontinue?"
+        );
+        assert_eq!(wrap_text("abcdefghijkl", 5), "abcde\nfghij\nkl");
+        assert_eq!(wrap_text("one\ntwo three", 5), "one\ntwo\nthree");
+        assert_eq!(
+            wrap_text("\x1b[1mbold text\x1b[0m", 4),
+            "\x1b[1mbold\ntext\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_text_rows() {
+        assert_eq!(text_rows("", 10), 0);
+        assert_eq!(text_rows("prompt", 10), 0);
+        assert_eq!(text_rows("one\ntwo\n", 10), 2);
+        assert_eq!(text_rows("0123456789\n", 10), 1);
+        assert_eq!(text_rows("0123456789ab\nc", 5), 3);
+        assert_eq!(text_rows("\x1b[1mbold\x1b[0m\n", 4), 1);
+    }
+
+    #[test]
+    fn test_countdown() {
+        let mut buf = String::new();
+        SimpleTheme.format_countdown(&mut buf, "yes", 7).unwrap();
+        assert_eq!(buf, "(auto-selecting 'yes' in 7s)");
+    }
+
+    #[test]
+    fn test_decorated_theme() {
+        let theme = DecoratedTheme::new(&SimpleTheme)
+            .prompt_prefix("?")
+            .error_symbol("x");
+        let mut buf = String::new();
+        theme
+            .format_singleline_prompt(&mut buf, "Name", None)
+            .unwrap();
+        theme.format_error(&mut buf, "oops").unwrap();
+        theme
+            .format_single_prompt_selection(&mut buf, "Name", "Ann")
+            .unwrap();
+        assert_eq!(buf, "? Name: x error: oopsName: Ann");
+    }
+
+    #[test]
+    fn test_extended_theme() {
+        let theme = ExtendedTheme::extending(SimpleTheme)
+            .with_format_error(|base, f, err| base.format_error(f, &err.to_uppercase()))
+            .with_format_key(|_, f, _| write!(f, "<key>"));
+        let mut buf = String::new();
+        theme.format_error(&mut buf, "oops").unwrap();
+        theme
+            .format_single_prompt_selection(&mut buf, "Name", "Ann")
+            .unwrap();
+        buf.push_str(&theme._format_key_prompt(Some(0), &[Key::Char('y'), Key::Enter]));
+        assert_eq!(buf, "error: OOPSName: AnnY/<key>");
+    }
+
+    #[test]
+    fn test_list_item() {
+        let theme = ExtendedTheme::extending(SimpleTheme).with_format_list_item(
+            |base, f, text, matches, style, item| {
+                f.write_str(if item.is_last() { "└ " } else { "├ " })?;
+                base.format_filtered_selection(f, text, matches, style)
+            },
+        );
+        let mut buf = String::new();
+        for index in 0..2 {
+            let item = ListItem {
+                index,
+                count: 2,
+                disabled: false,
+                score: None,
+            };
+            let style = SelectionStyle::MenuUnselected;
+            theme
+                .format_list_item(&mut buf, "x", &[], style, item)
+                .unwrap();
+        }
+        assert_eq!(buf, "├   x└   x");
+    }
+
+    #[test]
+    fn test_selection_count() {
+        let theme = ColoredTheme::default()
+            .colors(false)
+            .success_prefix("+")
+            .separator("-");
+        let mut buf = String::new();
+        theme
+            .format_checked_prompt_selection(&mut buf, "Pick", &["a", "b"], 5)
+            .unwrap();
+        assert_eq!(buf, "+ Pick - a, b");
+        buf.clear();
+        let theme = theme.selection_count(true);
+        theme
+            .format_checked_prompt_selection(&mut buf, "Pick", &["a", "b"], 5)
+            .unwrap();
+        assert_eq!(buf, "+ Pick - 2 of 5 selected");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let bold = Style::new().bold().force_styling(true);
+        assert_eq!(highlight("abc", &[], &Style::new(), &bold), "abc");
+        assert_eq!(
+            highlight("abcd", &[1, 2], &Style::new(), &bold),
+            "a\u{1b}[1mbc\u{1b}[0md"
+        );
+    }
+
+    #[test]
+    fn test_prompt_text() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.prompt("First\nSecond\nPick one").unwrap();
+        assert_eq!(render.prompt_rows(), 3);
+        assert_eq!(question_line("First\nPick one"), "Pick one");
+        assert_eq!(question_line("Pick one"), "Pick one");
+    }
+
+    #[test]
+    fn test_isolate() {
+        assert_eq!(isolate("Pick one"), "Pick one");
+        assert_eq!(isolate("בחר אחד"), "\u{2068}בחר אחד\u{2069}");
+        assert_eq!(question_line("First\nاختر"), "\u{2068}اختر\u{2069}");
+        assert_eq!(measure_text_width(&isolate("שלום")), 4);
+    }
+
+    #[test]
+    fn test_max_width() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.set_max_width(Some(6));
+        assert_eq!(render.width(), 6);
+        render.prompt("aaa bbb ccc").unwrap();
+        assert_eq!(render.prompt_rows(), 3);
+    }
+
+    #[test]
+    fn test_write_box() {
+        let mut buf = String::new();
+        write_box(&mut buf, "info", "one\nthree", &Style::new(), false).unwrap();
+        assert_eq!(buf, "┌─ info ─┐\n│ one    │\n│ three  │\n└────────┘");
+        buf.clear();
+        write_box(&mut buf, "warning", "a", &Style::new(), false).unwrap();
+        assert_eq!(buf, "┌─ warning ─┐\n│ a         │\n└───────────┘");
+        buf.clear();
+        write_box(&mut buf, "error", "a", &Style::new(), true).unwrap();
+        assert_eq!(buf, "+- error -+\n| a       |\n+---------+");
+    }
+
+    #[test]
+    fn test_wrap_text_wide_chars() {
+        assert_eq!(wrap_text("日本語のテキスト", 6), "日本語\nのテキ\nスト");
+        assert_eq!(wrap_text("ab日本", 4), "ab日\n本");
+        assert_eq!(wrap_text("🦀 rust 🦀", 7), "🦀 rust\n🦀");
+        assert_eq!(wrap_text("e\u{301}te\u{301}", 3), "e\u{301}te\u{301}");
+    }
 }
@@ -0,0 +1,246 @@
+//! 256 and RGB colors that fall back to the colors a terminal supports.
+use std::env;
+
+use console::{Color, Style};
+
+/// The basic colors with their usual RGB values.
+const BASIC: [(Color, (u8, u8, u8)); 8] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::White, (229, 229, 229)),
+];
+
+/// The levels of the color cube of the 256 colors.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The colors a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// The 8 basic colors and their bright variants
+    Ansi16,
+    /// The 256 color palette
+    Ansi256,
+    /// RGB colors
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the colors supported by the terminal.
+    ///
+    /// RGB colors are assumed if `COLORTERM` is `truecolor` or `24bit`
+    /// and 256 colors if `TERM` mentions them.
+    pub fn detect() -> ColorDepth {
+        let var = |name| env::var(name).unwrap_or_default();
+        let colorterm = var("COLORTERM");
+        let term = var("TERM");
+        if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Returns an RGB color or the closest color of this depth.
+    pub fn rgb(self, r: u8, g: u8, b: u8) -> Color {
+        match self {
+            ColorDepth::TrueColor => Color::TrueColor(r, g, b),
+            ColorDepth::Ansi256 => Color::Color256(nearest_256(r, g, b)),
+            ColorDepth::Ansi16 => nearest_basic((r, g, b)),
+        }
+    }
+
+    /// Returns a color of the 256 color palette or the closest basic
+    /// color.
+    pub fn color256(self, color: u8) -> Color {
+        match self {
+            ColorDepth::Ansi16 => nearest_basic(rgb_of_256(color)),
+            _ => Color::Color256(color),
+        }
+    }
+
+    /// Creates a style from a dotted string like `#88c0d0.bold` or
+    /// `110.on_236`, replacing the colors with ones of this depth.
+    ///
+    /// See `console::Style::from_dotted_str` for the notation.
+    pub fn style(self, dotted: &str) -> Style {
+        let parts: Vec<String> = dotted
+            .split('.')
+            .map(|part| {
+                let (prefix, color) = match part.strip_prefix("on_") {
+                    Some(color) => ("on_", color),
+                    None => ("", part),
+                };
+                match parse_color(color).map(|color| self.downgrade(color)) {
+                    Some(color) => format!("{}{}", prefix, dotted_name(color)),
+                    None => part.to_string(),
+                }
+            })
+            .collect();
+        Style::from_dotted_str(&parts.join("."))
+    }
+
+    fn downgrade(self, color: Color) -> Color {
+        match color {
+            Color::TrueColor(r, g, b) => self.rgb(r, g, b),
+            Color::Color256(color) => self.color256(color),
+            color => color,
+        }
+    }
+}
+
+/// Returns an RGB color or the closest color supported by the terminal.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate console;
+/// # extern crate dialoguer;
+/// use console::Style;
+/// use dialoguer::theme::{rgb, ColoredTheme};
+///
+/// let mut theme = ColoredTheme::default();
+/// theme.values_style = Style::new().fg(rgb(0xa3, 0xbe, 0x8c));
+/// ```
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    ColorDepth::detect().rgb(r, g, b)
+}
+
+/// Returns a color of the 256 color palette or the closest color
+/// supported by the terminal.
+pub fn color256(color: u8) -> Color {
+    ColorDepth::detect().color256(color)
+}
+
+/// Parses an RGB color like `#88c0d0` or a 256 color number.
+fn parse_color(s: &str) -> Option<Color> {
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+        return Some(Color::TrueColor(channel(0)?, channel(2)?, channel(4)?));
+    }
+    s.parse().ok().map(Color::Color256)
+}
+
+/// The name of a color in the dotted notation.
+fn dotted_name(color: Color) -> String {
+    match color {
+        Color::Black => "black".into(),
+        Color::Red => "red".into(),
+        Color::Green => "green".into(),
+        Color::Yellow => "yellow".into(),
+        Color::Blue => "blue".into(),
+        Color::Magenta => "magenta".into(),
+        Color::Cyan => "cyan".into(),
+        Color::White => "white".into(),
+        Color::Color256(color) => color.to_string(),
+        Color::TrueColor(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+    }
+}
+
+/// Returns the closest color of the 256 color palette, leaving out the
+/// first 16 colors that terminals tend to redefine.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&idx| (i32::from(CUBE[idx]) - i32::from(c)).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    let avg = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let gray = 232 + (avg.saturating_sub(3) / 10).min(23);
+    if distance(rgb_of_256(gray), (r, g, b)) < distance(rgb_of_256(cube), (r, g, b)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// Returns the closest basic color.
+fn nearest_basic(rgb: (u8, u8, u8)) -> Color {
+    BASIC
+        .iter()
+        .min_by_key(|&&(_, basic)| distance(basic, rgb))
+        .map(|&(color, _)| color)
+        .unwrap_or(Color::White)
+}
+
+/// Returns the usual RGB value of a color of the 256 color palette.
+fn rgb_of_256(color: u8) -> (u8, u8, u8) {
+    match color {
+        0..=7 => BASIC[color as usize].1,
+        8 => (127, 127, 127),
+        9..=15 => {
+            let (r, g, b) = BASIC[color as usize - 8].1;
+            let bright = |c: u8| if c == 0 { 0 } else { 255 };
+            (bright(r), bright(g), bright(b))
+        }
+        16..=231 => {
+            let idx = color - 16;
+            (
+                CUBE[(idx / 36) as usize],
+                CUBE[(idx / 6 % 6) as usize],
+                CUBE[(idx % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (color - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// The squared distance of two colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downgrade() {
+        assert_eq!(
+            ColorDepth::TrueColor.rgb(1, 2, 3),
+            Color::TrueColor(1, 2, 3)
+        );
+        assert_eq!(ColorDepth::Ansi256.rgb(255, 0, 0), Color::Color256(196));
+        assert_eq!(ColorDepth::Ansi256.rgb(128, 128, 128), Color::Color256(244));
+        assert_eq!(ColorDepth::Ansi16.rgb(220, 30, 20), Color::Red);
+        assert_eq!(ColorDepth::Ansi16.color256(21), Color::Blue);
+        assert_eq!(ColorDepth::Ansi256.color256(21), Color::Color256(21));
+    }
+
+    #[test]
+    fn test_style() {
+        let style = |depth: ColorDepth, dotted| {
+            depth
+                .style(dotted)
+                .force_styling(true)
+                .apply_to("x")
+                .to_string()
+        };
+        assert_eq!(
+            style(ColorDepth::TrueColor, "#ff0000"),
+            "\x1b[38;2;255;0;0mx\x1b[0m"
+        );
+        assert_eq!(
+            style(ColorDepth::Ansi256, "#ff0000"),
+            "\x1b[38;5;196mx\x1b[0m"
+        );
+        assert_eq!(
+            style(ColorDepth::Ansi16, "#ff0000.on_21"),
+            "\x1b[31m\x1b[44mx\x1b[0m"
+        );
+    }
+}
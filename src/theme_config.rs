@@ -0,0 +1,230 @@
+//! Loads a colored theme from a configuration file.
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use theme::{colors_from_env, ColorDepth, ColoredTheme};
+
+/// The styles of a theme configuration.
+///
+/// Styles are written in the dotted notation understood by
+/// `console::Style::from_dotted_str` (eg: `"cyan.bold"`).  256 colors
+/// (eg: `"110"`) and RGB colors (eg: `"#88c0d0"`) are replaced with the
+/// closest color on terminals that do not support them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StylesConfig {
+    pub defaults: Option<String>,
+    pub prompts: Option<String>,
+    pub prefixes: Option<String>,
+    pub values: Option<String>,
+    pub errors: Option<String>,
+    pub warnings: Option<String>,
+    pub selected: Option<String>,
+    pub unselected: Option<String>,
+    pub placeholder: Option<String>,
+    pub matched: Option<String>,
+}
+
+/// The symbols of a theme configuration.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SymbolsConfig {
+    pub prompt_prefix: Option<String>,
+    pub password_prefix: Option<String>,
+    pub prompt_suffix: Option<String>,
+    pub success_prefix: Option<String>,
+    pub error_prefix: Option<String>,
+    pub separator: Option<String>,
+    pub active_item_prefix: Option<String>,
+    pub checked_item_prefix: Option<String>,
+    pub partial_item_prefix: Option<String>,
+}
+
+/// A theme configuration deserialized from a file.
+///
+/// Everything not set in the configuration keeps the value of
+/// `ColoredTheme::default()`.
+///
+/// ## Example configuration
+///
+/// ```toml
+/// inline_selections = false
+///
+/// [styles]
+/// prompts = "bold"
+/// values = "magenta"
+///
+/// [symbols]
+/// prompt_prefix = ">"
+/// success_prefix = "+"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub styles: StylesConfig,
+    pub symbols: SymbolsConfig,
+    pub inline_selections: Option<bool>,
+    pub selection_count: Option<bool>,
+}
+
+impl ThemeConfig {
+    /// Parses a configuration in TOML format.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> io::Result<ThemeConfig> {
+        toml::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Parses a configuration in JSON format.
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(s: &str) -> io::Result<ThemeConfig> {
+        serde_json::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Reads a configuration file.
+    ///
+    /// Files ending in `.json` are parsed as JSON, everything else as
+    /// TOML.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<ThemeConfig> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            ThemeConfig::parse_json(&contents)
+        } else {
+            ThemeConfig::parse_toml(&contents)
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    fn parse_json(s: &str) -> io::Result<ThemeConfig> {
+        ThemeConfig::from_json(s)
+    }
+
+    #[cfg(not(feature = "serde_json"))]
+    fn parse_json(_s: &str) -> io::Result<ThemeConfig> {
+        Err(io::Error::other(
+            "JSON themes require the serde_json feature",
+        ))
+    }
+
+    #[cfg(feature = "toml")]
+    fn parse_toml(s: &str) -> io::Result<ThemeConfig> {
+        ThemeConfig::from_toml(s)
+    }
+
+    #[cfg(not(feature = "toml"))]
+    fn parse_toml(_s: &str) -> io::Result<ThemeConfig> {
+        Err(io::Error::other("TOML themes require the toml feature"))
+    }
+
+    /// Builds the theme described by the configuration.
+    pub fn into_theme(self) -> ColoredTheme {
+        let mut theme = ColoredTheme::default();
+        {
+            let styles = [
+                (self.styles.defaults, &mut theme.defaults_style),
+                (self.styles.prompts, &mut theme.prompts_style),
+                (self.styles.prefixes, &mut theme.prefixes_style),
+                (self.styles.values, &mut theme.values_style),
+                (self.styles.errors, &mut theme.errors_style),
+                (self.styles.warnings, &mut theme.warnings_style),
+                (self.styles.selected, &mut theme.selected_style),
+                (self.styles.unselected, &mut theme.unselected_style),
+                (self.styles.placeholder, &mut theme.placeholder_style),
+                (self.styles.matched, &mut theme.matched_style),
+            ];
+            let depth = ColorDepth::detect();
+            for (value, style) in styles {
+                if let Some(value) = value {
+                    *style = depth.style(&value);
+                }
+            }
+        }
+        {
+            let symbols = [
+                (self.symbols.prompt_prefix, &mut theme.prompt_prefix),
+                (self.symbols.password_prefix, &mut theme.password_prefix),
+                (self.symbols.prompt_suffix, &mut theme.prompt_suffix),
+                (self.symbols.success_prefix, &mut theme.success_prefix),
+                (self.symbols.error_prefix, &mut theme.error_prefix),
+                (self.symbols.separator, &mut theme.separator),
+                (
+                    self.symbols.active_item_prefix,
+                    &mut theme.active_item_prefix,
+                ),
+                (
+                    self.symbols.checked_item_prefix,
+                    &mut theme.checked_item_prefix,
+                ),
+                (
+                    self.symbols.partial_item_prefix,
+                    &mut theme.partial_item_prefix,
+                ),
+            ];
+            for (value, symbol) in symbols {
+                if let Some(value) = value {
+                    *symbol = value;
+                }
+            }
+        }
+        if let Some(inline_selections) = self.inline_selections {
+            theme.inline_selections = inline_selections;
+        }
+        if let Some(selection_count) = self.selection_count {
+            theme.selection_count = selection_count;
+        }
+        match colors_from_env() {
+            Some(val) => theme.colors(val),
+            None => theme,
+        }
+    }
+}
+
+impl From<ThemeConfig> for ColoredTheme {
+    fn from(config: ThemeConfig) -> ColoredTheme {
+        config.into_theme()
+    }
+}
+
+impl ColoredTheme {
+    /// Loads a theme from a configuration file.
+    ///
+    /// See `ThemeConfig` for the format of the file.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> io::Result<ColoredTheme> {
+        Ok(ThemeConfig::load(path)?.into_theme())
+    }
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use super::*;
+    use console::Style;
+
+    #[test]
+    fn test_from_toml() {
+        let theme = ThemeConfig::from_toml(
+            r#"
+            inline_selections = false
+
+            [styles]
+            values = "magenta"
+
+            [symbols]
+            prompt_prefix = ">"
+            "#,
+        )
+        .unwrap()
+        .into_theme();
+
+        assert_eq!(theme.prompt_prefix, ">");
+        assert_eq!(theme.success_prefix, "✔");
+        assert_eq!(theme.values_style, Style::new().magenta());
+        assert!(!theme.inline_selections);
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        assert!(ThemeConfig::from_toml("[styles]\nvalue = \"red\"").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Ready-made colored themes selectable by name.
+use console::Style;
+use theme::{color256, colors_from_env, ColoredTheme};
+
+/// The names of the bundled theme presets, eg: for the possible values
+/// of a `--theme` flag.
+pub const PRESETS: &[&str] = &[
+    "default",
+    "minimal",
+    "high-contrast",
+    "nord",
+    "dracula",
+    "emoji",
+];
+
+impl ColoredTheme {
+    /// Returns the bundled theme preset with the given name.
+    ///
+    /// The names are listed in `PRESETS`.  Returns `None` for an unknown
+    /// name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dialoguer::theme::ColoredTheme;
+    ///
+    /// let theme = ColoredTheme::preset("nord").unwrap_or_default();
+    /// ```
+    pub fn preset(name: &str) -> Option<ColoredTheme> {
+        let theme = match name {
+            "default" => return Some(ColoredTheme::default()),
+            "minimal" => minimal(),
+            "high-contrast" => high_contrast(),
+            "nord" => palette([110, 109, 144, 131, 222, 240]),
+            "dracula" => palette([141, 212, 84, 203, 228, 61]),
+            "emoji" => emoji(),
+            _ => return None,
+        };
+        Some(match colors_from_env() {
+            Some(val) => theme.colors(val),
+            None => theme,
+        })
+    }
+}
+
+/// Plain text with ASCII symbols, only errors stand out.
+fn minimal() -> ColoredTheme {
+    let mut theme = ColoredTheme::default()
+        .prompt_prefix("")
+        .password_prefix("")
+        .prompt_suffix(":")
+        .success_prefix("")
+        .error_prefix("!")
+        .separator(":")
+        .active_item_prefix(">")
+        .checked_item_prefix("*")
+        .partial_item_prefix("~");
+    for style in [
+        &mut theme.defaults_style,
+        &mut theme.prompts_style,
+        &mut theme.prefixes_style,
+        &mut theme.values_style,
+        &mut theme.warnings_style,
+        &mut theme.unselected_style,
+    ] {
+        *style = Style::new();
+    }
+    theme.errors_style = Style::new().bold();
+    theme.selected_style = Style::new().bold();
+    theme.placeholder_style = Style::new().dim();
+    theme.matched_style = Style::new().underlined();
+    theme
+}
+
+/// Bright and bold colors, the active item is shown in reverse video.
+fn high_contrast() -> ColoredTheme {
+    ColoredTheme {
+        defaults_style: Style::new().yellow().bright().bold(),
+        prompts_style: Style::new().white().bright().bold(),
+        prefixes_style: Style::new().cyan().bright().bold(),
+        values_style: Style::new().green().bright().bold(),
+        errors_style: Style::new().red().bright().bold(),
+        warnings_style: Style::new().yellow().bright().bold(),
+        selected_style: Style::new().bold().reverse(),
+        unselected_style: Style::new().white().bright(),
+        placeholder_style: Style::new().white(),
+        matched_style: Style::new().bold().underlined(),
+        ..ColoredTheme::default()
+    }
+}
+
+/// The default colors with emoji in front of prompts, answers, errors
+/// and the active item.
+fn emoji() -> ColoredTheme {
+    ColoredTheme::default()
+        .prompt_prefix("❓")
+        .password_prefix("🔒")
+        .success_prefix("✅")
+        .error_prefix("❌")
+        .active_item_prefix("👉")
+}
+
+/// A theme in 256 colors.  The colors are given for the accents, the
+/// highlighted items, the values, the errors, the defaults and the
+/// placeholders.  On terminals with 16 colors the closest basic colors
+/// are used.
+fn palette(colors: [u8; 6]) -> ColoredTheme {
+    let [accent, highlight, value, error, default, dim] = colors.map(color256);
+    ColoredTheme {
+        defaults_style: Style::new().fg(default).bold(),
+        prompts_style: Style::new().bold(),
+        prefixes_style: Style::new().fg(accent),
+        values_style: Style::new().fg(value),
+        errors_style: Style::new().fg(error),
+        warnings_style: Style::new().fg(default),
+        selected_style: Style::new().fg(highlight).bold(),
+        unselected_style: Style::new(),
+        placeholder_style: Style::new().fg(dim),
+        matched_style: Style::new().fg(accent).bold().underlined(),
+        ..ColoredTheme::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        for name in PRESETS {
+            assert!(ColoredTheme::preset(name).is_some(), "{}", name);
+        }
+        assert!(ColoredTheme::preset("solarized").is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! Waiting for key presses with a timeout and noticing when the
+//! terminal is resized.
+use std::io;
+use std::time::Duration;
+
+/// Waits until a key is pressed or the timeout elapses.
+///
+/// Returns `true` if a key can be read without blocking.  On platforms
+/// other than unix this does not wait and always returns `true`.
+#[cfg(unix)]
+pub(crate) fn key_ready(timeout: Duration) -> io::Result<bool> {
+    use guard::TtyMode;
+
+    let mode = TtyMode::save()?;
+    let mut termios = mode.original();
+    // keys only become readable once a line is complete in canonical mode
+    termios.c_lflag &= !(libc::ICANON | libc::ECHO);
+    mode.set(&termios)?;
+    let mut pollfd = libc::pollfd {
+        fd: mode.fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let millis = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+    let rv = unsafe { libc::poll(&mut pollfd, 1, millis) };
+    let err = io::Error::last_os_error();
+    mode.restore();
+    match rv {
+        -1 if err.kind() == io::ErrorKind::Interrupted => Ok(false),
+        -1 => Err(err),
+        rv => Ok(rv > 0),
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn key_ready(_timeout: Duration) -> io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(unix)]
+mod resize {
+    use std::mem;
+    use std::ptr;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Once;
+
+    static RESIZED: AtomicBool = AtomicBool::new(false);
+    static WATCH: Once = Once::new();
+    // the handler installed before ours, which is still called
+    static PREVIOUS: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+    static PREVIOUS_SIGINFO: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_resize(signal: libc::c_int) {
+        RESIZED.store(true, Ordering::SeqCst);
+        let previous = PREVIOUS.load(Ordering::SeqCst);
+        if previous == libc::SIG_DFL || previous == libc::SIG_IGN {
+            return;
+        }
+        unsafe {
+            if PREVIOUS_SIGINFO.load(Ordering::SeqCst) {
+                let previous: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                    mem::transmute(previous);
+                previous(signal, ptr::null_mut(), ptr::null_mut());
+            } else {
+                let previous: extern "C" fn(libc::c_int) = mem::transmute(previous);
+                previous(signal);
+            }
+        }
+    }
+
+    /// Installs a SIGWINCH handler once so resizes can be noticed.
+    ///
+    /// The signal interrupts `key_ready`, which is never restarted.
+    pub(crate) fn watch() -> bool {
+        WATCH.call_once(|| unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            let mut previous: libc::sigaction = mem::zeroed();
+            if libc::sigaction(libc::SIGWINCH, ptr::null(), &mut previous) == 0 {
+                PREVIOUS.store(previous.sa_sigaction, Ordering::SeqCst);
+                PREVIOUS_SIGINFO.store(previous.sa_flags & libc::SA_SIGINFO != 0, Ordering::SeqCst);
+            }
+            libc::sigaction(libc::SIGWINCH, &action, ptr::null_mut());
+        });
+        true
+    }
+
+    /// Returns whether the terminal was resized since the last call.
+    pub(crate) fn take() -> bool {
+        RESIZED.swap(false, Ordering::SeqCst)
+    }
+}
+
+#[cfg(not(unix))]
+mod resize {
+    pub(crate) fn watch() -> bool {
+        false
+    }
+
+    pub(crate) fn take() -> bool {
+        false
+    }
+}
+
+/// Starts noticing when the terminal is resized.  Returns `false` if
+/// resizes cannot be noticed on this platform.
+pub(crate) fn watch_resize() -> bool {
+    resize::watch()
+}
+
+/// Returns whether the terminal was resized since the last call.
+pub(crate) fn take_resized() -> bool {
+    resize::take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_resize() {
+        assert!(watch_resize());
+        take_resized();
+        unsafe {
+            libc::raise(libc::SIGWINCH);
+        }
+        assert!(take_resized());
+        assert!(!take_resized());
+    }
+}
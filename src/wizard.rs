@@ -0,0 +1,1082 @@
+//! Chains multiple prompts into a wizard.
+use std::cell::RefCell;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use console::Term;
+use error::Result;
+use localization;
+use prompts::{Confirmation, Input};
+use select::{Checkboxes, Select};
+#[cfg(feature = "serde")]
+use serde::de::{Deserializer, MapAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, SerializeMap, Serializer};
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use theme::{self, get_default_theme, ConfirmationLabels, TermThemeRenderer, Theme};
+
+/// An answer collected by a wizard step.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    /// A text answer (from an input prompt).
+    Text(String),
+    /// A yes/no answer (from a confirmation prompt).
+    Bool(bool),
+    /// The index and text of a selected item.
+    Choice(usize, String),
+    /// The indexes and texts of all checked items.
+    Choices(Vec<(usize, String)>),
+}
+
+impl Answer {
+    /// Returns the text of a text answer or of a single choice.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Answer::Text(ref text) | Answer::Choice(_, ref text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a yes/no answer.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Answer::Bool(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of a single choice.
+    pub fn as_index(&self) -> Option<usize> {
+        match *self {
+            Answer::Choice(idx, _) => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// Returns the indexes of all checked items.
+    pub fn as_indexes(&self) -> Option<Vec<usize>> {
+        match *self {
+            Answer::Choices(ref choices) => Some(choices.iter().map(|c| c.0).collect()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Text(ref text) | Answer::Choice(_, ref text) => write!(f, "{}", text),
+            Answer::Bool(val) => {
+                localization::with(|l| write!(f, "{}", if val { &l.yes } else { &l.no }))
+            }
+            Answer::Choices(ref choices) => {
+                for (idx, choice) in choices.iter().enumerate() {
+                    write!(f, "{}{}", if idx == 0 { "" } else { ", " }, choice.1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Answer::Text(ref text) | Answer::Choice(_, ref text) => serializer.serialize_str(text),
+            Answer::Bool(val) => serializer.serialize_bool(val),
+            Answer::Choices(ref choices) => {
+                serializer.collect_seq(choices.iter().map(|choice| &choice.1))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Answer, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Stored {
+            Bool(bool),
+            Text(String),
+            Choices(Vec<String>),
+        }
+        Ok(match Stored::deserialize(deserializer)? {
+            Stored::Bool(val) => Answer::Bool(val),
+            Stored::Text(text) => Answer::Text(text),
+            Stored::Choices(choices) => Answer::Choices(choices.into_iter().enumerate().collect()),
+        })
+    }
+}
+
+/// The formats answers can be written in.
+///
+/// Each format needs the `serde` feature along with the feature of its
+/// serializer: `serde_json`, `serde_yaml` or `toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswersFormat {
+    /// Pretty printed JSON.
+    Json,
+    /// YAML.
+    Yaml,
+    /// TOML.
+    Toml,
+}
+
+/// The named answers collected by a wizard, in the order they were given.
+///
+/// With the `serde` feature the answers serialize to a map of the keys to
+/// the answers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    answers: Vec<(String, Answer)>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Answers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.answers.len()))?;
+        for (key, answer) in &self.answers {
+            map.serialize_entry(key, answer)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Answers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Answers, D::Error> {
+        struct AnswersVisitor;
+
+        impl<'de> Visitor<'de> for AnswersVisitor {
+            type Value = Answers;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of answers")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Answers, M::Error> {
+                let mut answers = Answers::default();
+                while let Some((key, answer)) = map.next_entry::<String, Answer>()? {
+                    answers.push(&key, answer);
+                }
+                Ok(answers)
+            }
+        }
+
+        deserializer.deserialize_map(AnswersVisitor)
+    }
+}
+
+impl Answers {
+    /// Looks up the answer for a step.
+    pub fn get(&self, key: &str) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .find(|&(k, _)| k == key)
+            .map(|(_, answer)| answer)
+    }
+
+    /// Looks up the answer for a step and parses its text.
+    pub fn parse<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)
+            .and_then(|answer| answer.to_string().parse().ok())
+    }
+
+    /// Iterates over the keys and answers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Answer)> {
+        self.answers
+            .iter()
+            .map(|(key, answer)| (key.as_str(), answer))
+    }
+
+    /// Returns the number of answers.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Returns `true` if nothing was answered.
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Writes the answers in the given format.
+    pub fn write<W: io::Write>(&self, format: AnswersFormat, writer: W) -> io::Result<()> {
+        match format {
+            AnswersFormat::Json => self.write_json(writer),
+            AnswersFormat::Yaml => self.write_yaml(writer),
+            AnswersFormat::Toml => self.write_toml(writer),
+        }
+    }
+
+    /// Reads answers written by `write`, eg: to use them as the defaults
+    /// of a wizard.
+    ///
+    /// Single choices are read as text answers and the indexes of
+    /// checked items are their positions in the list.
+    pub fn read<R: io::Read>(format: AnswersFormat, mut reader: R) -> io::Result<Answers> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        match format {
+            AnswersFormat::Json => Answers::read_json(&text),
+            AnswersFormat::Yaml => Answers::read_yaml(&text),
+            AnswersFormat::Toml => Answers::read_toml(&text),
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn read_json(s: &str) -> io::Result<Answers> {
+        serde_json::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "serde_json")))]
+    fn read_json(_s: &str) -> io::Result<Answers> {
+        Err(io::Error::other(
+            "JSON input requires the serde and serde_json features",
+        ))
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_yaml"))]
+    fn read_yaml(s: &str) -> io::Result<Answers> {
+        serde_yaml::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "serde_yaml")))]
+    fn read_yaml(_s: &str) -> io::Result<Answers> {
+        Err(io::Error::other(
+            "YAML input requires the serde and serde_yaml features",
+        ))
+    }
+
+    #[cfg(all(feature = "serde", feature = "toml"))]
+    fn read_toml(s: &str) -> io::Result<Answers> {
+        toml::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "toml")))]
+    fn read_toml(_s: &str) -> io::Result<Answers> {
+        Err(io::Error::other(
+            "TOML input requires the serde and toml features",
+        ))
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn write_json<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "serde_json")))]
+    fn write_json<W: io::Write>(&self, _writer: W) -> io::Result<()> {
+        Err(io::Error::other(
+            "JSON output requires the serde and serde_json features",
+        ))
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_yaml"))]
+    fn write_yaml<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serde_yaml::to_writer(writer, self).map_err(io::Error::other)
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "serde_yaml")))]
+    fn write_yaml<W: io::Write>(&self, _writer: W) -> io::Result<()> {
+        Err(io::Error::other(
+            "YAML output requires the serde and serde_yaml features",
+        ))
+    }
+
+    #[cfg(all(feature = "serde", feature = "toml"))]
+    fn write_toml<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        let s = toml::to_string(self).map_err(io::Error::other)?;
+        writer.write_all(s.as_bytes())
+    }
+
+    #[cfg(not(all(feature = "serde", feature = "toml")))]
+    fn write_toml<W: io::Write>(&self, _writer: W) -> io::Result<()> {
+        Err(io::Error::other(
+            "TOML output requires the serde and toml features",
+        ))
+    }
+
+    fn push(&mut self, key: &str, answer: Answer) {
+        self.answers.push((key.to_string(), answer));
+    }
+
+    fn pop(&mut self) {
+        self.answers.pop();
+    }
+}
+
+/// A prompt that can be used as a step of a wizard.
+pub trait WizardStep {
+    /// Asks the question and returns the answer.
+    ///
+    /// If `allow_back` is set the step may return `None` to go back to
+    /// the previous step.  The answers the step reported are cleared
+    /// again when the wizard goes back over it.
+    fn ask(&self, term: &Term, allow_back: bool) -> Result<Option<Answer>>;
+}
+
+impl<'a, T> WizardStep for Input<'a, T>
+where
+    T: Clone + fmt::Display + 'static,
+{
+    fn ask(&self, term: &Term, allow_back: bool) -> Result<Option<Answer>> {
+        let value = if allow_back {
+            self.interact_on_opt(term)?
+        } else {
+            Some(self.interact_on(term)?)
+        };
+        Ok(value.map(|value| Answer::Text(value.to_string())))
+    }
+}
+
+impl<'a> WizardStep for Confirmation<'a> {
+    fn ask(&self, term: &Term, allow_back: bool) -> Result<Option<Answer>> {
+        let val = if allow_back {
+            self.interact_on_opt(term)?
+        } else {
+            Some(self.interact_on(term)?)
+        };
+        Ok(val.map(Answer::Bool))
+    }
+}
+
+impl<'a> WizardStep for Select<'a> {
+    fn ask(&self, term: &Term, allow_back: bool) -> Result<Option<Answer>> {
+        let sel = if allow_back {
+            self.interact_on_opt(term)?
+        } else {
+            Some(self.interact_on(term)?)
+        };
+        Ok(sel.map(|idx| Answer::Choice(idx, self.item_text(idx))))
+    }
+}
+
+impl<'a> WizardStep for Checkboxes<'a> {
+    fn ask(&self, term: &Term, allow_back: bool) -> Result<Option<Answer>> {
+        let checked = if allow_back {
+            self.interact_on_opt(term)?
+        } else {
+            Some(self.interact_on(term)?)
+        };
+        Ok(checked.map(|checked| {
+            Answer::Choices(
+                checked
+                    .into_iter()
+                    .map(|idx| (idx, self.item_text(idx).to_string()))
+                    .collect(),
+            )
+        }))
+    }
+}
+
+type SkipCallback<'a> = Box<dyn Fn(&Answers) -> bool + 'a>;
+type BuildCallback<'a> = Box<dyn Fn(&Answers) -> Box<dyn WizardStep + 'a> + 'a>;
+type DefaultCallback<'a> = Box<dyn Fn(&Answers) -> Option<String> + 'a>;
+type ItemsCallback<'a> = Box<dyn Fn(&Answers) -> Vec<String> + 'a>;
+
+/// The prompt of a step, either given up front or built from the
+/// answers of earlier steps when the step is asked.
+enum StepPrompt<'a> {
+    Fixed(Box<dyn WizardStep + 'a>),
+    Built(BuildCallback<'a>),
+    Shortcut(Shortcut<'a>),
+}
+
+/// A prompt added through a shortcut method of the wizard.
+///
+/// It is built when the step is asked so that it can default to the
+/// answer set with `Wizard::defaults`.
+enum Shortcut<'a> {
+    Input(String, Option<DefaultCallback<'a>>),
+    Confirm(String),
+    Select(String, ItemsCallback<'a>),
+    Checkboxes(String, Vec<String>),
+}
+
+impl<'a> Shortcut<'a> {
+    /// Returns the prompt text of the step.
+    fn prompt(&self) -> &str {
+        match *self {
+            Shortcut::Input(ref prompt, _)
+            | Shortcut::Confirm(ref prompt)
+            | Shortcut::Select(ref prompt, _)
+            | Shortcut::Checkboxes(ref prompt, _) => prompt,
+        }
+    }
+
+    /// Reports an accepted answer like the prompt would.
+    fn report(&self, render: &mut TermThemeRenderer, answer: &Answer) -> io::Result<()> {
+        match (self, answer) {
+            (Shortcut::Confirm(text), &Answer::Bool(val)) => {
+                render.confirmation_prompt_selection(text, val, &ConfirmationLabels::default())
+            }
+            (Shortcut::Checkboxes(prompt, items), Answer::Choices(choices)) => {
+                let texts: Vec<_> = choices.iter().map(|choice| choice.1.as_str()).collect();
+                render.checked_prompt_selection(prompt, &texts, items.len())
+            }
+            _ => render.single_prompt_selection(self.prompt(), &answer.to_string()),
+        }
+    }
+
+    /// Builds the prompt, defaulting to `default` where it fits.
+    fn build(
+        &self,
+        theme: &'a dyn Theme,
+        answers: &Answers,
+        default: Option<&Answer>,
+    ) -> Box<dyn WizardStep + 'a> {
+        match *self {
+            Shortcut::Input(ref prompt, ref computed) => {
+                let value = default
+                    .and_then(Answer::as_str)
+                    .map(str::to_string)
+                    .or_else(|| computed.as_ref().and_then(|computed| computed(answers)));
+                let mut input = Input::<String>::with_theme(theme);
+                input.with_prompt(prompt).default(value);
+                Box::new(input)
+            }
+            Shortcut::Confirm(ref text) => {
+                let mut confirmation = Confirmation::with_theme(theme);
+                confirmation.with_text(text);
+                if let Some(val) = default.and_then(Answer::as_bool) {
+                    confirmation.default(val);
+                }
+                Box::new(confirmation)
+            }
+            Shortcut::Select(ref prompt, ref items) => {
+                let items = items(answers);
+                let sel = default
+                    .and_then(Answer::as_str)
+                    .and_then(|text| items.iter().position(|item| item == text));
+                let mut select = Select::with_theme(theme);
+                select
+                    .with_prompt(prompt)
+                    .items(&items)
+                    .default(sel.unwrap_or(0));
+                Box::new(select)
+            }
+            Shortcut::Checkboxes(ref prompt, ref items) => {
+                let checked: Vec<_> = match default {
+                    Some(Answer::Choices(choices)) => items
+                        .iter()
+                        .map(|item| (item, choices.iter().any(|choice| choice.1 == *item)))
+                        .collect(),
+                    _ => items.iter().map(|item| (item, false)).collect(),
+                };
+                let mut checkboxes = Checkboxes::with_theme(theme);
+                checkboxes.with_prompt(prompt).items_checked(&checked);
+                Box::new(checkboxes)
+            }
+        }
+    }
+
+    /// Returns the answer of the step if `default` is accepted, `None`
+    /// if it does not fit the step.
+    fn accept(&self, answers: &Answers, default: &Answer) -> Option<Answer> {
+        match *self {
+            Shortcut::Input(..) => default.as_str().map(|text| Answer::Text(text.into())),
+            Shortcut::Confirm(_) => default.as_bool().map(Answer::Bool),
+            Shortcut::Select(_, ref items) => {
+                let text = default.as_str()?;
+                let idx = items(answers).iter().position(|item| item == text)?;
+                Some(Answer::Choice(idx, text.into()))
+            }
+            Shortcut::Checkboxes(_, ref items) => match *default {
+                Answer::Choices(ref choices) => {
+                    let mut checked = choices
+                        .iter()
+                        .map(|choice| {
+                            let idx = items.iter().position(|item| *item == choice.1)?;
+                            Some((idx, choice.1.clone()))
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    checked.sort();
+                    Some(Answer::Choices(checked))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A step of a wizard along with the key of its answer.
+struct Step<'a> {
+    key: String,
+    prompt: StepPrompt<'a>,
+    skip: Option<SkipCallback<'a>>,
+}
+
+/// Renders a sequence of prompts and collects their answers.
+///
+/// Every step is preceded by a header showing the progress through the
+/// wizard.  Pressing Esc goes back to the previous step.  Steps can be
+/// skipped depending on earlier answers with `skip_if` or built from
+/// them with `step_with`.
+///
+/// The steps added through the shortcut methods (`input`, `confirm`,
+/// `select`, etc.) can be prefilled with the answers of an earlier run
+/// set with `defaults`.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<std::error::Error>> {
+/// use dialoguer::Wizard;
+///
+/// let answers = Wizard::new()
+///     .with_title("Setup")
+///     .input("name", "Your name")
+///     .select("tls", "Configure TLS", &["automatic", "manual", "no"])
+///     .input("key", "Path to private key")
+///     .skip_if(|answers| answers.get("tls").and_then(|a| a.as_str()) != Some("manual"))
+///     .select_with("branch", "Branch", |answers| {
+///         let name = answers.get("name").unwrap();
+///         vec![format!("{}/main", name), format!("{}/dev", name)]
+///     })
+///     .confirm("save", "Save the configuration?")
+///     .interact()?;
+/// println!("Hello {}!", answers.get("name").unwrap());
+/// # Ok(()) } fn main() { test().unwrap(); }
+/// ```
+pub struct Wizard<'a> {
+    title: Option<String>,
+    steps: Vec<Step<'a>>,
+    allow_back: bool,
+    output: Option<(AnswersFormat, RefCell<Box<dyn io::Write + 'a>>)>,
+    defaults: Answers,
+    offer_defaults: bool,
+    theme: &'a dyn Theme,
+    term: Term,
+}
+
+impl<'a> Default for Wizard<'a> {
+    fn default() -> Wizard<'a> {
+        Wizard::new()
+    }
+}
+
+impl<'a> Wizard<'a> {
+    /// Creates a new wizard.
+    pub fn new() -> Wizard<'a> {
+        Wizard::with_theme(get_default_theme())
+    }
+
+    /// Creates a wizard with a specific theme.
+    ///
+    /// The theme is used for the step headers and the prompts added
+    /// through the shortcut methods.
+    pub fn with_theme(theme: &'a dyn Theme) -> Wizard<'a> {
+        Wizard {
+            title: None,
+            steps: vec![],
+            allow_back: true,
+            output: None,
+            defaults: Answers::default(),
+            offer_defaults: true,
+            theme,
+            term: Term::stderr(),
+        }
+    }
+
+    /// Sets the title shown in the step header.
+    pub fn with_title(&mut self, title: &str) -> &mut Wizard<'a> {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Enables or disables going back to the previous step.
+    ///
+    /// This is enabled by default.
+    pub fn allow_back(&mut self, val: bool) -> &mut Wizard<'a> {
+        self.allow_back = val;
+        self
+    }
+
+    /// Writes the answers to `writer` once all steps are answered.
+    ///
+    /// This allows interactive runs to emit a configuration file that can
+    /// be reused later.
+    pub fn write_answers<W: io::Write + 'a>(
+        &mut self,
+        format: AnswersFormat,
+        writer: W,
+    ) -> &mut Wizard<'a> {
+        self.output = Some((format, RefCell::new(Box::new(writer))));
+        self
+    }
+
+    /// Prefills the steps with answers, eg: those of an earlier run read
+    /// with `Answers::read`.
+    ///
+    /// Only the steps added through the shortcut methods are prefilled.
+    /// Answers that do not fit their step, eg: an item no longer listed,
+    /// are ignored.
+    pub fn defaults(&mut self, answers: Answers) -> &mut Wizard<'a> {
+        self.defaults = answers;
+        self
+    }
+
+    /// Asks up front whether to accept all defaults at once.
+    ///
+    /// This is enabled by default and only asked if defaults are set.
+    /// The steps without a fitting default are still asked.  Going back
+    /// asks the steps again, prefilled with their defaults.
+    pub fn offer_defaults(&mut self, val: bool) -> &mut Wizard<'a> {
+        self.offer_defaults = val;
+        self
+    }
+
+    /// Adds a step whose answer is stored under `key`.
+    pub fn step<S: WizardStep + 'a>(&mut self, key: &str, step: S) -> &mut Wizard<'a> {
+        self.steps.push(Step {
+            key: key.into(),
+            prompt: StepPrompt::Fixed(Box::new(step)),
+            skip: None,
+        });
+        self
+    }
+
+    /// Adds a step built from the answers given so far, eg: to compute
+    /// its items or default from an earlier answer.
+    ///
+    /// `f` is called each time the step is asked, also after going back.
+    pub fn step_with<S, F>(&mut self, key: &str, f: F) -> &mut Wizard<'a>
+    where
+        S: WizardStep + 'a,
+        F: Fn(&Answers) -> S + 'a,
+    {
+        self.steps.push(Step {
+            key: key.into(),
+            prompt: StepPrompt::Built(Box::new(move |answers| Box::new(f(answers)))),
+            skip: None,
+        });
+        self
+    }
+
+    /// Skips the step added last if `f` returns `true` for the answers
+    /// given so far.
+    ///
+    /// Skipped steps leave no answer behind and are not counted in the
+    /// header.  A step has to be added before calling this, otherwise it
+    /// panics.
+    pub fn skip_if<F: Fn(&Answers) -> bool + 'a>(&mut self, f: F) -> &mut Wizard<'a> {
+        match self.steps.last_mut() {
+            Some(step) => step.skip = Some(Box::new(f)),
+            None => panic!("Expected a step to be added before skip_if"),
+        }
+        self
+    }
+
+    /// Adds a step through a shortcut method.
+    fn shortcut(&mut self, key: &str, shortcut: Shortcut<'a>) -> &mut Wizard<'a> {
+        self.steps.push(Step {
+            key: key.into(),
+            prompt: StepPrompt::Shortcut(shortcut),
+            skip: None,
+        });
+        self
+    }
+
+    /// Adds a text input step.
+    pub fn input(&mut self, key: &str, prompt: &str) -> &mut Wizard<'a> {
+        self.shortcut(key, Shortcut::Input(prompt.into(), None))
+    }
+
+    /// Adds a text input step with a default computed from the answers
+    /// given so far.
+    pub fn input_with<F>(&mut self, key: &str, prompt: &str, default: F) -> &mut Wizard<'a>
+    where
+        F: Fn(&Answers) -> Option<String> + 'a,
+    {
+        self.shortcut(key, Shortcut::Input(prompt.into(), Some(Box::new(default))))
+    }
+
+    /// Adds a confirmation step.
+    pub fn confirm(&mut self, key: &str, text: &str) -> &mut Wizard<'a> {
+        self.shortcut(key, Shortcut::Confirm(text.into()))
+    }
+
+    /// Adds a selection step.
+    pub fn select<T: ToString>(&mut self, key: &str, prompt: &str, items: &[T]) -> &mut Wizard<'a> {
+        let items: Vec<_> = items.iter().map(ToString::to_string).collect();
+        self.shortcut(
+            key,
+            Shortcut::Select(prompt.into(), Box::new(move |_| items.clone())),
+        )
+    }
+
+    /// Adds a selection step with items computed from the answers given
+    /// so far.
+    pub fn select_with<F>(&mut self, key: &str, prompt: &str, items: F) -> &mut Wizard<'a>
+    where
+        F: Fn(&Answers) -> Vec<String> + 'a,
+    {
+        self.shortcut(key, Shortcut::Select(prompt.into(), Box::new(items)))
+    }
+
+    /// Adds a checkboxes step.
+    pub fn checkboxes<T: ToString>(
+        &mut self,
+        key: &str,
+        prompt: &str,
+        items: &[T],
+    ) -> &mut Wizard<'a> {
+        let items = items.iter().map(ToString::to_string).collect();
+        self.shortcut(key, Shortcut::Checkboxes(prompt.into(), items))
+    }
+
+    /// Sets the terminal the wizard is rendered on.
+    pub fn on(&mut self, term: &Term) -> &mut Wizard<'a> {
+        self.term = term.clone();
+        self
+    }
+
+    /// Renders the wizard on stdout.
+    pub fn stdout(&mut self) -> &mut Wizard<'a> {
+        self.term = Term::stdout();
+        self
+    }
+
+    /// Renders the wizard on stderr.
+    pub fn stderr(&mut self) -> &mut Wizard<'a> {
+        self.term = Term::stderr();
+        self
+    }
+
+    /// Runs all steps and returns the collected answers.
+    ///
+    /// The wizard is rendered on the terminal set with `on`.
+    pub fn interact(&self) -> Result<Answers> {
+        self.interact_on(&self.term)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<Answers> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        let mut answers = Answers::default();
+        // the indexes of the answered steps to go back to along with the
+        // text they rendered
+        let mut answered: Vec<(usize, String)> = vec![];
+        let mut accept_all = false;
+        if self.offer_defaults && !self.defaults.is_empty() {
+            let text = localization::with(|l| l.accept_defaults.to_string());
+            accept_all = Confirmation::with_theme(self.theme)
+                .with_text(&text)
+                .default(true)
+                .interact_on(term)?;
+        }
+        let mut idx = 0;
+        while idx < self.steps.len() {
+            let step = &self.steps[idx];
+            if step.skip.as_ref().is_some_and(|skip| skip(&answers)) {
+                idx += 1;
+                continue;
+            }
+            // the steps before this one were either answered or skipped
+            let skipped = idx - answered.len();
+            render.step_header(
+                self.title.as_deref(),
+                answered.len() + 1,
+                self.steps.len() - skipped,
+            )?;
+            let header = render.take_frame();
+            theme::take_reported();
+            let allow_back = self.allow_back && !answered.is_empty();
+            let default = self.defaults.get(&step.key);
+            let answer = match step.prompt {
+                StepPrompt::Fixed(ref prompt) => prompt.ask(term, allow_back)?,
+                StepPrompt::Built(ref build) => build(&answers).ask(term, allow_back)?,
+                StepPrompt::Shortcut(ref shortcut) => {
+                    let accepted = default
+                        .filter(|_| accept_all)
+                        .and_then(|default| shortcut.accept(&answers, default));
+                    match accepted {
+                        Some(answer) => {
+                            shortcut.report(&mut render, &answer)?;
+                            Some(answer)
+                        }
+                        None => {
+                            let prompt = shortcut.build(self.theme, &answers, default);
+                            prompt.ask(term, allow_back)?
+                        }
+                    }
+                }
+            };
+            match answer {
+                Some(answer) => {
+                    answers.push(&step.key, answer);
+                    answered.push((idx, header + &theme::take_reported()));
+                    idx += 1;
+                }
+                None => {
+                    // drop this step and the previous one along with
+                    // their headers
+                    let (prev, rendered) = answered.pop().unwrap_or_default();
+                    render.clear_text(&(rendered + &header + &theme::take_reported()))?;
+                    accept_all = false;
+                    answers.pop();
+                    idx = prev;
+                }
+            }
+        }
+        if let Some((format, ref writer)) = self.output {
+            answers.write(format, &mut *writer.borrow_mut())?;
+        }
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::Key;
+    #[cfg(unix)]
+    use testing::Capture;
+    use testing::Script;
+
+    #[test]
+    fn test_answers() {
+        let mut answers = Answers::default();
+        answers.push("name", Answer::Text("Ann".into()));
+        answers.push("port", Answer::Text("8080".into()));
+        answers.push("tls", Answer::Choice(1, "manual".into()));
+        answers.push(
+            "features",
+            Answer::Choices(vec![(0, "a".into()), (2, "c".into())]),
+        );
+
+        assert_eq!(answers.get("name").and_then(Answer::as_str), Some("Ann"));
+        assert_eq!(answers.parse::<u16>("port"), Some(8080));
+        assert_eq!(answers.get("tls").and_then(Answer::as_index), Some(1));
+        assert_eq!(answers.get("features").unwrap().to_string(), "a, c");
+
+        answers.pop();
+        assert_eq!(answers.get("features"), None);
+        assert_eq!(answers.len(), 3);
+    }
+
+    #[test]
+    fn test_step_with() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line("ann")
+            .expect_prompt("Branch")
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .expect_prompt("Directory")
+            .type_line("")
+            .start()
+            .unwrap();
+        let answers = Wizard::new()
+            .input("name", "Name")
+            .select_with("branch", "Branch", |answers| {
+                let name = answers.get("name").unwrap();
+                vec![format!("{}/main", name), format!("{}/dev", name)]
+            })
+            .input_with("dir", "Directory", |answers| {
+                answers.get("name").map(|name| format!("/home/{}", name))
+            })
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(
+            answers.get("branch"),
+            Some(&Answer::Choice(1, "ann/dev".into()))
+        );
+        assert_eq!(
+            answers.get("dir").and_then(Answer::as_str),
+            Some("/home/ann")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_back() {
+        let (capture, term) = Capture::new();
+        let name = "a".repeat(100);
+        let session = Script::new()
+            .expect_prompt("Name")
+            .type_line(&name)
+            .expect_prompt("TLS")
+            .press(Key::Escape)
+            .expect_prompt("Name")
+            .type_line("ann")
+            .expect_prompt("TLS")
+            .press(Key::Enter)
+            .expect_prompt("Features")
+            .press(Key::Escape)
+            .expect_prompt("TLS")
+            .press(Key::ArrowDown)
+            .press(Key::Enter)
+            .expect_prompt("Features")
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .start()
+            .unwrap();
+        let answers = Wizard::new()
+            .input("name", "Name")
+            .select("tls", "TLS", &["automatic", "manual"])
+            .checkboxes("features", "Features", &["a", "b"])
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(answers.get("name").and_then(Answer::as_str), Some("ann"));
+        assert_eq!(
+            answers.get("tls"),
+            Some(&Answer::Choice(1, "manual".into()))
+        );
+        assert_eq!(
+            answers.get("features").and_then(Answer::as_indexes),
+            Some(vec![0])
+        );
+        // the header and the report of the name wrapped over three rows
+        // along with the header and the cancelled line of the selection
+        let output = capture.output();
+        assert!(output.contains("\x1b[6A[1/3]"));
+        assert!(output.contains("\x1b[4A[2/3]"));
+    }
+
+    #[test]
+    fn test_back_from_input() {
+        let term = Term::buffered_stderr();
+        let session = Script::new()
+            .expect_prompt("Save?")
+            .press(Key::Char('n'))
+            .expect_prompt("Name")
+            .press(Key::Char('x'))
+            .press(Key::Escape)
+            .expect_prompt("Save?")
+            .press(Key::Char('y'))
+            .expect_prompt("Name")
+            .type_line("ann")
+            .start()
+            .unwrap();
+        let answers = Wizard::new()
+            .confirm("save", "Save?")
+            .input("name", "Name")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        assert_eq!(answers.get("save"), Some(&Answer::Bool(true)));
+        assert_eq!(answers.get("name").and_then(Answer::as_str), Some("ann"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_skipped_header() {
+        let (capture, term) = Capture::new();
+        let session = Script::new()
+            .expect_prompt("Save?")
+            .press(Key::Char('y'))
+            .start()
+            .unwrap();
+        Wizard::new()
+            .input("name", "Name")
+            .skip_if(|_| true)
+            .confirm("save", "Save?")
+            .interact_on(&term)
+            .unwrap();
+        session.finish().unwrap();
+        let mut header = String::new();
+        get_default_theme()
+            .format_step_header(&mut header, None, 1, 1)
+            .unwrap();
+        assert!(capture.output().starts_with(&header));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let mut defaults = Answers::default();
+        defaults.push("name", Answer::Text("ann".into()));
+        defaults.push("tls", Answer::Text("manual".into()));
+        defaults.push("features", Answer::Choices(vec![(0, "c".into())]));
+        let term = Term::buffered_stderr();
+        let run = |session: &mut Script| {
+            let session = session.start().unwrap();
+            let answers = Wizard::new()
+                .defaults(defaults.clone())
+                .input("name", "Name")
+                .select("tls", "TLS", &["automatic", "manual"])
+                .checkboxes("features", "Features", &["a", "b", "c"])
+                .input("dir", "Directory")
+                .interact_on(&term)
+                .unwrap();
+            session.finish().unwrap();
+            answers
+        };
+
+        let answers = run(Script::new()
+            .expect_prompt("Accept all defaults?")
+            .press(Key::Enter)
+            .expect_prompt("Directory")
+            .type_line("/srv"));
+        assert_eq!(answers.get("name").and_then(Answer::as_str), Some("ann"));
+        assert_eq!(
+            answers.get("tls"),
+            Some(&Answer::Choice(1, "manual".into()))
+        );
+        assert_eq!(
+            answers.get("features").and_then(Answer::as_indexes),
+            Some(vec![2])
+        );
+        assert_eq!(answers.get("dir").and_then(Answer::as_str), Some("/srv"));
+
+        let answers = run(Script::new()
+            .expect_prompt("Accept all defaults?")
+            .press(Key::Char('n'))
+            .expect_prompt("Name")
+            .type_line("")
+            .expect_prompt("TLS")
+            .press(Key::ArrowUp)
+            .press(Key::Enter)
+            .expect_prompt("Features")
+            .press(Key::Char(' '))
+            .press(Key::Enter)
+            .expect_prompt("Directory")
+            .type_line("/srv"));
+        assert_eq!(answers.get("name").and_then(Answer::as_str), Some("ann"));
+        assert_eq!(
+            answers.get("tls"),
+            Some(&Answer::Choice(0, "automatic".into()))
+        );
+        assert_eq!(
+            answers.get("features").and_then(Answer::as_indexes),
+            Some(vec![0, 2])
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn test_read_json() {
+        let json = r#"{"name": "Ann", "save": true, "features": ["a", "c"]}"#;
+        let answers = Answers::read(AnswersFormat::Json, json.as_bytes()).unwrap();
+        let keys: Vec<_> = answers.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, ["name", "save", "features"]);
+        assert_eq!(answers.get("save"), Some(&Answer::Bool(true)));
+        assert_eq!(answers.get("features").unwrap().to_string(), "a, c");
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn test_write_json() {
+        let mut answers = Answers::default();
+        answers.push("name", Answer::Text("Ann".into()));
+        answers.push("save", Answer::Bool(true));
+        answers.push("tls", Answer::Choice(1, "manual".into()));
+        answers.push("features", Answer::Choices(vec![(0, "a".into())]));
+
+        let mut out = vec![];
+        answers.write(AnswersFormat::Json, &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"name": "Ann", "save": true, "tls": "manual", "features": ["a"]})
+        );
+    }
+}